@@ -1,5 +1,5 @@
-import { getMintSaleStateAddress } from "@/programs/accounts";
-import { getMintSaleProgram } from "@/programs/programs";
+import { getSaleStateAddress } from "@/programs/accounts";
+import { getSaleProgram } from "@/programs/programs";
 import {
   getActionParametersFromDefinition,
   getActionParametersFromRequest,
@@ -71,9 +71,9 @@ export const POST = async (req: Request) => {
     const body: ActionPostRequest = await req.json();
     const signer = new PublicKey(body.account);
 
-    const { program, connection } = getMintSaleProgram();
+    const { program, connection } = getSaleProgram();
 
-    const salePdaAddress = getMintSaleStateAddress(saleName, program);
+    const salePdaAddress = getSaleStateAddress(saleName, program);
     const salePda = await program.account.whitelistSale.fetch(salePdaAddress);
 
     const instruction = await program.methods
@@ -1,5 +1,5 @@
-import { getMintSaleStateAddress } from "@/programs/accounts";
-import { getMintSaleProgram } from "@/programs/programs";
+import { getSaleStateAddress } from "@/programs/accounts";
+import { getSaleProgram, getTokenProgram } from "@/programs/programs";
 import {
   getActionParametersFromDefinition,
   getActionParametersFromRequest,
@@ -70,9 +70,9 @@ export const POST = async (req: Request) => {
     const body: ActionPostRequest = await req.json();
     const signer = new PublicKey(body.account);
 
-    const { program, connection } = getMintSaleProgram();
+    const { program, connection } = getSaleProgram();
 
-    const salePdaAddress = getMintSaleStateAddress(saleName, program);
+    const salePdaAddress = getSaleStateAddress(saleName, program);
     const salePda = await program.account.whitelistSale.fetch(salePdaAddress);
 
     const mint = salePda.tokenMint;
@@ -81,7 +81,13 @@ export const POST = async (req: Request) => {
       .closeWhitelistSale(
         saleName,
       )
-      .accounts({ signer, tokenMint: mint })
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer,
+        tokenMint: mint,
+        tokenProgram: await getTokenProgram(connection, mint),
+      })
       .instruction();
 
     const transaction = new Transaction();
@@ -1,4 +1,4 @@
-import { getMintSaleProgram } from "@/programs/programs";
+import { getSaleProgram, getTokenProgram } from "@/programs/programs";
 import {
   getActionParametersFromDefinition,
   getActionParametersFromRequest,
@@ -75,16 +75,24 @@ export const POST = async (req: Request) => {
     const signer = new PublicKey(body.account);
     const mintAddr = new PublicKey(mint);
 
-    const { program, connection } = getMintSaleProgram();
+    const { program, connection } = getSaleProgram();
 
     const instruction = await program.methods
       .createWhitelistSale(
         saleName,
         new BN(lamportsPerToken),
         new BN(maxTokensPerBuyer),
-        new BN(maxBuyers)
+        new BN(maxBuyers),
+        { mint: {} },
+        0
       )
-      .accounts({ signer, tokenMint: mintAddr })
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer,
+        tokenMint: mintAddr,
+        tokenProgram: await getTokenProgram(connection, mintAddr),
+      })
       .instruction();
 
     const transaction = new Transaction();
@@ -1,4 +1,4 @@
-import { getMintSaleProgram } from "@/programs/programs";
+import { getSaleProgram } from "@/programs/programs";
 import {
   getActionParametersFromDefinition,
   getActionParametersFromRequest,
@@ -67,7 +67,7 @@ export const POST = async (req: Request) => {
     const body: ActionPostRequest = await req.json();
     const signer = new PublicKey(body.account);
 
-    const { program, connection } = getMintSaleProgram();
+    const { program, connection } = getSaleProgram();
 
     const instruction = await program.methods
       .registerForWhitelist(saleName)
@@ -1,5 +1,9 @@
-import { getVaultSaleStateAddress } from "@/programs/accounts";
-import { getVaultSaleProgram } from "@/programs/programs";
+import {
+  getAllowanceAddress,
+  getReferrerStatsAddress,
+  getSaleStateAddress,
+} from "@/programs/accounts";
+import { getSaleProgram, getTokenProgram } from "@/programs/programs";
 import {
   getActionParametersFromDefinition,
   getActionParametersFromRequest,
@@ -79,16 +83,28 @@ export const POST = async (req: Request) => {
     const body: ActionPostRequest = await req.json();
     const signer = new PublicKey(body.account);
 
-    const { program, connection } = getVaultSaleProgram();
+    const { program, connection } = getSaleProgram();
 
-    const salePdaAddress = getVaultSaleStateAddress(saleName, program);
+    const salePdaAddress = getSaleStateAddress(saleName, program);
     const salePda = await program.account.whitelistSale.fetch(salePdaAddress);
 
     const mint = salePda.tokenMint;
 
+    const allowance = await program.account.allowance.fetch(
+      getAllowanceAddress(saleName, signer, program)
+    );
+
     const instruction = await program.methods
       .buyTokens(saleName, new BN(amount))
-      .accounts({ signer, tokenMint: mint })
+      .accounts({
+        signer,
+        tokenMint: mint,
+        tokenProgram: await getTokenProgram(connection, mint),
+        feeRecipient: salePda.feeBps > 0 ? salePda.feeRecipient : null,
+        referrerStats: allowance.referrer
+          ? getReferrerStatsAddress(saleName, allowance.referrer, program)
+          : null,
+      })
       .instruction();
 
     const transaction = new Transaction();
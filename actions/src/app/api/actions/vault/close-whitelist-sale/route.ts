@@ -1,5 +1,5 @@
-import { getVaultSaleStateAddress } from "@/programs/accounts";
-import { getVaultSaleProgram } from "@/programs/programs";
+import { getSaleStateAddress } from "@/programs/accounts";
+import { getSaleProgram, getTokenProgram } from "@/programs/programs";
 import {
   getActionParametersFromDefinition,
   getActionParametersFromRequest,
@@ -70,9 +70,9 @@ export const POST = async (req: Request) => {
     const body: ActionPostRequest = await req.json();
     const signer = new PublicKey(body.account);
 
-    const { program, connection } = getVaultSaleProgram();
+    const { program, connection } = getSaleProgram();
 
-    const salePdaAddress = getVaultSaleStateAddress(saleName, program);
+    const salePdaAddress = getSaleStateAddress(saleName, program);
     const salePda = await program.account.whitelistSale.fetch(salePdaAddress);
 
     const mint = salePda.tokenMint;
@@ -81,7 +81,11 @@ export const POST = async (req: Request) => {
       .closeWhitelistSale(
         saleName,
       )
-      .accounts({ signer, tokenMint: mint })
+      .accounts({
+        signer,
+        tokenMint: mint,
+        tokenProgram: await getTokenProgram(connection, mint),
+      })
       .instruction();
 
     const transaction = new Transaction();
@@ -1,4 +1,4 @@
-import { getVaultSaleProgram } from "@/programs/programs";
+import { getSaleProgram, getTokenProgram } from "@/programs/programs";
 import {
   getActionParametersFromDefinition,
   getActionParametersFromRequest,
@@ -75,16 +75,22 @@ export const POST = async (req: Request) => {
     const signer = new PublicKey(body.account);
     const mintAddr = new PublicKey(mint);
 
-    const { program, connection } = getVaultSaleProgram();
+    const { program, connection } = getSaleProgram();
 
     const instruction = await program.methods
       .createWhitelistSale(
         saleName,
         new BN(lamportsPerToken),
         new BN(maxTokensPerBuyer),
-        new BN(maxBuyers)
+        new BN(maxBuyers),
+        { vault: {} },
+        0
       )
-      .accounts({ signer, tokenMint: mintAddr })
+      .accounts({
+        signer,
+        tokenMint: mintAddr,
+        tokenProgram: await getTokenProgram(connection, mintAddr),
+      })
       .instruction();
 
     const transaction = new Transaction();
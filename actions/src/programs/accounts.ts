@@ -1,9 +1,8 @@
 import { Program } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
-import { WhitelistTokenSaleMint } from "./types/whitelist_token_sale_mint";
-import { WhitelistTokenSaleVault } from "./types/whitelist_token_sale_vault";
+import { WhitelistTokenSale } from "./types/whitelist_token_sale";
 
-export function getMintSaleStateAddress(name: string, program: Program<WhitelistTokenSaleMint>) {
+export function getSaleStateAddress(name: string, program: Program<WhitelistTokenSale>) {
   const [address, _bump] = PublicKey.findProgramAddressSync(
     [Buffer.from("sale"), Buffer.from(name)],
     program.programId
@@ -12,22 +11,26 @@ export function getMintSaleStateAddress(name: string, program: Program<Whitelist
   return address;
 }
 
-export function getVaultSaleStateAddress(name: string, program: Program<WhitelistTokenSaleVault>) {
+export function getAllowanceAddress(
+  name: string,
+  pubkey: PublicKey,
+  program: Program<WhitelistTokenSale>
+) {
   const [address, _bump] = PublicKey.findProgramAddressSync(
-    [Buffer.from("sale"), Buffer.from(name)],
+    [Buffer.from("allowance"), Buffer.from(name), pubkey.toBytes()],
     program.programId
   );
 
   return address;
 }
 
-export function getMintAllowanceAddress(
+export function getReferrerStatsAddress(
   name: string,
-  pubkey: PublicKey,
-  program: Program<WhitelistTokenSaleMint>
+  referrer: PublicKey,
+  program: Program<WhitelistTokenSale>
 ) {
   const [address, _bump] = PublicKey.findProgramAddressSync(
-    [Buffer.from("allowance"), Buffer.from(name), pubkey.toBytes()],
+    [Buffer.from("referrer"), Buffer.from(name), referrer.toBytes()],
     program.programId
   );
 
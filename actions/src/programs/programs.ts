@@ -1,15 +1,13 @@
-import { WhitelistTokenSaleMint } from "./types/whitelist_token_sale_mint";
-import { WhitelistTokenSaleVault } from "./types/whitelist_token_sale_vault";
-import mintIdl from "./idl/whitelist_token_sale_mint.json";
-import vaultIdl from "./idl/whitelist_token_sale_vault.json";
+import { WhitelistTokenSale } from "./types/whitelist_token_sale";
+import idl from "./idl/whitelist_token_sale.json";
 import { Program, web3 } from "@coral-xyz/anchor";
-import { Cluster } from "@solana/web3.js";
+import { Cluster, PublicKey } from "@solana/web3.js";
 
-export function getMintSaleProgram() {
+export function getSaleProgram() {
   const connection = new web3.Connection(web3.clusterApiUrl(process.env.NEXT_PUBLIC_CLUSTER as Cluster));
 
-  const program = new Program<WhitelistTokenSaleMint>(
-    mintIdl as WhitelistTokenSaleMint,
+  const program = new Program<WhitelistTokenSale>(
+    idl as WhitelistTokenSale,
     {
       connection,
     }
@@ -18,15 +16,13 @@ export function getMintSaleProgram() {
   return { program, connection };
 }
 
-export function getVaultSaleProgram() {
-  const connection = new web3.Connection(web3.clusterApiUrl(process.env.NEXT_PUBLIC_CLUSTER as Cluster));
+// Token program owning the mint, either SPL token or Token-2022
+export async function getTokenProgram(connection: web3.Connection, mint: PublicKey) {
+  const mintAccount = await connection.getAccountInfo(mint);
 
-  const program = new Program<WhitelistTokenSaleVault>(
-    vaultIdl as WhitelistTokenSaleVault,
-    {
-      connection,
-    }
-  );
+  if (!mintAccount) {
+    throw new Error(`Mint ${mint} doesn't exist`);
+  }
 
-  return { program, connection };
-}
\ No newline at end of file
+  return mintAccount.owner;
+}
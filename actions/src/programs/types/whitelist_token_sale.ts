@@ -0,0 +1,7212 @@
+/**
+ * Program IDL in camelCase format in order to be used in JS/TS.
+ *
+ * Note that this is only a type helper and is not the actual IDL. The original
+ * IDL can be found at `target/idl/whitelist_token_sale.json`.
+ */
+export type WhitelistTokenSale = {
+  "address": "4KSUrirLpTrGMmgKNCcXtv7wsJ2kdfPTZJdtU4k4ABHs",
+  "metadata": {
+    "name": "whitelistTokenSale",
+    "version": "0.1.0",
+    "spec": "0.1.0",
+    "description": "Created with Anchor"
+  },
+  "instructions": [
+    {
+      "name": "acceptOwnership",
+      "discriminator": [
+        172,
+        23,
+        43,
+        13,
+        238,
+        213,
+        85,
+        150
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "addToWhitelist",
+      "discriminator": [
+        157,
+        211,
+        52,
+        54,
+        144,
+        81,
+        5,
+        55
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "buyer"
+        },
+        {
+          "name": "allowance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  108,
+                  108,
+                  111,
+                  119,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              },
+              {
+                "kind": "account",
+                "path": "buyer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "buyTokens",
+      "discriminator": [
+        189,
+        21,
+        230,
+        133,
+        247,
+        2,
+        110,
+        42
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "allowance",
+          "docs": [
+            "Created on the fly for buyers purchasing in phases that don't require the whitelist"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  108,
+                  108,
+                  111,
+                  119,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultAta",
+          "docs": [
+            "Only used by vault sales"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "sale"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "signerAta",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "signer"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "tokenMint",
+          "docs": [
+            "Mutable for mint sales, which mint the tokens bought"
+          ],
+          "writable": true
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "signerPaymentAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "signer"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "proceedsAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "sale"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "feeRecipient",
+          "docs": [
+            "Only used by sales created with a platform fee."
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "feePaymentAta",
+          "docs": [
+            "Only used by sales created with a platform fee, when paid in the payment mint"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "feeRecipient"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "referrerStats",
+          "docs": [
+            "Only used by buyers who registered with a referrer, accruing the referrer's reward"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "paymentTokenProgram",
+          "docs": [
+            "Token program of the payment mint, which can differ from the sold mint's"
+          ],
+          "optional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "claimRebate",
+      "discriminator": [
+        180,
+        77,
+        169,
+        215,
+        170,
+        162,
+        242,
+        1
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "allowance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  108,
+                  108,
+                  111,
+                  119,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "signerPaymentAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "signer"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "proceedsAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "sale"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "paymentTokenProgram",
+          "docs": [
+            "Token program of the payment mint"
+          ],
+          "optional": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "claimReferralRewards",
+      "discriminator": [
+        23,
+        112,
+        76,
+        162,
+        157,
+        106,
+        203,
+        246
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "referrerStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  102,
+                  101,
+                  114,
+                  114,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "signerPaymentAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "signer"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "proceedsAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "sale"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "paymentTokenProgram",
+          "docs": [
+            "Token program of the payment mint"
+          ],
+          "optional": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "claimRefund",
+      "discriminator": [
+        15,
+        16,
+        30,
+        161,
+        255,
+        228,
+        97,
+        60
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "allowance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  108,
+                  108,
+                  111,
+                  119,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenMint"
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "signerPaymentAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "signer"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "proceedsAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "sale"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "paymentTokenProgram",
+          "docs": [
+            "Token program of the payment mint"
+          ],
+          "optional": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "claimVested",
+      "discriminator": [
+        208,
+        190,
+        166,
+        114,
+        203,
+        225,
+        140,
+        208
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "allowance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  108,
+                  108,
+                  111,
+                  119,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultAta",
+          "docs": [
+            "Only used by vault sales"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "sale"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "signerAta",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "signer"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "tokenMint",
+          "docs": [
+            "Mutable for mint sales, which only mint held back tokens once they're claimed"
+          ],
+          "writable": true
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "closeWhitelistSale",
+      "discriminator": [
+        122,
+        254,
+        201,
+        184,
+        41,
+        230,
+        0,
+        15
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultAta",
+          "docs": [
+            "Only used by vault sales"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "sale"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "paymentTokenProgram",
+          "docs": [
+            "Token program of the payment mint, which can differ from the sold mint's"
+          ],
+          "optional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "tokenMint",
+          "docs": [
+            "Mutable for mint sales, which hand its mint authority back to the owner"
+          ],
+          "writable": true
+        },
+        {
+          "name": "signerAta",
+          "docs": [
+            "Only used by vault sales, receiving the tokens left in the vault"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "signer"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "proceedsAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "sale"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "signerPaymentAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "signer"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "commit",
+      "discriminator": [
+        223,
+        140,
+        142,
+        165,
+        229,
+        208,
+        156,
+        74
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "allowance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  108,
+                  108,
+                  111,
+                  119,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "signerPaymentAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "signer"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "proceedsAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "sale"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "paymentTokenProgram",
+          "docs": [
+            "Token program of the payment mint"
+          ],
+          "optional": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "createWhitelistSale",
+      "discriminator": [
+        250,
+        194,
+        137,
+        75,
+        201,
+        218,
+        133,
+        141
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "paymentTokenProgram",
+          "docs": [
+            "Token program of the payment mint, which can differ from the sold mint's"
+          ],
+          "optional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "tokenMint",
+          "docs": [
+            "Mutable for mint sales, which take over its mint authority"
+          ],
+          "writable": true
+        },
+        {
+          "name": "vaultAta",
+          "docs": [
+            "Only used by vault sales, holding the tokens for sale"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "sale"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "signerAta",
+          "docs": [
+            "Only used by vault sales, funding the vault"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "signer"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "paymentMint",
+          "docs": [
+            "Set to have buyers pay in this mint instead of SOL"
+          ],
+          "optional": true
+        },
+        {
+          "name": "proceedsAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "sale"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "lamportsPerToken",
+          "type": "u64"
+        },
+        {
+          "name": "maxTokensPerBuyer",
+          "type": "u64"
+        },
+        {
+          "name": "maxBuyers",
+          "type": "u64"
+        },
+        {
+          "name": "saleMode",
+          "type": {
+            "defined": {
+              "name": "saleMode"
+            }
+          }
+        },
+        {
+          "name": "referralBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "drawLottery",
+      "discriminator": [
+        17,
+        188,
+        124,
+        77,
+        90,
+        34,
+        97,
+        19
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "randomnessAccount"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "finalizeSale",
+      "discriminator": [
+        62,
+        138,
+        254,
+        160,
+        192,
+        113,
+        177,
+        58
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "proceedsAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "sale"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "feeRecipient",
+          "docs": [
+            "Only used by sales created with a platform fee."
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "feePaymentAta",
+          "docs": [
+            "Only used by sales created with a platform fee, when paid in the payment mint"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "feeRecipient"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "paymentTokenProgram",
+          "docs": [
+            "Token program of the payment mint"
+          ],
+          "optional": true
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "initializeConfig",
+      "discriminator": [
+        208,
+        127,
+        21,
+        1,
+        194,
+        190,
+        196,
+        70
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "program",
+          "address": "4KSUrirLpTrGMmgKNCcXtv7wsJ2kdfPTZJdtU4k4ABHs"
+        },
+        {
+          "name": "programData"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "admin",
+          "type": "pubkey"
+        },
+        {
+          "name": "feeRecipient",
+          "type": "pubkey"
+        },
+        {
+          "name": "feeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "migrateAllowance",
+      "discriminator": [
+        103,
+        232,
+        37,
+        3,
+        117,
+        13,
+        2,
+        174
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "docs": [
+            "Has to be migrated first"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "allowance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  108,
+                  108,
+                  111,
+                  119,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              },
+              {
+                "kind": "arg",
+                "path": "buyer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "docs": [
+            "Pays for the extra rent, whether it's the buyer or anyone else"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "buyer",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "migrateWhitelistSale",
+      "discriminator": [
+        179,
+        214,
+        201,
+        123,
+        6,
+        23,
+        74,
+        76
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "proposeOwner",
+      "discriminator": [
+        90,
+        57,
+        141,
+        110,
+        196,
+        241,
+        172,
+        39
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "pendingOwner",
+          "type": {
+            "option": "pubkey"
+          }
+        }
+      ]
+    },
+    {
+      "name": "registerForWhitelist",
+      "discriminator": [
+        103,
+        170,
+        102,
+        132,
+        84,
+        67,
+        94,
+        16
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "allowance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  108,
+                  108,
+                  111,
+                  119,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "instructionsSysvar",
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        },
+        {
+          "name": "referrer",
+          "optional": true
+        },
+        {
+          "name": "referrerAllowance",
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  108,
+                  108,
+                  111,
+                  119,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              },
+              {
+                "kind": "account",
+                "path": "referrer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "referrerStats",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  102,
+                  101,
+                  114,
+                  114,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              },
+              {
+                "kind": "account",
+                "path": "referrer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "registerWithMerkleProof",
+      "discriminator": [
+        8,
+        113,
+        218,
+        224,
+        131,
+        235,
+        204,
+        208
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "allowance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  108,
+                  108,
+                  111,
+                  119,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "allocation",
+          "type": "u64"
+        },
+        {
+          "name": "proof",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "removeFromWhitelist",
+      "discriminator": [
+        7,
+        144,
+        216,
+        239,
+        243,
+        236,
+        193,
+        235
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "allowance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  108,
+                  108,
+                  111,
+                  119,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              },
+              {
+                "kind": "account",
+                "path": "allowance.buyer",
+                "account": "allowance"
+              }
+            ]
+          }
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "setCommitMode",
+      "discriminator": [
+        194,
+        113,
+        157,
+        55,
+        136,
+        225,
+        176,
+        167
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "isCommitMode",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setOperator",
+      "discriminator": [
+        238,
+        153,
+        101,
+        169,
+        243,
+        131,
+        36,
+        1
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "operator",
+          "type": {
+            "option": "pubkey"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setPricingMode",
+      "discriminator": [
+        174,
+        156,
+        112,
+        129,
+        111,
+        151,
+        226,
+        228
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "pricingMode",
+          "type": {
+            "defined": {
+              "name": "pricingMode"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setRandomnessSource",
+      "discriminator": [
+        211,
+        136,
+        252,
+        240,
+        143,
+        148,
+        245,
+        106
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "randomnessSource",
+          "type": {
+            "defined": {
+              "name": "randomnessSource"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setSaleCaps",
+      "discriminator": [
+        248,
+        0,
+        189,
+        77,
+        137,
+        121,
+        131,
+        205
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "totalTokensForSale",
+          "type": "u64"
+        },
+        {
+          "name": "hardCapLamports",
+          "type": {
+            "option": "u64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setSalePhase",
+      "discriminator": [
+        173,
+        143,
+        183,
+        221,
+        215,
+        168,
+        246,
+        61
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "index",
+          "type": "u8"
+        },
+        {
+          "name": "start",
+          "type": "i64"
+        },
+        {
+          "name": "end",
+          "type": "i64"
+        },
+        {
+          "name": "lamportsPerToken",
+          "type": "u64"
+        },
+        {
+          "name": "maxTokensPerBuyer",
+          "type": "u64"
+        },
+        {
+          "name": "maxTokens",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "requiresWhitelist",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setSaleWindows",
+      "discriminator": [
+        92,
+        74,
+        173,
+        12,
+        197,
+        217,
+        58,
+        120
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "registrationStart",
+          "type": {
+            "option": "i64"
+          }
+        },
+        {
+          "name": "registrationEnd",
+          "type": {
+            "option": "i64"
+          }
+        },
+        {
+          "name": "saleStart",
+          "type": {
+            "option": "i64"
+          }
+        },
+        {
+          "name": "saleEnd",
+          "type": {
+            "option": "i64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setSoftCap",
+      "discriminator": [
+        32,
+        127,
+        137,
+        78,
+        251,
+        80,
+        3,
+        9
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "softCap",
+          "type": "u64"
+        },
+        {
+          "name": "deadline",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "setTreasury",
+      "discriminator": [
+        57,
+        97,
+        196,
+        95,
+        195,
+        206,
+        106,
+        136
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "treasury",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "setVestingSchedule",
+      "discriminator": [
+        149,
+        168,
+        210,
+        86,
+        2,
+        27,
+        168,
+        83
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "vesting",
+          "type": {
+            "option": {
+              "defined": {
+                "name": "vestingSchedule"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setWhitelistMode",
+      "discriminator": [
+        148,
+        176,
+        44,
+        35,
+        39,
+        214,
+        58,
+        190
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "whitelistMode",
+          "type": {
+            "defined": {
+              "name": "whitelistMode"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setWhitelistSigner",
+      "discriminator": [
+        24,
+        206,
+        103,
+        122,
+        205,
+        153,
+        115,
+        253
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "whitelistSigner",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "settleCommitment",
+      "discriminator": [
+        131,
+        217,
+        252,
+        92,
+        136,
+        78,
+        89,
+        78
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "allowance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  108,
+                  108,
+                  111,
+                  119,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenMint"
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "signerPaymentAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "signer"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "proceedsAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "sale"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "feeRecipient",
+          "docs": [
+            "Only used by sales created with a platform fee."
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "feePaymentAta",
+          "docs": [
+            "Only used by sales created with a platform fee, when paid in the payment mint"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "feeRecipient"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "referrerStats",
+          "docs": [
+            "Only used by buyers who registered with a referrer, accruing the referrer's reward"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "paymentTokenProgram",
+          "docs": [
+            "Token program of the payment mint"
+          ],
+          "optional": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "settleLotteryTicket",
+      "discriminator": [
+        124,
+        48,
+        54,
+        206,
+        189,
+        141,
+        195,
+        228
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "allowance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  108,
+                  108,
+                  111,
+                  119,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "updateConfig",
+      "discriminator": [
+        29,
+        158,
+        252,
+        191,
+        10,
+        83,
+        219,
+        99
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "admin",
+          "type": "pubkey"
+        },
+        {
+          "name": "feeRecipient",
+          "type": "pubkey"
+        },
+        {
+          "name": "feeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "updateMerkleRoot",
+      "discriminator": [
+        195,
+        173,
+        38,
+        60,
+        242,
+        203,
+        158,
+        93
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "merkleRoot",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "updateSaleState",
+      "discriminator": [
+        178,
+        28,
+        126,
+        89,
+        175,
+        38,
+        196,
+        30
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        },
+        {
+          "name": "isRegistrationOpen",
+          "type": "bool"
+        },
+        {
+          "name": "isSaleOpen",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "withdrawProceeds",
+      "discriminator": [
+        124,
+        68,
+        215,
+        12,
+        201,
+        136,
+        54,
+        72
+      ],
+      "accounts": [
+        {
+          "name": "sale",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  97,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "saleName"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "treasury",
+          "writable": true
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "proceedsAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "sale"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "treasuryPaymentAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "treasury"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "paymentTokenProgram",
+          "optional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "saleName",
+          "type": "string"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "allowance",
+      "discriminator": [
+        144,
+        8,
+        184,
+        213,
+        49,
+        248,
+        73,
+        131
+      ]
+    },
+    {
+      "name": "programConfig",
+      "discriminator": [
+        196,
+        210,
+        90,
+        231,
+        144,
+        149,
+        140,
+        63
+      ]
+    },
+    {
+      "name": "referrerStats",
+      "discriminator": [
+        181,
+        235,
+        242,
+        229,
+        103,
+        242,
+        144,
+        118
+      ]
+    },
+    {
+      "name": "whitelistSale",
+      "discriminator": [
+        61,
+        244,
+        247,
+        94,
+        6,
+        19,
+        119,
+        68
+      ]
+    }
+  ],
+  "events": [
+    {
+      "name": "buyerRegistered",
+      "discriminator": [
+        136,
+        93,
+        200,
+        253,
+        46,
+        158,
+        16,
+        74
+      ]
+    },
+    {
+      "name": "buyerRemoved",
+      "discriminator": [
+        97,
+        162,
+        6,
+        197,
+        53,
+        145,
+        45,
+        0
+      ]
+    },
+    {
+      "name": "commitmentSettled",
+      "discriminator": [
+        55,
+        253,
+        253,
+        55,
+        211,
+        151,
+        103,
+        56
+      ]
+    },
+    {
+      "name": "lotteryTicketSettled",
+      "discriminator": [
+        164,
+        96,
+        85,
+        83,
+        240,
+        19,
+        203,
+        239
+      ]
+    },
+    {
+      "name": "operatorUpdated",
+      "discriminator": [
+        28,
+        104,
+        226,
+        145,
+        253,
+        229,
+        17,
+        245
+      ]
+    },
+    {
+      "name": "ownershipProposed",
+      "discriminator": [
+        128,
+        116,
+        158,
+        165,
+        202,
+        8,
+        153,
+        162
+      ]
+    },
+    {
+      "name": "ownershipTransferred",
+      "discriminator": [
+        172,
+        61,
+        205,
+        183,
+        250,
+        50,
+        38,
+        98
+      ]
+    },
+    {
+      "name": "paymentCommitted",
+      "discriminator": [
+        41,
+        129,
+        140,
+        120,
+        105,
+        85,
+        45,
+        121
+      ]
+    },
+    {
+      "name": "proceedsWithdrawn",
+      "discriminator": [
+        39,
+        167,
+        165,
+        1,
+        8,
+        206,
+        214,
+        13
+      ]
+    },
+    {
+      "name": "rebateClaimed",
+      "discriminator": [
+        225,
+        106,
+        198,
+        84,
+        60,
+        69,
+        42,
+        135
+      ]
+    },
+    {
+      "name": "referralRewardsClaimed",
+      "discriminator": [
+        178,
+        107,
+        76,
+        169,
+        252,
+        154,
+        45,
+        235
+      ]
+    },
+    {
+      "name": "refundClaimed",
+      "discriminator": [
+        136,
+        64,
+        242,
+        99,
+        4,
+        244,
+        208,
+        130
+      ]
+    },
+    {
+      "name": "saleClosed",
+      "discriminator": [
+        159,
+        55,
+        174,
+        68,
+        129,
+        10,
+        121,
+        96
+      ]
+    },
+    {
+      "name": "saleCreated",
+      "discriminator": [
+        164,
+        187,
+        32,
+        35,
+        143,
+        167,
+        235,
+        132
+      ]
+    },
+    {
+      "name": "saleFinalized",
+      "discriminator": [
+        40,
+        86,
+        126,
+        227,
+        165,
+        195,
+        95,
+        182
+      ]
+    },
+    {
+      "name": "saleStateUpdated",
+      "discriminator": [
+        85,
+        216,
+        74,
+        153,
+        238,
+        61,
+        87,
+        145
+      ]
+    },
+    {
+      "name": "tokensClaimed",
+      "discriminator": [
+        25,
+        128,
+        244,
+        55,
+        241,
+        136,
+        200,
+        91
+      ]
+    },
+    {
+      "name": "tokensPurchased",
+      "discriminator": [
+        214,
+        119,
+        105,
+        186,
+        114,
+        205,
+        228,
+        181
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "onlyOwner",
+      "msg": "Only the sale owner can perform this action"
+    },
+    {
+      "code": 6001,
+      "name": "onlyOperator",
+      "msg": "Only the sale owner or its operator can perform this action"
+    },
+    {
+      "code": 6002,
+      "name": "whitelistRegistrationClosed",
+      "msg": "Whitelist registration is closed"
+    },
+    {
+      "code": 6003,
+      "name": "saleClosed",
+      "msg": "Whiteliste sale is closed"
+    },
+    {
+      "code": 6004,
+      "name": "allowanceExceeded",
+      "msg": "Token purchase is larger than remaining allowance"
+    },
+    {
+      "code": 6005,
+      "name": "mintMismatch",
+      "msg": "Mismatch in mints of provided accounts"
+    },
+    {
+      "code": 6006,
+      "name": "buyerLimitReached",
+      "msg": "The maximum number of registered buyers has been reached"
+    },
+    {
+      "code": 6007,
+      "name": "notWhitelisted",
+      "msg": "Buyer has not been approved for this whitelist"
+    },
+    {
+      "code": 6008,
+      "name": "selfRegistrationDisabled",
+      "msg": "Buyers can't register themselves for a curated whitelist"
+    },
+    {
+      "code": 6009,
+      "name": "whitelistModeLocked",
+      "msg": "Whitelist mode can't be changed once buyers are registered"
+    },
+    {
+      "code": 6010,
+      "name": "whitelistModeMismatch",
+      "msg": "This action isn't available in the sale's whitelist mode"
+    },
+    {
+      "code": 6011,
+      "name": "invalidRemainingAccounts",
+      "msg": "Remaining accounts don't match the expected layout"
+    },
+    {
+      "code": 6012,
+      "name": "invalidMerkleProof",
+      "msg": "Merkle proof doesn't match the sale's whitelist root"
+    },
+    {
+      "code": 6013,
+      "name": "invalidVoucher",
+      "msg": "Missing or invalid whitelist voucher"
+    },
+    {
+      "code": 6014,
+      "name": "voucherExpired",
+      "msg": "Whitelist voucher has expired"
+    },
+    {
+      "code": 6015,
+      "name": "missingPaymentAccounts",
+      "msg": "Payment mint and token accounts are required for this sale"
+    },
+    {
+      "code": 6016,
+      "name": "invalidTimeWindow",
+      "msg": "Time window must end after it starts"
+    },
+    {
+      "code": 6017,
+      "name": "phaseAlreadyStarted",
+      "msg": "Phases can only be added or edited before they start"
+    },
+    {
+      "code": 6018,
+      "name": "invalidPhase",
+      "msg": "Phase doesn't exist or the sale has no room for more phases"
+    },
+    {
+      "code": 6019,
+      "name": "phaseOverlap",
+      "msg": "Phase overlaps another phase of the sale"
+    },
+    {
+      "code": 6020,
+      "name": "phaseSoldOut",
+      "msg": "All tokens of the current phase have been sold"
+    },
+    {
+      "code": 6021,
+      "name": "invalidVestingSchedule",
+      "msg": "Vesting schedule is invalid"
+    },
+    {
+      "code": 6022,
+      "name": "vestingLocked",
+      "msg": "Vesting schedule can't be changed once buyers have held back or claimed tokens"
+    },
+    {
+      "code": 6023,
+      "name": "claimsOutstanding",
+      "msg": "Sale can't be closed while buyers have unclaimed tokens, refunds or rebates"
+    },
+    {
+      "code": 6024,
+      "name": "nothingToClaim",
+      "msg": "There are no unlocked tokens to claim"
+    },
+    {
+      "code": 6025,
+      "name": "softCapLocked",
+      "msg": "Soft cap can't be changed once the sale has raised funds"
+    },
+    {
+      "code": 6026,
+      "name": "softCapNotReached",
+      "msg": "Soft cap hasn't been reached"
+    },
+    {
+      "code": 6027,
+      "name": "saleNotFinalized",
+      "msg": "Sale hasn't been finalized"
+    },
+    {
+      "code": 6028,
+      "name": "refundUnavailable",
+      "msg": "Refunds are only available when the soft cap was missed by the deadline"
+    },
+    {
+      "code": 6029,
+      "name": "saleSoldOut",
+      "msg": "All tokens for sale have been sold"
+    },
+    {
+      "code": 6030,
+      "name": "hardCapReached",
+      "msg": "Purchase would exceed the sale's hard cap"
+    },
+    {
+      "code": 6031,
+      "name": "invalidSaleCaps",
+      "msg": "Sale caps must cover what has been sold or raised and fit the tokens available"
+    },
+    {
+      "code": 6032,
+      "name": "invalidPricingMode",
+      "msg": "Pricing mode is invalid or conflicts with the sale's phases"
+    },
+    {
+      "code": 6033,
+      "name": "pricingModeLocked",
+      "msg": "Pricing mode can't be changed once tokens are sold"
+    },
+    {
+      "code": 6034,
+      "name": "rebateUnavailable",
+      "msg": "Rebates are only available once a uniform clearing auction is over"
+    },
+    {
+      "code": 6035,
+      "name": "priceOverflow",
+      "msg": "Purchase cost is too large to be represented"
+    },
+    {
+      "code": 6036,
+      "name": "commitModeMismatch",
+      "msg": "This action isn't available with the sale's commit mode setting"
+    },
+    {
+      "code": 6037,
+      "name": "commitModeLocked",
+      "msg": "Commit mode and its supply can't be changed once tokens are committed or settled"
+    },
+    {
+      "code": 6038,
+      "name": "commitModeConflict",
+      "msg": "Commit mode can't be combined with phases, pricing modes or a soft cap"
+    },
+    {
+      "code": 6039,
+      "name": "invalidRandomnessSource",
+      "msg": "Randomness account doesn't match the sale's randomness source"
+    },
+    {
+      "code": 6040,
+      "name": "randomnessUnavailable",
+      "msg": "Randomness hasn't been provided yet"
+    },
+    {
+      "code": 6041,
+      "name": "lotteryAlreadyDrawn",
+      "msg": "Lottery has already been drawn"
+    },
+    {
+      "code": 6042,
+      "name": "lotteryNotDrawn",
+      "msg": "Lottery hasn't been drawn yet"
+    },
+    {
+      "code": 6043,
+      "name": "missingVaultAccounts",
+      "msg": "Vault token accounts are required for this sale"
+    },
+    {
+      "code": 6044,
+      "name": "saleAlreadyMigrated",
+      "msg": "Sale account doesn't need to be migrated"
+    },
+    {
+      "code": 6045,
+      "name": "allowanceAlreadyMigrated",
+      "msg": "Allowance account doesn't need to be migrated"
+    },
+    {
+      "code": 6046,
+      "name": "treasuryMismatch",
+      "msg": "Account doesn't match the sale's treasury"
+    },
+    {
+      "code": 6047,
+      "name": "nothingToWithdraw",
+      "msg": "There are no proceeds available to withdraw"
+    },
+    {
+      "code": 6048,
+      "name": "onlyUpgradeAuthority",
+      "msg": "Only the program's upgrade authority can perform this action"
+    },
+    {
+      "code": 6049,
+      "name": "onlyAdmin",
+      "msg": "Only the program config's admin can perform this action"
+    },
+    {
+      "code": 6050,
+      "name": "invalidFee",
+      "msg": "Fee can't be more than 100%"
+    },
+    {
+      "code": 6051,
+      "name": "feeRecipientMismatch",
+      "msg": "Account doesn't match the sale's fee recipient"
+    },
+    {
+      "code": 6052,
+      "name": "missingFeeAccounts",
+      "msg": "Fee recipient accounts are required for this sale"
+    },
+    {
+      "code": 6053,
+      "name": "invalidReferralFee",
+      "msg": "Referral and platform fees can't add up to more than 100%"
+    },
+    {
+      "code": 6054,
+      "name": "selfReferral",
+      "msg": "Buyers can't refer themselves"
+    },
+    {
+      "code": 6055,
+      "name": "referrerNotRegistered",
+      "msg": "Referrers have to register for the sale before the buyers they refer"
+    },
+    {
+      "code": 6056,
+      "name": "referrerMismatch",
+      "msg": "Referrer accounts are missing or don't match the buyer's referrer"
+    },
+    {
+      "code": 6057,
+      "name": "referralRewardsUnavailable",
+      "msg": "Referral rewards are only available once the sale's soft cap is reached"
+    },
+    {
+      "code": 6058,
+      "name": "onlyPendingOwner",
+      "msg": "Only the sale's pending owner can accept ownership"
+    },
+    {
+      "code": 6059,
+      "name": "randomnessSourceLocked",
+      "msg": "Randomness source can't be changed once lottery tickets are registered"
+    }
+  ],
+  "types": [
+    {
+      "name": "allowance",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "buyer",
+            "type": "pubkey"
+          },
+          {
+            "name": "tokensBought",
+            "type": "u64"
+          },
+          {
+            "name": "isApproved",
+            "type": "bool"
+          },
+          {
+            "name": "allocation",
+            "docs": [
+              "Overrides `max_tokens_per_buyer` for this buyer when set"
+            ],
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "phaseTokensBought",
+            "docs": [
+              "Tokens bought in each of the sale's phases"
+            ],
+            "type": {
+              "array": [
+                "u64",
+                4
+              ]
+            }
+          },
+          {
+            "name": "tokensHeld",
+            "docs": [
+              "Tokens bought but held back by the sale until they're claimed"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "tokensClaimed",
+            "docs": [
+              "Held back tokens already claimed, in base units"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lamportsPaid",
+            "docs": [
+              "Payments made by the buyer, in lamports or the payment mint's base units"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lamportsCommitted",
+            "docs": [
+              "Payment committed by the buyer in commit mode that hasn't been settled yet"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "ticketNumber",
+            "docs": [
+              "Buyer's ticket when the sale's whitelist is a lottery"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "referrer",
+            "docs": [
+              "Earns a share of the buyer's payments when set"
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "buyerRegistered",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "buyer",
+            "type": "pubkey"
+          },
+          {
+            "name": "isApproved",
+            "docs": [
+              "Lottery registrations are only approved once their ticket wins the draw"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "allocation",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "referrer",
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "numBuyers",
+            "type": "u64"
+          },
+          {
+            "name": "numTickets",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "buyerRemoved",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "buyer",
+            "type": "pubkey"
+          },
+          {
+            "name": "numBuyers",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "commitmentSettled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "buyer",
+            "type": "pubkey"
+          },
+          {
+            "name": "tokens",
+            "docs": [
+              "Whole tokens allocated, held back until `claim_vested`"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "cost",
+            "docs": [
+              "Share of the commitment paid for the allocated tokens"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "refund",
+            "docs": [
+              "Rest of the commitment, paid back to the buyer"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "fee",
+            "docs": [
+              "Share of the cost paid to the platform's fee recipient"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "referralReward",
+            "docs": [
+              "Share of the cost owed to the buyer's referrer"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "decayCurve",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "linear"
+          },
+          {
+            "name": "exponential",
+            "fields": [
+              {
+                "name": "halfLifeSeconds",
+                "type": "i64"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "dutchAuction",
+      "docs": [
+        "Price falls over time from `start_price` towards `floor_price`, which it reaches when the auction ends.",
+        "With uniform clearing, every buyer ends up paying the last price tokens sold at."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "startPrice",
+            "type": "u64"
+          },
+          {
+            "name": "floorPrice",
+            "type": "u64"
+          },
+          {
+            "name": "startTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "endTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "curve",
+            "type": {
+              "defined": {
+                "name": "decayCurve"
+              }
+            }
+          },
+          {
+            "name": "uniformClearing",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "exponentialCurve",
+      "docs": [
+        "Bonding curve where each token sold costs `growth_bps` more than the previous one,",
+        "i.e. the n-th token sold (counting from 0) costs `base_price * (1 + growth_bps / 10000)^n`"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "basePrice",
+            "type": "u64"
+          },
+          {
+            "name": "growthBps",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "linearCurve",
+      "docs": [
+        "Bonding curve where the n-th token sold (counting from 0) costs `base_price + slope * n`"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "basePrice",
+            "type": "u64"
+          },
+          {
+            "name": "slope",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "lotteryTicketSettled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "buyer",
+            "type": "pubkey"
+          },
+          {
+            "name": "ticketNumber",
+            "type": "u64"
+          },
+          {
+            "name": "isWinner",
+            "docs": [
+              "Winners are approved, while losing tickets are closed to give their rent back"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "numBuyers",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "operatorUpdated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "operator",
+            "docs": [
+              "Cleared when the operator is removed"
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ownershipProposed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingOwner",
+            "docs": [
+              "Cleared when the proposal is withdrawn"
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ownershipTransferred",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "previousOwner",
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "paymentCommitted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "buyer",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "docs": [
+              "Paid in lamports, or in base units of the sale's payment mint"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "buyerCommitted",
+            "type": "u64"
+          },
+          {
+            "name": "totalCommitted",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "pricingMode",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "fixed"
+          },
+          {
+            "name": "dutchAuction",
+            "fields": [
+              {
+                "defined": {
+                  "name": "dutchAuction"
+                }
+              }
+            ]
+          },
+          {
+            "name": "linearCurve",
+            "fields": [
+              {
+                "defined": {
+                  "name": "linearCurve"
+                }
+              }
+            ]
+          },
+          {
+            "name": "exponentialCurve",
+            "fields": [
+              {
+                "defined": {
+                  "name": "exponentialCurve"
+                }
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "proceedsWithdrawn",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "treasury",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "docs": [
+              "Paid in lamports, or in base units of the sale's payment mint"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lamportsWithdrawn",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "programConfig",
+      "docs": [
+        "Settings shared by every sale, created by the program's upgrade authority"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "docs": [
+              "Can update the config once it's created"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "feeRecipient",
+            "type": "pubkey"
+          },
+          {
+            "name": "feeBps",
+            "docs": [
+              "Share of each payment going to the fee recipient, in basis points"
+            ],
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "randomnessSource",
+      "docs": [
+        "Where a sale's lottery draw gets its randomness from"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "slotHashes"
+          },
+          {
+            "name": "oracle",
+            "fields": [
+              {
+                "name": "program",
+                "type": "pubkey"
+              },
+              {
+                "name": "account",
+                "type": "pubkey"
+              },
+              {
+                "name": "offset",
+                "type": "u32"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "rebateClaimed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "buyer",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "docs": [
+              "Paid back in lamports, or in base units of the sale's payment mint"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "clearingPrice",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "referralRewardsClaimed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "referrer",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "docs": [
+              "Paid in lamports, or in base units of the sale's payment mint"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "rewardsClaimed",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "referrerStats",
+      "docs": [
+        "Referral rewards of a referrer in a sale"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "referrer",
+            "type": "pubkey"
+          },
+          {
+            "name": "numReferrals",
+            "type": "u64"
+          },
+          {
+            "name": "rewardsEarned",
+            "docs": [
+              "Rewards accrued from referred purchases, in lamports or the payment mint's base units"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "rewardsClaimed",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "refundClaimed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "buyer",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "docs": [
+              "Paid back in lamports, or in base units of the sale's payment mint"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "saleClosed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "numBuyers",
+            "type": "u64"
+          },
+          {
+            "name": "tokensSold",
+            "type": "u64"
+          },
+          {
+            "name": "lamportsRaised",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "saleCreated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "saleMode",
+            "type": {
+              "defined": {
+                "name": "saleMode"
+              }
+            }
+          },
+          {
+            "name": "tokenMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "paymentMint",
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "lamportsPerToken",
+            "type": "u64"
+          },
+          {
+            "name": "maxTokensPerBuyer",
+            "type": "u64"
+          },
+          {
+            "name": "maxBuyers",
+            "type": "u64"
+          },
+          {
+            "name": "totalTokensForSale",
+            "type": "u64"
+          },
+          {
+            "name": "feeBps",
+            "docs": [
+              "Platform fee taken from each purchase, in basis points"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "referralBps",
+            "docs": [
+              "Share of referred buyers' payments rewarded to their referrer, in basis points"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "saleFinalized",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "actor",
+            "docs": [
+              "Signer of the finalization, either the owner or anyone once the deadline or commit window has passed"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "tokensSold",
+            "type": "u64"
+          },
+          {
+            "name": "lamportsRaised",
+            "type": "u64"
+          },
+          {
+            "name": "totalCommitted",
+            "type": "u64"
+          },
+          {
+            "name": "fee",
+            "docs": [
+              "Fees held back from purchases while the soft cap was pending, paid to the fee recipient"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "saleMode",
+      "docs": [
+        "How a sale supplies the tokens it sells"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "vault"
+          },
+          {
+            "name": "mint"
+          }
+        ]
+      }
+    },
+    {
+      "name": "salePhase",
+      "docs": [
+        "A round of the sale with its own price and limits, e.g. a guaranteed round followed by a public one"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "start",
+            "docs": [
+              "Unix timestamps bounding the phase, start inclusive and end exclusive"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "end",
+            "type": "i64"
+          },
+          {
+            "name": "lamportsPerToken",
+            "type": "u64"
+          },
+          {
+            "name": "maxTokensPerBuyer",
+            "type": "u64"
+          },
+          {
+            "name": "maxTokens",
+            "docs": [
+              "Total number of tokens sold in this phase across all buyers, uncapped when not set"
+            ],
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "tokensSold",
+            "type": "u64"
+          },
+          {
+            "name": "requiresWhitelist",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "saleStateUpdated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "actor",
+            "docs": [
+              "Signer of the update, either the owner or the sale's operator"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "isRegistrationOpen",
+            "type": "bool"
+          },
+          {
+            "name": "isSaleOpen",
+            "type": "bool"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "tokensClaimed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "buyer",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "docs": [
+              "Base units of the token released by this claim"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "buyerTokensClaimed",
+            "docs": [
+              "Base units released to the buyer so far"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "tokensOwed",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "tokensPurchased",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sale",
+            "type": "pubkey"
+          },
+          {
+            "name": "buyer",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "docs": [
+              "Whole tokens bought"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "cost",
+            "docs": [
+              "Paid in lamports, or in base units of the sale's payment mint"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "price",
+            "docs": [
+              "Average price per token of this purchase"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "fee",
+            "docs": [
+              "Share of the cost paid to the platform's fee recipient"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "referralReward",
+            "docs": [
+              "Share of the cost rewarded to the buyer's referrer"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "isHeld",
+            "docs": [
+              "Whether the tokens are held back until `claim_vested`"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "buyerTokensBought",
+            "type": "u64"
+          },
+          {
+            "name": "tokensSold",
+            "type": "u64"
+          },
+          {
+            "name": "lamportsRaised",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "vestingSchedule",
+      "docs": [
+        "Releases purchased tokens gradually instead of delivering them at purchase"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tgeTimestamp",
+            "docs": [
+              "Unix timestamp of the token generation event, before which nothing is unlocked"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "tgeUnlockBps",
+            "docs": [
+              "Share of the tokens unlocked at TGE, in basis points"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "cliffSeconds",
+            "docs": [
+              "Seconds after TGE before the rest starts unlocking"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "durationSeconds",
+            "docs": [
+              "Seconds over which the rest unlocks linearly once the cliff has passed"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "whitelistMode",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "open"
+          },
+          {
+            "name": "curated"
+          },
+          {
+            "name": "merkle"
+          },
+          {
+            "name": "voucher"
+          },
+          {
+            "name": "lottery"
+          }
+        ]
+      }
+    },
+    {
+      "name": "whitelistSale",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "lamportsPerToken",
+            "type": "u64"
+          },
+          {
+            "name": "maxTokensPerBuyer",
+            "type": "u64"
+          },
+          {
+            "name": "maxBuyers",
+            "type": "u64"
+          },
+          {
+            "name": "numBuyers",
+            "type": "u64"
+          },
+          {
+            "name": "isRegistrationOpen",
+            "type": "bool"
+          },
+          {
+            "name": "isSaleOpen",
+            "type": "bool"
+          },
+          {
+            "name": "whitelistMode",
+            "type": {
+              "defined": {
+                "name": "whitelistMode"
+              }
+            }
+          },
+          {
+            "name": "merkleRoot",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "whitelistSigner",
+            "type": "pubkey"
+          },
+          {
+            "name": "paymentMint",
+            "docs": [
+              "Mint that buyers pay in. Buyers pay in SOL when it isn't set."
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "registrationStart",
+            "docs": [
+              "Optional unix timestamps bounding when registration and purchasing are possible.",
+              "Both are only possible when their flag is set and the current time is within their window."
+            ],
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "registrationEnd",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "saleStart",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "saleEnd",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "phases",
+            "docs": [
+              "Sale rounds, of which only the first `num_phases` are in use.",
+              "When there are none, the sale-wide price and limits above apply."
+            ],
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "salePhase"
+                  }
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "numPhases",
+            "type": "u8"
+          },
+          {
+            "name": "vesting",
+            "docs": [
+              "Tokens are delivered right away when not set"
+            ],
+            "type": {
+              "option": {
+                "defined": {
+                  "name": "vestingSchedule"
+                }
+              }
+            }
+          },
+          {
+            "name": "tokensOwed",
+            "docs": [
+              "Tokens held back by the sale but not yet claimed by buyers, in base units"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "softCap",
+            "docs": [
+              "Minimum raise, in lamports or the payment mint's base units, for the sale to go through.",
+              "Purchased tokens are held back until the owner finalizes the sale. Disabled when zero."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "deadline",
+            "docs": [
+              "Unix timestamp at which purchasing ends when there is a soft cap"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "isFinalized",
+            "type": "bool"
+          },
+          {
+            "name": "lamportsRaised",
+            "docs": [
+              "Payments received from buyers, in lamports or the payment mint's base units"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalTokensForSale",
+            "docs": [
+              "Maximum number of tokens sold across all buyers and phases"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "tokensSold",
+            "type": "u64"
+          },
+          {
+            "name": "hardCapLamports",
+            "docs": [
+              "Maximum raise, in lamports or the payment mint's base units, uncapped when not set"
+            ],
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "pricingMode",
+            "docs": [
+              "How the price of tokens is determined when the sale has no phases"
+            ],
+            "type": {
+              "defined": {
+                "name": "pricingMode"
+              }
+            }
+          },
+          {
+            "name": "clearingPrice",
+            "docs": [
+              "Last price per token paid in a Dutch auction, which buyers are rebated down to with uniform clearing"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "isCommitMode",
+            "docs": [
+              "Buyers commit payments during the sale instead of buying, and are allocated tokens pro-rata once it's finalized"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "totalCommitted",
+            "docs": [
+              "Payments committed by all buyers, in lamports or the payment mint's base units"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "committedUnsettled",
+            "docs": [
+              "Committed payments that haven't been settled yet"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "numTickets",
+            "docs": [
+              "Number of lottery tickets registered, each of which has the next ticket number"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "randomnessSource",
+            "type": {
+              "defined": {
+                "name": "randomnessSource"
+              }
+            }
+          },
+          {
+            "name": "isLotteryDrawn",
+            "type": "bool"
+          },
+          {
+            "name": "lotterySeed",
+            "docs": [
+              "Seed of the lottery draw, which decides the winning tickets"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "saleMode",
+            "docs": [
+              "Fields from here on are appended, so sales created before they existed only need to be extended by",
+              "`migrate_whitelist_sale`, which leaves them zeroed"
+            ],
+            "type": {
+              "defined": {
+                "name": "saleMode"
+              }
+            }
+          },
+          {
+            "name": "treasury",
+            "docs": [
+              "Receives the proceeds withdrawn during the sale, the owner when not set"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "lamportsWithdrawn",
+            "docs": [
+              "Proceeds withdrawn to the treasury, in lamports or the payment mint's base units"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "feeBps",
+            "docs": [
+              "Platform fee taken from each purchase, snapshotted from the program config when the sale is created"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "feeRecipient",
+            "type": "pubkey"
+          },
+          {
+            "name": "feesPaid",
+            "docs": [
+              "Fees paid to the fee recipient, in lamports or the payment mint's base units"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "referralBps",
+            "docs": [
+              "Share of referred buyers' payments rewarded to their referrer"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "referralRewardsOwed",
+            "docs": [
+              "Referral rewards accrued but not yet claimed, in lamports or the payment mint's base units"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "pendingOwner",
+            "docs": [
+              "Proposed by the owner with `propose_owner`, becomes the owner once it accepts"
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "operator",
+            "docs": [
+              "Can open and close registration and the sale, and manage the whitelist, but not the sale's economics"
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "feesOwed",
+            "docs": [
+              "Fees of purchases made while the soft cap is pending, paid to the fee recipient once the sale is finalized"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "tokensClaimed",
+            "docs": [
+              "Held back tokens released to buyers through `claim_vested`, in base units"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    }
+  ],
+  "constants": [
+    {
+      "name": "bpsDenominator",
+      "type": "u16",
+      "value": "10000"
+    },
+    {
+      "name": "pdaSeedAllowance",
+      "type": {
+        "array": [
+          "u8",
+          9
+        ]
+      },
+      "value": "[97, 108, 108, 111, 119, 97, 110, 99, 101]"
+    },
+    {
+      "name": "pdaSeedConfig",
+      "type": {
+        "array": [
+          "u8",
+          6
+        ]
+      },
+      "value": "[99, 111, 110, 102, 105, 103]"
+    },
+    {
+      "name": "pdaSeedReferrer",
+      "type": {
+        "array": [
+          "u8",
+          8
+        ]
+      },
+      "value": "[114, 101, 102, 101, 114, 114, 101, 114]"
+    },
+    {
+      "name": "pdaSeedSale",
+      "type": {
+        "array": [
+          "u8",
+          4
+        ]
+      },
+      "value": "[115, 97, 108, 101]"
+    }
+  ]
+};
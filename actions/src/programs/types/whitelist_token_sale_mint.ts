@@ -1,614 +0,0 @@
-/**
- * Program IDL in camelCase format in order to be used in JS/TS.
- *
- * Note that this is only a type helper and is not the actual IDL. The original
- * IDL can be found at `target/idl/whitelist_token_sale_mint.json`.
- */
-export type WhitelistTokenSaleMint = {
-  "address": "BiHgbT5QViTwpY658qAcgcTNuPYo2UZr2sjTwTF9JdbX",
-  "metadata": {
-    "name": "whitelistTokenSaleMint",
-    "version": "0.1.0",
-    "spec": "0.1.0",
-    "description": "Created with Anchor"
-  },
-  "instructions": [
-    {
-      "name": "buyTokens",
-      "discriminator": [
-        189,
-        21,
-        230,
-        133,
-        247,
-        2,
-        110,
-        42
-      ],
-      "accounts": [
-        {
-          "name": "sale",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  115,
-                  97,
-                  108,
-                  101
-                ]
-              },
-              {
-                "kind": "arg",
-                "path": "saleName"
-              }
-            ]
-          }
-        },
-        {
-          "name": "allowance",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  97,
-                  108,
-                  108,
-                  111,
-                  119,
-                  97,
-                  110,
-                  99,
-                  101
-                ]
-              },
-              {
-                "kind": "arg",
-                "path": "saleName"
-              },
-              {
-                "kind": "account",
-                "path": "signer"
-              }
-            ]
-          }
-        },
-        {
-          "name": "signer",
-          "writable": true,
-          "signer": true
-        },
-        {
-          "name": "signerAta",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "account",
-                "path": "signer"
-              },
-              {
-                "kind": "const",
-                "value": [
-                  6,
-                  221,
-                  246,
-                  225,
-                  215,
-                  101,
-                  161,
-                  147,
-                  217,
-                  203,
-                  225,
-                  70,
-                  206,
-                  235,
-                  121,
-                  172,
-                  28,
-                  180,
-                  133,
-                  237,
-                  95,
-                  91,
-                  55,
-                  145,
-                  58,
-                  140,
-                  245,
-                  133,
-                  126,
-                  255,
-                  0,
-                  169
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "tokenMint"
-              }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                140,
-                151,
-                37,
-                143,
-                78,
-                36,
-                137,
-                241,
-                187,
-                61,
-                16,
-                41,
-                20,
-                142,
-                13,
-                131,
-                11,
-                90,
-                19,
-                153,
-                218,
-                255,
-                16,
-                132,
-                4,
-                142,
-                123,
-                216,
-                219,
-                233,
-                248,
-                89
-              ]
-            }
-          }
-        },
-        {
-          "name": "tokenMint",
-          "writable": true
-        },
-        {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
-        },
-        {
-          "name": "associatedTokenProgram",
-          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
-        },
-        {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
-        }
-      ],
-      "args": [
-        {
-          "name": "saleName",
-          "type": "string"
-        },
-        {
-          "name": "amount",
-          "type": "u64"
-        }
-      ]
-    },
-    {
-      "name": "closeWhitelistSale",
-      "discriminator": [
-        122,
-        254,
-        201,
-        184,
-        41,
-        230,
-        0,
-        15
-      ],
-      "accounts": [
-        {
-          "name": "sale",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  115,
-                  97,
-                  108,
-                  101
-                ]
-              },
-              {
-                "kind": "arg",
-                "path": "saleName"
-              }
-            ]
-          }
-        },
-        {
-          "name": "tokenMint",
-          "writable": true
-        },
-        {
-          "name": "signer",
-          "writable": true,
-          "signer": true
-        },
-        {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
-        },
-        {
-          "name": "associatedTokenProgram",
-          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
-        },
-        {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
-        }
-      ],
-      "args": [
-        {
-          "name": "saleName",
-          "type": "string"
-        }
-      ]
-    },
-    {
-      "name": "createWhitelistSale",
-      "discriminator": [
-        250,
-        194,
-        137,
-        75,
-        201,
-        218,
-        133,
-        141
-      ],
-      "accounts": [
-        {
-          "name": "sale",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  115,
-                  97,
-                  108,
-                  101
-                ]
-              },
-              {
-                "kind": "arg",
-                "path": "saleName"
-              }
-            ]
-          }
-        },
-        {
-          "name": "signer",
-          "writable": true,
-          "signer": true
-        },
-        {
-          "name": "tokenMint",
-          "writable": true
-        },
-        {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
-        },
-        {
-          "name": "associatedTokenProgram",
-          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
-        },
-        {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
-        }
-      ],
-      "args": [
-        {
-          "name": "saleName",
-          "type": "string"
-        },
-        {
-          "name": "lamportsPerToken",
-          "type": "u64"
-        },
-        {
-          "name": "maxTokensPerBuyer",
-          "type": "u64"
-        },
-        {
-          "name": "maxBuyers",
-          "type": "u64"
-        }
-      ]
-    },
-    {
-      "name": "registerForWhitelist",
-      "discriminator": [
-        103,
-        170,
-        102,
-        132,
-        84,
-        67,
-        94,
-        16
-      ],
-      "accounts": [
-        {
-          "name": "sale",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  115,
-                  97,
-                  108,
-                  101
-                ]
-              },
-              {
-                "kind": "arg",
-                "path": "saleName"
-              }
-            ]
-          }
-        },
-        {
-          "name": "signer",
-          "writable": true,
-          "signer": true
-        },
-        {
-          "name": "allowance",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  97,
-                  108,
-                  108,
-                  111,
-                  119,
-                  97,
-                  110,
-                  99,
-                  101
-                ]
-              },
-              {
-                "kind": "arg",
-                "path": "saleName"
-              },
-              {
-                "kind": "account",
-                "path": "signer"
-              }
-            ]
-          }
-        },
-        {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
-        }
-      ],
-      "args": [
-        {
-          "name": "saleName",
-          "type": "string"
-        }
-      ]
-    },
-    {
-      "name": "updateSaleState",
-      "discriminator": [
-        178,
-        28,
-        126,
-        89,
-        175,
-        38,
-        196,
-        30
-      ],
-      "accounts": [
-        {
-          "name": "sale",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  115,
-                  97,
-                  108,
-                  101
-                ]
-              },
-              {
-                "kind": "arg",
-                "path": "saleName"
-              }
-            ]
-          }
-        },
-        {
-          "name": "signer",
-          "writable": true,
-          "signer": true
-        }
-      ],
-      "args": [
-        {
-          "name": "saleName",
-          "type": "string"
-        },
-        {
-          "name": "isRegistrationOpen",
-          "type": "bool"
-        },
-        {
-          "name": "isSaleOpen",
-          "type": "bool"
-        }
-      ]
-    }
-  ],
-  "accounts": [
-    {
-      "name": "allowance",
-      "discriminator": [
-        144,
-        8,
-        184,
-        213,
-        49,
-        248,
-        73,
-        131
-      ]
-    },
-    {
-      "name": "whitelistSale",
-      "discriminator": [
-        61,
-        244,
-        247,
-        94,
-        6,
-        19,
-        119,
-        68
-      ]
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "onlyOwner",
-      "msg": "Only the sale owner can perform this action"
-    },
-    {
-      "code": 6001,
-      "name": "whitelistRegistrationClosed",
-      "msg": "Whitelist registration is closed"
-    },
-    {
-      "code": 6002,
-      "name": "saleClosed",
-      "msg": "Whiteliste sale is closed"
-    },
-    {
-      "code": 6003,
-      "name": "allowanceExceeded",
-      "msg": "Token purchase is larger than remaining allowance"
-    },
-    {
-      "code": 6004,
-      "name": "mintMismatch",
-      "msg": "Mismatch in mints of provided accounts"
-    },
-    {
-      "code": 6005,
-      "name": "buyerLimitReached",
-      "msg": "The maximum number of registered buyers has been reached"
-    }
-  ],
-  "types": [
-    {
-      "name": "allowance",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "tokensBought",
-            "type": "u64"
-          }
-        ]
-      }
-    },
-    {
-      "name": "whitelistSale",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "tokenMint",
-            "type": "pubkey"
-          },
-          {
-            "name": "owner",
-            "type": "pubkey"
-          },
-          {
-            "name": "lamportsPerToken",
-            "type": "u64"
-          },
-          {
-            "name": "maxTokensPerBuyer",
-            "type": "u64"
-          },
-          {
-            "name": "maxBuyers",
-            "type": "u64"
-          },
-          {
-            "name": "numBuyers",
-            "type": "u64"
-          },
-          {
-            "name": "isRegistrationOpen",
-            "type": "bool"
-          },
-          {
-            "name": "isSaleOpen",
-            "type": "bool"
-          }
-        ]
-      }
-    }
-  ],
-  "constants": [
-    {
-      "name": "pdaSeedAllowance",
-      "type": {
-        "array": [
-          "u8",
-          9
-        ]
-      },
-      "value": "[97, 108, 108, 111, 119, 97, 110, 99, 101]"
-    },
-    {
-      "name": "pdaSeedSale",
-      "type": {
-        "array": [
-          "u8",
-          4
-        ]
-      },
-      "value": "[115, 97, 108, 101]"
-    }
-  ]
-};
@@ -0,0 +1,959 @@
+use anyhow::{anyhow, bail, Context as _, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+    transaction::Transaction,
+};
+use whitelist_token_sale_client::{
+    accounts::{deserialize_allowance, deserialize_sale},
+    instructions::{self, TokenMint},
+    pda::{find_allowance_address, find_sale_address, get_vault_address},
+    state::{SaleMode, WhitelistSale},
+};
+
+#[derive(Parser)]
+#[command(version, about = "Runs whitelist token sales from the command line")]
+struct Cli {
+    /// RPC URL of the cluster
+    #[arg(
+        long,
+        short,
+        env = "SOLANA_URL",
+        default_value = "http://localhost:8899"
+    )]
+    url: String,
+
+    /// Keypair file of the signer, who is the sale's owner or a buyer depending on the command
+    #[arg(
+        long,
+        short,
+        env = "SOLANA_KEYPAIR",
+        default_value = "~/.config/solana/id.json"
+    )]
+    keypair: String,
+
+    /// Simulate transactions instead of sending them
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Mode {
+    Vault,
+    Mint,
+}
+
+impl From<Mode> for SaleMode {
+    fn from(mode: Mode) -> Self {
+        match mode {
+            Mode::Vault => SaleMode::Vault,
+            Mode::Mint => SaleMode::Mint,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create the program config, signed by the program's upgrade authority
+    InitConfig {
+        #[arg(long)]
+        admin: Pubkey,
+        #[arg(long)]
+        fee_recipient: Pubkey,
+        /// Share of each payment going to the fee recipient, in basis points
+        #[arg(long)]
+        fee_bps: u16,
+    },
+    /// Update the program config as its admin, which only applies to sales created afterwards
+    UpdateConfig {
+        #[arg(long)]
+        admin: Pubkey,
+        #[arg(long)]
+        fee_recipient: Pubkey,
+        #[arg(long)]
+        fee_bps: u16,
+    },
+    /// Create a sale, which opens its registration
+    Create {
+        sale_name: String,
+        #[arg(long)]
+        token_mint: Pubkey,
+        /// Price of a whole token, in lamports or in base units of the payment mint
+        #[arg(long)]
+        lamports_per_token: u64,
+        #[arg(long)]
+        max_tokens_per_buyer: u64,
+        #[arg(long)]
+        max_buyers: u64,
+        #[arg(long, value_enum, default_value = "vault")]
+        mode: Mode,
+        /// Have buyers pay in this mint instead of SOL
+        #[arg(long)]
+        payment_mint: Option<Pubkey>,
+        /// Share of referred buyers' payments rewarded to their referrer, in basis points
+        #[arg(long, default_value_t = 0)]
+        referral_bps: u16,
+    },
+    OpenRegistration {
+        sale_name: String,
+    },
+    CloseRegistration {
+        sale_name: String,
+    },
+    OpenSale {
+        sale_name: String,
+    },
+    CloseSale {
+        sale_name: String,
+    },
+    /// Register the signer on the sale's whitelist
+    Register {
+        sale_name: String,
+        /// Wallet that referred the signer, earning a share of their purchases
+        #[arg(long)]
+        referrer: Option<Pubkey>,
+    },
+    /// Buy whole tokens as the signer
+    Buy {
+        sale_name: String,
+        #[arg(long)]
+        amount: u64,
+    },
+    /// Claim the referral rewards the signer earned in the sale
+    ClaimReferralRewards {
+        sale_name: String,
+    },
+    /// Send the proceeds collected so far to the sale's treasury, keeping back refundable payments
+    Withdraw {
+        sale_name: String,
+    },
+    /// Set the account receiving withdrawn proceeds
+    SetTreasury {
+        sale_name: String,
+        treasury: Pubkey,
+    },
+    /// Let another key open and close the sale and manage who is on its whitelist
+    SetOperator {
+        sale_name: String,
+        /// Removes the current operator when not set
+        #[arg(long)]
+        operator: Option<Pubkey>,
+    },
+    /// Propose a new owner for the sale, who takes over once they accept
+    ProposeOwner {
+        sale_name: String,
+        /// Withdraws the current proposal when not set
+        #[arg(long)]
+        new_owner: Option<Pubkey>,
+    },
+    /// Become the sale's owner, as its pending owner
+    AcceptOwnership {
+        sale_name: String,
+    },
+    /// Close the sale, sending its proceeds and remaining tokens to the owner
+    Close {
+        sale_name: String,
+    },
+    /// Print the sale's state and the tokens left in its vault
+    Show {
+        sale_name: String,
+    },
+}
+
+struct Context {
+    rpc: RpcClient,
+    signer: Keypair,
+    dry_run: bool,
+}
+
+/// Account lookups that commands build their instructions from
+trait Fetch {
+    fn fetch_sale(&self, sale_name: &str) -> Result<WhitelistSale>;
+
+    /// Mints are paired with the token program owning them
+    fn fetch_token_mint(&self, mint: &Pubkey) -> Result<TokenMint>;
+
+    /// Referrer recorded on the wallet's allowance, if they're registered with one
+    fn fetch_referrer(&self, sale_name: &str, wallet: &Pubkey) -> Result<Option<Pubkey>>;
+
+    fn fetch_payment_mint(&self, sale: &WhitelistSale) -> Result<Option<TokenMint>> {
+        sale.payment_mint
+            .map(|payment_mint| self.fetch_token_mint(&payment_mint))
+            .transpose()
+    }
+}
+
+impl Fetch for Context {
+    fn fetch_sale(&self, sale_name: &str) -> Result<WhitelistSale> {
+        let (sale, _bump) = find_sale_address(sale_name);
+        let data = self
+            .rpc
+            .get_account_data(&sale)
+            .with_context(|| format!("Sale \"{sale_name}\" doesn't exist at {sale}"))?;
+
+        deserialize_sale(&data).map_err(|e| anyhow!("Invalid sale account {sale}: {e}"))
+    }
+
+    fn fetch_token_mint(&self, mint: &Pubkey) -> Result<TokenMint> {
+        let account = self
+            .rpc
+            .get_account(mint)
+            .with_context(|| format!("Mint {mint} doesn't exist"))?;
+
+        Ok(TokenMint {
+            mint: *mint,
+            token_program: account.owner,
+        })
+    }
+
+    fn fetch_referrer(&self, sale_name: &str, wallet: &Pubkey) -> Result<Option<Pubkey>> {
+        let (allowance, _bump) = find_allowance_address(sale_name, wallet);
+        let Some(account) = self
+            .rpc
+            .get_account_with_commitment(&allowance, self.rpc.commitment())?
+            .value
+        else {
+            return Ok(None);
+        };
+
+        let allowance = deserialize_allowance(&account.data)
+            .map_err(|e| anyhow!("Invalid allowance account {allowance}: {e}"))?;
+
+        Ok(allowance.referrer)
+    }
+}
+
+impl Context {
+    fn send(&self, instructions: &[Instruction]) -> Result<()> {
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.signer.pubkey()),
+            &[&self.signer],
+            self.rpc.get_latest_blockhash()?,
+        );
+
+        if !self.dry_run {
+            let signature = self.rpc.send_and_confirm_transaction(&transaction)?;
+            println!("Signature: {signature}");
+
+            return Ok(());
+        }
+
+        let simulation = self.rpc.simulate_transaction(&transaction)?.value;
+        for log in simulation.logs.unwrap_or_default() {
+            println!("{log}");
+        }
+
+        match simulation.err {
+            Some(err) => bail!("Simulation failed: {err}"),
+            None => {
+                println!(
+                    "Simulation succeeded, using {} compute units",
+                    simulation.units_consumed.unwrap_or_default()
+                );
+
+                Ok(())
+            }
+        }
+    }
+
+    fn show(&self, sale_name: &str) -> Result<()> {
+        let (address, _bump) = find_sale_address(sale_name);
+        let sale = self.fetch_sale(sale_name)?;
+
+        println!("Sale:                   {address}");
+        println!("Owner:                  {}", sale.owner);
+        if let Some(pending_owner) = sale.pending_owner {
+            println!("Pending owner:          {pending_owner}");
+        }
+        if let Some(operator) = sale.operator {
+            println!("Operator:               {operator}");
+        }
+        println!("Mode:                   {:?}", sale.sale_mode);
+        println!("Token mint:             {}", sale.token_mint);
+        if let Some(payment_mint) = sale.payment_mint {
+            println!("Payment mint:           {payment_mint}");
+        }
+        println!("Price per token:        {}", sale.lamports_per_token);
+        println!("Pricing mode:           {:?}", sale.pricing_mode);
+        println!("Whitelist mode:         {:?}", sale.whitelist_mode);
+        println!("Registration open:      {}", sale.is_registration_open);
+        println!("Sale open:              {}", sale.is_sale_open);
+        println!(
+            "Buyers:                 {} / {}",
+            sale.num_buyers, sale.max_buyers
+        );
+        println!("Max tokens per buyer:   {}", sale.max_tokens_per_buyer);
+        println!(
+            "Tokens sold:            {} / {}",
+            sale.tokens_sold, sale.total_tokens_for_sale
+        );
+        println!("Raised:                 {}", sale.lamports_raised);
+        if sale.fee_bps > 0 {
+            println!(
+                "Platform fee:           {} bps to {} ({} paid)",
+                sale.fee_bps, sale.fee_recipient, sale.fees_paid
+            );
+        }
+        if sale.referral_bps > 0 {
+            println!(
+                "Referral reward:        {} bps ({} owed)",
+                sale.referral_bps, sale.referral_rewards_owed
+            );
+        }
+        println!("Treasury:               {}", sale.treasury_address());
+        println!("Withdrawn:              {}", sale.lamports_withdrawn);
+        if let Some(hard_cap_lamports) = sale.hard_cap_lamports {
+            println!("Hard cap:               {hard_cap_lamports}");
+        }
+        if sale.soft_cap > 0 {
+            println!(
+                "Soft cap:               {} (deadline {}, finalized: {})",
+                sale.soft_cap, sale.deadline, sale.is_finalized
+            );
+        }
+        println!("Tokens owed to buyers:  {}", sale.tokens_owed);
+
+        if sale.sale_mode == SaleMode::Vault {
+            let token = self.fetch_token_mint(&sale.token_mint)?;
+            let vault = get_vault_address(&address, &token.mint, &token.token_program);
+            let balance = self.rpc.get_token_account_balance(&vault)?;
+
+            println!(
+                "Vault balance:          {}",
+                balance.real_number_string_trimmed()
+            );
+        }
+
+        Ok(())
+    }
+
+    fn run(&self, command: Command) -> Result<()> {
+        if let Command::Show { sale_name } = &command {
+            return self.show(sale_name);
+        }
+
+        self.send(&build_instructions(self, &self.signer.pubkey(), command)?)
+    }
+}
+
+/// Flips one of the sale's flags, keeping the other as it is
+fn update_sale_state(
+    fetch: &impl Fetch,
+    signer: &Pubkey,
+    sale_name: &str,
+    is_registration_open: Option<bool>,
+    is_sale_open: Option<bool>,
+) -> Result<Vec<Instruction>> {
+    let sale = fetch.fetch_sale(sale_name)?;
+
+    Ok(vec![instructions::update_sale_state(
+        signer,
+        sale_name,
+        is_registration_open.unwrap_or(sale.is_registration_open),
+        is_sale_open.unwrap_or(sale.is_sale_open),
+    )])
+}
+
+/// Instructions sent by the command, built from the accounts it depends on
+fn build_instructions(
+    fetch: &impl Fetch,
+    signer: &Pubkey,
+    command: Command,
+) -> Result<Vec<Instruction>> {
+    match command {
+        Command::InitConfig {
+            admin,
+            fee_recipient,
+            fee_bps,
+        } => Ok(vec![instructions::initialize_config(
+            signer,
+            &admin,
+            &fee_recipient,
+            fee_bps,
+        )]),
+        Command::UpdateConfig {
+            admin,
+            fee_recipient,
+            fee_bps,
+        } => Ok(vec![instructions::update_config(
+            signer,
+            &admin,
+            &fee_recipient,
+            fee_bps,
+        )]),
+        Command::Create {
+            sale_name,
+            token_mint,
+            lamports_per_token,
+            max_tokens_per_buyer,
+            max_buyers,
+            mode,
+            payment_mint,
+            referral_bps,
+        } => {
+            let token = fetch.fetch_token_mint(&token_mint)?;
+            let payment = payment_mint
+                .map(|payment_mint| fetch.fetch_token_mint(&payment_mint))
+                .transpose()?;
+
+            Ok(vec![instructions::create_whitelist_sale(
+                signer,
+                &sale_name,
+                &token,
+                payment.as_ref(),
+                lamports_per_token,
+                max_tokens_per_buyer,
+                max_buyers,
+                mode.into(),
+                referral_bps,
+            )])
+        }
+        Command::OpenRegistration { sale_name } => {
+            update_sale_state(fetch, signer, &sale_name, Some(true), None)
+        }
+        Command::CloseRegistration { sale_name } => {
+            update_sale_state(fetch, signer, &sale_name, Some(false), None)
+        }
+        Command::OpenSale { sale_name } => {
+            update_sale_state(fetch, signer, &sale_name, None, Some(true))
+        }
+        Command::CloseSale { sale_name } => {
+            update_sale_state(fetch, signer, &sale_name, None, Some(false))
+        }
+        Command::Register {
+            sale_name,
+            referrer,
+        } => Ok(vec![instructions::register_for_whitelist(
+            signer,
+            &sale_name,
+            referrer.as_ref(),
+        )]),
+        Command::Buy { sale_name, amount } => {
+            let sale = fetch.fetch_sale(&sale_name)?;
+            let token = fetch.fetch_token_mint(&sale.token_mint)?;
+            let payment = fetch.fetch_payment_mint(&sale)?;
+            let referrer = fetch.fetch_referrer(&sale_name, signer)?;
+
+            Ok(vec![instructions::buy_tokens(
+                signer,
+                &sale_name,
+                &token,
+                payment.as_ref(),
+                (sale.fee_bps > 0).then_some(&sale.fee_recipient),
+                referrer.as_ref(),
+                sale.sale_mode,
+                amount,
+            )])
+        }
+        Command::ClaimReferralRewards { sale_name } => {
+            let sale = fetch.fetch_sale(&sale_name)?;
+            let payment = fetch.fetch_payment_mint(&sale)?;
+
+            Ok(vec![instructions::claim_referral_rewards(
+                signer,
+                &sale_name,
+                payment.as_ref(),
+            )])
+        }
+        Command::Withdraw { sale_name } => {
+            let sale = fetch.fetch_sale(&sale_name)?;
+            let payment = fetch.fetch_payment_mint(&sale)?;
+
+            Ok(vec![instructions::withdraw_proceeds(
+                signer,
+                &sale_name,
+                &sale.treasury_address(),
+                payment.as_ref(),
+            )])
+        }
+        Command::SetTreasury {
+            sale_name,
+            treasury,
+        } => Ok(vec![instructions::set_treasury(
+            signer, &sale_name, &treasury,
+        )]),
+        Command::SetOperator {
+            sale_name,
+            operator,
+        } => Ok(vec![instructions::set_operator(
+            signer,
+            &sale_name,
+            operator.as_ref(),
+        )]),
+        Command::ProposeOwner {
+            sale_name,
+            new_owner,
+        } => Ok(vec![instructions::propose_owner(
+            signer,
+            &sale_name,
+            new_owner.as_ref(),
+        )]),
+        Command::AcceptOwnership { sale_name } => {
+            Ok(vec![instructions::accept_ownership(signer, &sale_name)])
+        }
+        Command::Close { sale_name } => {
+            let sale = fetch.fetch_sale(&sale_name)?;
+            let token = fetch.fetch_token_mint(&sale.token_mint)?;
+            let payment = fetch.fetch_payment_mint(&sale)?;
+
+            Ok(vec![instructions::close_whitelist_sale(
+                signer,
+                &sale_name,
+                &token,
+                payment.as_ref(),
+                sale.sale_mode,
+            )])
+        }
+        // Only reads the sale
+        Command::Show { .. } => Ok(Vec::new()),
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let keypair_path = match cli.keypair.strip_prefix("~/") {
+        Some(path) => format!("{}/{path}", std::env::var("HOME")?),
+        None => cli.keypair,
+    };
+    let signer = read_keypair_file(&keypair_path)
+        .map_err(|e| anyhow!("Couldn't read keypair file {keypair_path}: {e}"))?;
+
+    let context = Context {
+        rpc: RpcClient::new_with_commitment(cli.url, CommitmentConfig::confirmed()),
+        signer,
+        dry_run: cli.dry_run,
+    };
+
+    context.run(cli.command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AnchorDeserialize;
+    use clap::CommandFactory;
+    use std::collections::HashMap;
+
+    /// Accounts of a single sale, in place of the cluster
+    struct Accounts {
+        sale: WhitelistSale,
+        token_programs: HashMap<Pubkey, Pubkey>,
+        referrer: Option<Pubkey>,
+    }
+
+    impl Accounts {
+        fn new() -> Self {
+            let token_mint = Pubkey::new_unique();
+            let mut sale = WhitelistSale::deserialize(&mut &[0; 4_096][..]).unwrap();
+            sale.token_mint = token_mint;
+            sale.owner = Pubkey::new_unique();
+
+            Self {
+                sale,
+                token_programs: HashMap::from([(token_mint, Pubkey::new_unique())]),
+                referrer: None,
+            }
+        }
+
+        fn token(&self, mint: &Pubkey) -> TokenMint {
+            self.fetch_token_mint(mint).unwrap()
+        }
+    }
+
+    impl Fetch for Accounts {
+        fn fetch_sale(&self, _sale_name: &str) -> Result<WhitelistSale> {
+            Ok(self.sale.clone())
+        }
+
+        fn fetch_token_mint(&self, mint: &Pubkey) -> Result<TokenMint> {
+            let token_program = self
+                .token_programs
+                .get(mint)
+                .with_context(|| format!("Mint {mint} doesn't exist"))?;
+
+            Ok(TokenMint {
+                mint: *mint,
+                token_program: *token_program,
+            })
+        }
+
+        fn fetch_referrer(&self, _sale_name: &str, _wallet: &Pubkey) -> Result<Option<Pubkey>> {
+            Ok(self.referrer)
+        }
+    }
+
+    fn parse(args: &[&str]) -> Cli {
+        Cli::try_parse_from([&["whitelist-token-sale"], args].concat()).unwrap()
+    }
+
+    #[test]
+    fn create_defaults_to_a_vault_sale_paid_in_sol() {
+        let token_mint = Pubkey::new_unique();
+        let cli = parse(&[
+            "create",
+            "presale",
+            "--token-mint",
+            &token_mint.to_string(),
+            "--lamports-per-token",
+            "100",
+            "--max-tokens-per-buyer",
+            "10",
+            "--max-buyers",
+            "3",
+        ]);
+
+        let Command::Create {
+            sale_name,
+            token_mint: parsed_mint,
+            lamports_per_token,
+            max_tokens_per_buyer,
+            max_buyers,
+            mode,
+            payment_mint,
+            referral_bps,
+        } = cli.command
+        else {
+            panic!("Expected the create command");
+        };
+        assert_eq!(sale_name, "presale");
+        assert_eq!(parsed_mint, token_mint);
+        assert_eq!(
+            (lamports_per_token, max_tokens_per_buyer, max_buyers),
+            (100, 10, 3)
+        );
+        assert_eq!(SaleMode::from(mode), SaleMode::Vault);
+        assert_eq!(payment_mint, None);
+        assert_eq!(referral_bps, 0);
+        assert!(!cli.dry_run);
+    }
+
+    #[test]
+    fn dry_run_can_follow_the_command() {
+        let cli = parse(&[
+            "--url",
+            "http://rpc",
+            "buy",
+            "presale",
+            "--amount",
+            "2",
+            "--dry-run",
+        ]);
+
+        assert!(cli.dry_run);
+        assert_eq!(cli.url, "http://rpc");
+        assert!(matches!(cli.command, Command::Buy { amount: 2, .. }));
+    }
+
+    #[test]
+    fn invalid_arguments_are_rejected() {
+        Cli::command().debug_assert();
+
+        let token_mint = Pubkey::new_unique().to_string();
+        for args in [
+            // Missing the amount
+            vec!["buy", "presale"],
+            vec!["register", "presale", "--referrer", "not-a-pubkey"],
+            vec![
+                "create",
+                "presale",
+                "--token-mint",
+                &token_mint,
+                "--lamports-per-token",
+                "100",
+                "--max-tokens-per-buyer",
+                "10",
+                "--max-buyers",
+                "3",
+                "--mode",
+                "burn",
+            ],
+            // Missing the treasury
+            vec!["set-treasury", "presale"],
+            vec!["close-registration"],
+        ] {
+            let args = [&["whitelist-token-sale"], &args[..]].concat();
+            assert!(Cli::try_parse_from(args).is_err());
+        }
+    }
+
+    #[test]
+    fn create_uses_the_token_program_of_each_mint() {
+        let mut accounts = Accounts::new();
+        let payment_mint = Pubkey::new_unique();
+        accounts
+            .token_programs
+            .insert(payment_mint, Pubkey::new_unique());
+        let signer = Pubkey::new_unique();
+
+        let command = Command::Create {
+            sale_name: "presale".to_string(),
+            token_mint: accounts.sale.token_mint,
+            lamports_per_token: 100,
+            max_tokens_per_buyer: 10,
+            max_buyers: 3,
+            mode: Mode::Mint,
+            payment_mint: Some(payment_mint),
+            referral_bps: 500,
+        };
+        assert_eq!(
+            build_instructions(&accounts, &signer, command).unwrap(),
+            vec![instructions::create_whitelist_sale(
+                &signer,
+                "presale",
+                &accounts.token(&accounts.sale.token_mint),
+                Some(&accounts.token(&payment_mint)),
+                100,
+                10,
+                3,
+                SaleMode::Mint,
+                500,
+            )]
+        );
+
+        // Mints that don't exist can't be sold
+        let command = Command::Create {
+            sale_name: "presale".to_string(),
+            token_mint: Pubkey::new_unique(),
+            lamports_per_token: 100,
+            max_tokens_per_buyer: 10,
+            max_buyers: 3,
+            mode: Mode::Vault,
+            payment_mint: None,
+            referral_bps: 0,
+        };
+        assert!(build_instructions(&accounts, &signer, command).is_err());
+    }
+
+    #[test]
+    fn opening_the_sale_keeps_registration_as_it_is() {
+        let mut accounts = Accounts::new();
+        accounts.sale.is_registration_open = true;
+        let signer = Pubkey::new_unique();
+
+        let command = Command::OpenSale {
+            sale_name: "presale".to_string(),
+        };
+        assert_eq!(
+            build_instructions(&accounts, &signer, command).unwrap(),
+            vec![instructions::update_sale_state(
+                &signer, "presale", true, true
+            )]
+        );
+
+        let command = Command::CloseRegistration {
+            sale_name: "presale".to_string(),
+        };
+        assert_eq!(
+            build_instructions(&accounts, &signer, command).unwrap(),
+            vec![instructions::update_sale_state(
+                &signer, "presale", false, false
+            )]
+        );
+    }
+
+    #[test]
+    fn purchases_pass_the_fee_recipient_and_the_buyer_referrer() {
+        let mut accounts = Accounts::new();
+        let signer = Pubkey::new_unique();
+        let buy = || Command::Buy {
+            sale_name: "presale".to_string(),
+            amount: 2,
+        };
+        let token = accounts.token(&accounts.sale.token_mint);
+
+        assert_eq!(
+            build_instructions(&accounts, &signer, buy()).unwrap(),
+            vec![instructions::buy_tokens(
+                &signer,
+                "presale",
+                &token,
+                None,
+                None,
+                None,
+                SaleMode::Vault,
+                2,
+            )]
+        );
+
+        // The fee recipient is only passed to sales created with a platform fee
+        let referrer = Pubkey::new_unique();
+        accounts.referrer = Some(referrer);
+        accounts.sale.fee_recipient = Pubkey::new_unique();
+        accounts.sale.sale_mode = SaleMode::Mint;
+        assert_eq!(
+            build_instructions(&accounts, &signer, buy()).unwrap(),
+            vec![instructions::buy_tokens(
+                &signer,
+                "presale",
+                &token,
+                None,
+                None,
+                Some(&referrer),
+                SaleMode::Mint,
+                2,
+            )]
+        );
+
+        accounts.sale.fee_bps = 100;
+        assert_eq!(
+            build_instructions(&accounts, &signer, buy()).unwrap(),
+            vec![instructions::buy_tokens(
+                &signer,
+                "presale",
+                &token,
+                None,
+                Some(&accounts.sale.fee_recipient),
+                Some(&referrer),
+                SaleMode::Mint,
+                2,
+            )]
+        );
+    }
+
+    #[test]
+    fn proceeds_are_withdrawn_to_the_treasury() {
+        let mut accounts = Accounts::new();
+        let payment_mint = Pubkey::new_unique();
+        accounts
+            .token_programs
+            .insert(payment_mint, Pubkey::new_unique());
+        accounts.sale.payment_mint = Some(payment_mint);
+        let withdraw = || Command::Withdraw {
+            sale_name: "presale".to_string(),
+        };
+
+        // The owner is the treasury until one is set
+        let owner = accounts.sale.owner;
+        let payment = accounts.token(&payment_mint);
+        assert_eq!(
+            build_instructions(&accounts, &owner, withdraw()).unwrap(),
+            vec![instructions::withdraw_proceeds(
+                &owner,
+                "presale",
+                &owner,
+                Some(&payment)
+            )]
+        );
+
+        accounts.sale.treasury = Pubkey::new_unique();
+        assert_eq!(
+            build_instructions(&accounts, &owner, withdraw()).unwrap(),
+            vec![instructions::withdraw_proceeds(
+                &owner,
+                "presale",
+                &accounts.sale.treasury,
+                Some(&payment)
+            )]
+        );
+    }
+
+    #[test]
+    fn closing_the_sale_returns_its_tokens_in_its_mode() {
+        let mut accounts = Accounts::new();
+        accounts.sale.sale_mode = SaleMode::Mint;
+        let owner = accounts.sale.owner;
+
+        let command = Command::Close {
+            sale_name: "presale".to_string(),
+        };
+        assert_eq!(
+            build_instructions(&accounts, &owner, command).unwrap(),
+            vec![instructions::close_whitelist_sale(
+                &owner,
+                "presale",
+                &accounts.token(&accounts.sale.token_mint),
+                None,
+                SaleMode::Mint,
+            )]
+        );
+    }
+
+    #[test]
+    fn commands_without_lookups_pass_their_arguments_through() {
+        let signer = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let accounts = Accounts::new();
+        let sale_name = || "presale".to_string();
+
+        for (command, instruction) in [
+            (
+                Command::InitConfig {
+                    admin: other,
+                    fee_recipient: other,
+                    fee_bps: 100,
+                },
+                instructions::initialize_config(&signer, &other, &other, 100),
+            ),
+            (
+                Command::UpdateConfig {
+                    admin: other,
+                    fee_recipient: other,
+                    fee_bps: 50,
+                },
+                instructions::update_config(&signer, &other, &other, 50),
+            ),
+            (
+                Command::Register {
+                    sale_name: sale_name(),
+                    referrer: Some(other),
+                },
+                instructions::register_for_whitelist(&signer, "presale", Some(&other)),
+            ),
+            (
+                Command::ClaimReferralRewards {
+                    sale_name: sale_name(),
+                },
+                instructions::claim_referral_rewards(&signer, "presale", None),
+            ),
+            (
+                Command::SetTreasury {
+                    sale_name: sale_name(),
+                    treasury: other,
+                },
+                instructions::set_treasury(&signer, "presale", &other),
+            ),
+            (
+                Command::SetOperator {
+                    sale_name: sale_name(),
+                    operator: None,
+                },
+                instructions::set_operator(&signer, "presale", None),
+            ),
+            (
+                Command::ProposeOwner {
+                    sale_name: sale_name(),
+                    new_owner: Some(other),
+                },
+                instructions::propose_owner(&signer, "presale", Some(&other)),
+            ),
+            (
+                Command::AcceptOwnership {
+                    sale_name: sale_name(),
+                },
+                instructions::accept_ownership(&signer, "presale"),
+            ),
+        ] {
+            assert_eq!(
+                build_instructions(&accounts, &signer, command).unwrap(),
+                vec![instruction]
+            );
+        }
+
+        // Show only reads the sale
+        let command = Command::Show {
+            sale_name: sale_name(),
+        };
+        assert!(build_instructions(&accounts, &signer, command)
+            .unwrap()
+            .is_empty());
+    }
+}
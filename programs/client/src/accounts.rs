@@ -0,0 +1,22 @@
+use anchor_lang::{AccountDeserialize, Result};
+use whitelist_token_sale::state::{Allowance, ProgramConfig, ReferrerStats, WhitelistSale};
+
+/// Deserializes a sale account's data, checking its discriminator
+pub fn deserialize_sale(mut data: &[u8]) -> Result<WhitelistSale> {
+    WhitelistSale::try_deserialize(&mut data)
+}
+
+/// Deserializes an allowance account's data, checking its discriminator
+pub fn deserialize_allowance(mut data: &[u8]) -> Result<Allowance> {
+    Allowance::try_deserialize(&mut data)
+}
+
+/// Deserializes a referrer stats account's data, checking its discriminator
+pub fn deserialize_referrer_stats(mut data: &[u8]) -> Result<ReferrerStats> {
+    ReferrerStats::try_deserialize(&mut data)
+}
+
+/// Deserializes the program config account's data, checking its discriminator
+pub fn deserialize_config(mut data: &[u8]) -> Result<ProgramConfig> {
+    ProgramConfig::try_deserialize(&mut data)
+}
@@ -0,0 +1,969 @@
+use crate::pda::{
+    find_allowance_address, find_config_address, find_event_authority_address,
+    find_referrer_stats_address, find_sale_address, get_proceeds_address, get_program_data_address,
+    get_vault_address,
+};
+use anchor_lang::{
+    prelude::{AccountMeta, Pubkey},
+    solana_program::{
+        ed25519_program, instruction::Instruction,
+        sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID,
+    },
+    system_program, InstructionData, ToAccountMetas,
+};
+use anchor_spl::associated_token::{
+    get_associated_token_address_with_program_id, ID as ASSOCIATED_TOKEN_PROGRAM_ID,
+};
+use whitelist_token_sale::{
+    accounts as ix_accounts, instruction as ix_data,
+    lottery::RandomnessSource,
+    pricing::PricingMode,
+    state::{SaleMode, VestingSchedule, WhitelistMode},
+    voucher::{Voucher, VOUCHER_MESSAGE_SIZE},
+    ID,
+};
+
+/// A mint, along with the token program owning it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenMint {
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+}
+
+impl TokenMint {
+    fn associated_address(&self, wallet: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(wallet, &self.mint, &self.token_program)
+    }
+}
+
+/// Optional accounts of instructions that move a sale's payment mint
+struct PaymentAccounts {
+    payment_mint: Option<Pubkey>,
+    signer_payment_ata: Option<Pubkey>,
+    proceeds_ata: Option<Pubkey>,
+    payment_token_program: Option<Pubkey>,
+}
+
+impl PaymentAccounts {
+    fn new(sale: &Pubkey, signer: &Pubkey, payment: Option<&TokenMint>) -> Self {
+        Self {
+            payment_mint: payment.map(|payment| payment.mint),
+            signer_payment_ata: payment.map(|payment| payment.associated_address(signer)),
+            proceeds_ata: payment
+                .map(|payment| get_proceeds_address(sale, &payment.mint, &payment.token_program)),
+            payment_token_program: payment.map(|payment| payment.token_program),
+        }
+    }
+}
+
+fn instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Accounts of the owner and operator instructions that only update the sale's settings
+fn sale_settings(signer: &Pubkey, sale_name: &str) -> (Pubkey, Pubkey) {
+    (find_sale_address(sale_name).0, *signer)
+}
+
+/// Creates the program config, signed by the program's upgrade authority
+pub fn initialize_config(
+    upgrade_authority: &Pubkey,
+    admin: &Pubkey,
+    fee_recipient: &Pubkey,
+    fee_bps: u16,
+) -> Instruction {
+    instruction(
+        ix_accounts::InitializeConfig {
+            config: find_config_address().0,
+            signer: *upgrade_authority,
+            program: ID,
+            program_data: get_program_data_address(),
+            system_program: system_program::ID,
+        },
+        ix_data::InitializeConfig {
+            admin: *admin,
+            fee_recipient: *fee_recipient,
+            fee_bps,
+        },
+    )
+}
+
+pub fn update_config(
+    admin: &Pubkey,
+    new_admin: &Pubkey,
+    fee_recipient: &Pubkey,
+    fee_bps: u16,
+) -> Instruction {
+    instruction(
+        ix_accounts::UpdateConfig {
+            config: find_config_address().0,
+            signer: *admin,
+        },
+        ix_data::UpdateConfig {
+            admin: *new_admin,
+            fee_recipient: *fee_recipient,
+            fee_bps,
+        },
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_whitelist_sale(
+    owner: &Pubkey,
+    sale_name: &str,
+    token: &TokenMint,
+    payment: Option<&TokenMint>,
+    lamports_per_token: u64,
+    max_tokens_per_buyer: u64,
+    max_buyers: u64,
+    sale_mode: SaleMode,
+    referral_bps: u16,
+) -> Instruction {
+    let sale = find_sale_address(sale_name).0;
+    let is_vault = sale_mode == SaleMode::Vault;
+
+    instruction(
+        ix_accounts::CreateWhitelistSale {
+            sale,
+            signer: *owner,
+            config: find_config_address().0,
+            token_program: token.token_program,
+            payment_token_program: payment.map(|payment| payment.token_program),
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            token_mint: token.mint,
+            vault_ata: is_vault
+                .then(|| get_vault_address(&sale, &token.mint, &token.token_program)),
+            signer_ata: is_vault.then(|| token.associated_address(owner)),
+            payment_mint: payment.map(|payment| payment.mint),
+            proceeds_ata: payment
+                .map(|payment| get_proceeds_address(&sale, &payment.mint, &payment.token_program)),
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::CreateWhitelistSale {
+            sale_name: sale_name.to_string(),
+            lamports_per_token,
+            max_tokens_per_buyer,
+            max_buyers,
+            sale_mode,
+            referral_bps,
+        },
+    )
+}
+
+pub fn migrate_whitelist_sale(owner: &Pubkey, sale_name: &str) -> Instruction {
+    instruction(
+        ix_accounts::MigrateWhitelistSale {
+            sale: find_sale_address(sale_name).0,
+            signer: *owner,
+            system_program: system_program::ID,
+        },
+        ix_data::MigrateWhitelistSale {
+            sale_name: sale_name.to_string(),
+        },
+    )
+}
+
+/// Migrates `buyer`'s allowance once the sale is migrated, signed by whoever pays for the extra rent
+pub fn migrate_allowance(payer: &Pubkey, sale_name: &str, buyer: &Pubkey) -> Instruction {
+    instruction(
+        ix_accounts::MigrateAllowance {
+            sale: find_sale_address(sale_name).0,
+            allowance: find_allowance_address(sale_name, buyer).0,
+            signer: *payer,
+            system_program: system_program::ID,
+        },
+        ix_data::MigrateAllowance {
+            sale_name: sale_name.to_string(),
+            buyer: *buyer,
+        },
+    )
+}
+
+pub fn update_sale_state(
+    operator: &Pubkey,
+    sale_name: &str,
+    is_registration_open: bool,
+    is_sale_open: bool,
+) -> Instruction {
+    let (sale, signer) = sale_settings(operator, sale_name);
+
+    instruction(
+        ix_accounts::UpdateSaleState {
+            sale,
+            signer,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::UpdateSaleState {
+            sale_name: sale_name.to_string(),
+            is_registration_open,
+            is_sale_open,
+        },
+    )
+}
+
+pub fn set_sale_windows(
+    owner: &Pubkey,
+    sale_name: &str,
+    registration_start: Option<i64>,
+    registration_end: Option<i64>,
+    sale_start: Option<i64>,
+    sale_end: Option<i64>,
+) -> Instruction {
+    let (sale, signer) = sale_settings(owner, sale_name);
+
+    instruction(
+        ix_accounts::SetSaleWindows { sale, signer },
+        ix_data::SetSaleWindows {
+            sale_name: sale_name.to_string(),
+            registration_start,
+            registration_end,
+            sale_start,
+            sale_end,
+        },
+    )
+}
+
+pub fn set_sale_caps(
+    owner: &Pubkey,
+    sale_name: &str,
+    total_tokens_for_sale: u64,
+    hard_cap_lamports: Option<u64>,
+) -> Instruction {
+    let (sale, signer) = sale_settings(owner, sale_name);
+
+    instruction(
+        ix_accounts::SetSaleCaps { sale, signer },
+        ix_data::SetSaleCaps {
+            sale_name: sale_name.to_string(),
+            total_tokens_for_sale,
+            hard_cap_lamports,
+        },
+    )
+}
+
+pub fn set_commit_mode(owner: &Pubkey, sale_name: &str, is_commit_mode: bool) -> Instruction {
+    let (sale, signer) = sale_settings(owner, sale_name);
+
+    instruction(
+        ix_accounts::SetCommitMode { sale, signer },
+        ix_data::SetCommitMode {
+            sale_name: sale_name.to_string(),
+            is_commit_mode,
+        },
+    )
+}
+
+pub fn set_pricing_mode(owner: &Pubkey, sale_name: &str, pricing_mode: PricingMode) -> Instruction {
+    let (sale, signer) = sale_settings(owner, sale_name);
+
+    instruction(
+        ix_accounts::SetPricingMode { sale, signer },
+        ix_data::SetPricingMode {
+            sale_name: sale_name.to_string(),
+            pricing_mode,
+        },
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn set_sale_phase(
+    owner: &Pubkey,
+    sale_name: &str,
+    index: u8,
+    start: i64,
+    end: i64,
+    lamports_per_token: u64,
+    max_tokens_per_buyer: u64,
+    max_tokens: Option<u64>,
+    requires_whitelist: bool,
+) -> Instruction {
+    let (sale, signer) = sale_settings(owner, sale_name);
+
+    instruction(
+        ix_accounts::SetSalePhase { sale, signer },
+        ix_data::SetSalePhase {
+            sale_name: sale_name.to_string(),
+            index,
+            start,
+            end,
+            lamports_per_token,
+            max_tokens_per_buyer,
+            max_tokens,
+            requires_whitelist,
+        },
+    )
+}
+
+pub fn set_soft_cap(owner: &Pubkey, sale_name: &str, soft_cap: u64, deadline: i64) -> Instruction {
+    let (sale, signer) = sale_settings(owner, sale_name);
+
+    instruction(
+        ix_accounts::SetSoftCap { sale, signer },
+        ix_data::SetSoftCap {
+            sale_name: sale_name.to_string(),
+            soft_cap,
+            deadline,
+        },
+    )
+}
+
+/// Signed by the owner, or by anyone once the soft cap's deadline or the commit window has passed.
+/// `fee_recipient` is the sale's fee recipient, only needed when the sale was created with a platform fee
+pub fn finalize_sale(
+    signer: &Pubkey,
+    sale_name: &str,
+    payment: Option<&TokenMint>,
+    fee_recipient: Option<&Pubkey>,
+) -> Instruction {
+    let sale = find_sale_address(sale_name).0;
+    let payment_accounts = PaymentAccounts::new(&sale, signer, payment);
+
+    instruction(
+        ix_accounts::FinalizeSale {
+            sale,
+            signer: *signer,
+            payment_mint: payment_accounts.payment_mint,
+            proceeds_ata: payment_accounts.proceeds_ata,
+            fee_recipient: fee_recipient.copied(),
+            fee_payment_ata: payment
+                .zip(fee_recipient)
+                .map(|(payment, fee_recipient)| payment.associated_address(fee_recipient)),
+            payment_token_program: payment_accounts.payment_token_program,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::FinalizeSale {
+            sale_name: sale_name.to_string(),
+        },
+    )
+}
+
+pub fn set_vesting_schedule(
+    owner: &Pubkey,
+    sale_name: &str,
+    vesting: Option<VestingSchedule>,
+) -> Instruction {
+    let (sale, signer) = sale_settings(owner, sale_name);
+
+    instruction(
+        ix_accounts::SetVestingSchedule { sale, signer },
+        ix_data::SetVestingSchedule {
+            sale_name: sale_name.to_string(),
+            vesting,
+        },
+    )
+}
+
+pub fn set_whitelist_mode(
+    owner: &Pubkey,
+    sale_name: &str,
+    whitelist_mode: WhitelistMode,
+) -> Instruction {
+    let (sale, signer) = sale_settings(owner, sale_name);
+
+    instruction(
+        ix_accounts::SetWhitelistMode { sale, signer },
+        ix_data::SetWhitelistMode {
+            sale_name: sale_name.to_string(),
+            whitelist_mode,
+        },
+    )
+}
+
+/// Adds `buyer` to a curated whitelist, along with `more_buyers` passed as remaining accounts
+pub fn add_to_whitelist(
+    operator: &Pubkey,
+    sale_name: &str,
+    buyer: &Pubkey,
+    more_buyers: &[Pubkey],
+) -> Instruction {
+    let (sale, signer) = sale_settings(operator, sale_name);
+
+    let mut ix = instruction(
+        ix_accounts::AddToWhitelist {
+            sale,
+            signer,
+            buyer: *buyer,
+            allowance: find_allowance_address(sale_name, buyer).0,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::AddToWhitelist {
+            sale_name: sale_name.to_string(),
+        },
+    );
+
+    for buyer in more_buyers {
+        ix.accounts.push(AccountMeta::new_readonly(*buyer, false));
+        ix.accounts.push(AccountMeta::new(
+            find_allowance_address(sale_name, buyer).0,
+            false,
+        ));
+    }
+
+    ix
+}
+
+/// Removes `buyer` from a curated whitelist, along with `more_buyers` passed as remaining accounts
+pub fn remove_from_whitelist(
+    operator: &Pubkey,
+    sale_name: &str,
+    buyer: &Pubkey,
+    more_buyers: &[Pubkey],
+) -> Instruction {
+    let (sale, signer) = sale_settings(operator, sale_name);
+
+    let mut ix = instruction(
+        ix_accounts::RemoveFromWhitelist {
+            sale,
+            signer,
+            allowance: find_allowance_address(sale_name, buyer).0,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::RemoveFromWhitelist {
+            sale_name: sale_name.to_string(),
+        },
+    );
+
+    for buyer in more_buyers {
+        ix.accounts.push(AccountMeta::new(
+            find_allowance_address(sale_name, buyer).0,
+            false,
+        ));
+    }
+
+    ix
+}
+
+pub fn set_whitelist_signer(
+    owner: &Pubkey,
+    sale_name: &str,
+    whitelist_signer: &Pubkey,
+) -> Instruction {
+    let (sale, signer) = sale_settings(owner, sale_name);
+
+    instruction(
+        ix_accounts::SetWhitelistSigner { sale, signer },
+        ix_data::SetWhitelistSigner {
+            sale_name: sale_name.to_string(),
+            whitelist_signer: *whitelist_signer,
+        },
+    )
+}
+
+pub fn update_merkle_root(owner: &Pubkey, sale_name: &str, merkle_root: [u8; 32]) -> Instruction {
+    let (sale, signer) = sale_settings(owner, sale_name);
+
+    instruction(
+        ix_accounts::UpdateMerkleRoot { sale, signer },
+        ix_data::UpdateMerkleRoot {
+            sale_name: sale_name.to_string(),
+            merkle_root,
+        },
+    )
+}
+
+pub fn set_randomness_source(
+    owner: &Pubkey,
+    sale_name: &str,
+    randomness_source: RandomnessSource,
+) -> Instruction {
+    let (sale, signer) = sale_settings(owner, sale_name);
+
+    instruction(
+        ix_accounts::SetRandomnessSource { sale, signer },
+        ix_data::SetRandomnessSource {
+            sale_name: sale_name.to_string(),
+            randomness_source,
+        },
+    )
+}
+
+/// `randomness_account` is the SlotHashes sysvar or the oracle account, depending on the sale's randomness source
+pub fn draw_lottery(
+    operator: &Pubkey,
+    sale_name: &str,
+    randomness_account: &Pubkey,
+) -> Instruction {
+    let (sale, signer) = sale_settings(operator, sale_name);
+
+    instruction(
+        ix_accounts::DrawLottery {
+            sale,
+            signer,
+            randomness_account: *randomness_account,
+        },
+        ix_data::DrawLottery {
+            sale_name: sale_name.to_string(),
+        },
+    )
+}
+
+pub fn settle_lottery_ticket(buyer: &Pubkey, sale_name: &str) -> Instruction {
+    instruction(
+        ix_accounts::SettleLotteryTicket {
+            sale: find_sale_address(sale_name).0,
+            allowance: find_allowance_address(sale_name, buyer).0,
+            signer: *buyer,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::SettleLotteryTicket {
+            sale_name: sale_name.to_string(),
+        },
+    )
+}
+
+/// Ed25519 precompile instruction verifying a voucher signed by the sale's whitelist signer.
+/// It has to be placed right before the buyer's `register_for_whitelist` instruction.
+pub fn verify_voucher(
+    whitelist_signer: &Pubkey,
+    signature: &[u8; 64],
+    voucher: &Voucher,
+) -> Instruction {
+    const HEADER_SIZE: u16 = 16;
+    const PUBLIC_KEY_OFFSET: u16 = HEADER_SIZE;
+    const SIGNATURE_OFFSET: u16 = PUBLIC_KEY_OFFSET + 32;
+    const MESSAGE_OFFSET: u16 = SIGNATURE_OFFSET + 64;
+    // Every offset points into this instruction's own data
+    const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+    let mut data = vec![1, 0];
+    for offset in [
+        SIGNATURE_OFFSET,
+        CURRENT_INSTRUCTION,
+        PUBLIC_KEY_OFFSET,
+        CURRENT_INSTRUCTION,
+        MESSAGE_OFFSET,
+        VOUCHER_MESSAGE_SIZE as u16,
+        CURRENT_INSTRUCTION,
+    ] {
+        data.extend_from_slice(&offset.to_le_bytes());
+    }
+    data.extend_from_slice(whitelist_signer.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(&voucher.to_message());
+
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// For voucher whitelists, the voucher's `verify_voucher` instruction has to come right before this one
+/// `referrer` has to be registered for the sale already
+pub fn register_for_whitelist(
+    buyer: &Pubkey,
+    sale_name: &str,
+    referrer: Option<&Pubkey>,
+) -> Instruction {
+    instruction(
+        ix_accounts::RegisterForWhitelist {
+            sale: find_sale_address(sale_name).0,
+            signer: *buyer,
+            allowance: find_allowance_address(sale_name, buyer).0,
+            instructions_sysvar: INSTRUCTIONS_SYSVAR_ID,
+            referrer: referrer.copied(),
+            referrer_allowance: referrer
+                .map(|referrer| find_allowance_address(sale_name, referrer).0),
+            referrer_stats: referrer
+                .map(|referrer| find_referrer_stats_address(sale_name, referrer).0),
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::RegisterForWhitelist {
+            sale_name: sale_name.to_string(),
+        },
+    )
+}
+
+pub fn register_with_merkle_proof(
+    buyer: &Pubkey,
+    sale_name: &str,
+    allocation: u64,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    instruction(
+        ix_accounts::RegisterWithMerkleProof {
+            sale: find_sale_address(sale_name).0,
+            signer: *buyer,
+            allowance: find_allowance_address(sale_name, buyer).0,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::RegisterWithMerkleProof {
+            sale_name: sale_name.to_string(),
+            allocation,
+            proof,
+        },
+    )
+}
+
+/// `fee_recipient` is the sale's fee recipient, only needed when the sale was created with a platform fee,
+/// and `referrer` is the referrer recorded on the buyer's allowance
+#[allow(clippy::too_many_arguments)]
+pub fn buy_tokens(
+    buyer: &Pubkey,
+    sale_name: &str,
+    token: &TokenMint,
+    payment: Option<&TokenMint>,
+    fee_recipient: Option<&Pubkey>,
+    referrer: Option<&Pubkey>,
+    sale_mode: SaleMode,
+    amount: u64,
+) -> Instruction {
+    let sale = find_sale_address(sale_name).0;
+    let payment_accounts = PaymentAccounts::new(&sale, buyer, payment);
+
+    instruction(
+        ix_accounts::BuyTokens {
+            sale,
+            allowance: find_allowance_address(sale_name, buyer).0,
+            vault_ata: (sale_mode == SaleMode::Vault)
+                .then(|| get_vault_address(&sale, &token.mint, &token.token_program)),
+            signer: *buyer,
+            signer_ata: token.associated_address(buyer),
+            token_mint: token.mint,
+            payment_mint: payment_accounts.payment_mint,
+            signer_payment_ata: payment_accounts.signer_payment_ata,
+            proceeds_ata: payment_accounts.proceeds_ata,
+            fee_recipient: fee_recipient.copied(),
+            fee_payment_ata: payment
+                .zip(fee_recipient)
+                .map(|(payment, fee_recipient)| payment.associated_address(fee_recipient)),
+            referrer_stats: referrer
+                .map(|referrer| find_referrer_stats_address(sale_name, referrer).0),
+            token_program: token.token_program,
+            payment_token_program: payment_accounts.payment_token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::BuyTokens {
+            sale_name: sale_name.to_string(),
+            amount,
+        },
+    )
+}
+
+pub fn commit(
+    buyer: &Pubkey,
+    sale_name: &str,
+    payment: Option<&TokenMint>,
+    lamports: u64,
+) -> Instruction {
+    let sale = find_sale_address(sale_name).0;
+    let payment_accounts = PaymentAccounts::new(&sale, buyer, payment);
+
+    instruction(
+        ix_accounts::Commit {
+            sale,
+            allowance: find_allowance_address(sale_name, buyer).0,
+            signer: *buyer,
+            payment_mint: payment_accounts.payment_mint,
+            signer_payment_ata: payment_accounts.signer_payment_ata,
+            proceeds_ata: payment_accounts.proceeds_ata,
+            payment_token_program: payment_accounts.payment_token_program,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::Commit {
+            sale_name: sale_name.to_string(),
+            lamports,
+        },
+    )
+}
+
+/// `fee_recipient` is the sale's fee recipient, only needed when the sale was created with a platform fee,
+/// and `referrer` is the referrer recorded on the buyer's allowance
+pub fn settle_commitment(
+    buyer: &Pubkey,
+    sale_name: &str,
+    token_mint: &Pubkey,
+    payment: Option<&TokenMint>,
+    fee_recipient: Option<&Pubkey>,
+    referrer: Option<&Pubkey>,
+) -> Instruction {
+    let sale = find_sale_address(sale_name).0;
+    let payment_accounts = PaymentAccounts::new(&sale, buyer, payment);
+
+    instruction(
+        ix_accounts::SettleCommitment {
+            sale,
+            allowance: find_allowance_address(sale_name, buyer).0,
+            signer: *buyer,
+            token_mint: *token_mint,
+            payment_mint: payment_accounts.payment_mint,
+            signer_payment_ata: payment_accounts.signer_payment_ata,
+            proceeds_ata: payment_accounts.proceeds_ata,
+            fee_recipient: fee_recipient.copied(),
+            fee_payment_ata: payment
+                .zip(fee_recipient)
+                .map(|(payment, fee_recipient)| payment.associated_address(fee_recipient)),
+            referrer_stats: referrer
+                .map(|referrer| find_referrer_stats_address(sale_name, referrer).0),
+            payment_token_program: payment_accounts.payment_token_program,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::SettleCommitment {
+            sale_name: sale_name.to_string(),
+        },
+    )
+}
+
+/// Claims the buyer's unlocked tokens, which also releases tokens held back for a soft cap or by commit mode
+pub fn claim_vested(
+    buyer: &Pubkey,
+    sale_name: &str,
+    token: &TokenMint,
+    sale_mode: SaleMode,
+) -> Instruction {
+    let sale = find_sale_address(sale_name).0;
+
+    instruction(
+        ix_accounts::ClaimVested {
+            sale,
+            allowance: find_allowance_address(sale_name, buyer).0,
+            vault_ata: (sale_mode == SaleMode::Vault)
+                .then(|| get_vault_address(&sale, &token.mint, &token.token_program)),
+            signer: *buyer,
+            signer_ata: token.associated_address(buyer),
+            token_mint: token.mint,
+            token_program: token.token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::ClaimVested {
+            sale_name: sale_name.to_string(),
+        },
+    )
+}
+
+pub fn claim_rebate(buyer: &Pubkey, sale_name: &str, payment: Option<&TokenMint>) -> Instruction {
+    let sale = find_sale_address(sale_name).0;
+    let payment_accounts = PaymentAccounts::new(&sale, buyer, payment);
+
+    instruction(
+        ix_accounts::ClaimRebate {
+            sale,
+            allowance: find_allowance_address(sale_name, buyer).0,
+            signer: *buyer,
+            payment_mint: payment_accounts.payment_mint,
+            signer_payment_ata: payment_accounts.signer_payment_ata,
+            proceeds_ata: payment_accounts.proceeds_ata,
+            payment_token_program: payment_accounts.payment_token_program,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::ClaimRebate {
+            sale_name: sale_name.to_string(),
+        },
+    )
+}
+
+pub fn claim_referral_rewards(
+    referrer: &Pubkey,
+    sale_name: &str,
+    payment: Option<&TokenMint>,
+) -> Instruction {
+    let sale = find_sale_address(sale_name).0;
+    let payment_accounts = PaymentAccounts::new(&sale, referrer, payment);
+
+    instruction(
+        ix_accounts::ClaimReferralRewards {
+            sale,
+            referrer_stats: find_referrer_stats_address(sale_name, referrer).0,
+            signer: *referrer,
+            payment_mint: payment_accounts.payment_mint,
+            signer_payment_ata: payment_accounts.signer_payment_ata,
+            proceeds_ata: payment_accounts.proceeds_ata,
+            payment_token_program: payment_accounts.payment_token_program,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::ClaimReferralRewards {
+            sale_name: sale_name.to_string(),
+        },
+    )
+}
+
+pub fn claim_refund(
+    buyer: &Pubkey,
+    sale_name: &str,
+    token_mint: &Pubkey,
+    payment: Option<&TokenMint>,
+) -> Instruction {
+    let sale = find_sale_address(sale_name).0;
+    let payment_accounts = PaymentAccounts::new(&sale, buyer, payment);
+
+    instruction(
+        ix_accounts::ClaimRefund {
+            sale,
+            allowance: find_allowance_address(sale_name, buyer).0,
+            signer: *buyer,
+            token_mint: *token_mint,
+            payment_mint: payment_accounts.payment_mint,
+            signer_payment_ata: payment_accounts.signer_payment_ata,
+            proceeds_ata: payment_accounts.proceeds_ata,
+            payment_token_program: payment_accounts.payment_token_program,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::ClaimRefund {
+            sale_name: sale_name.to_string(),
+        },
+    )
+}
+
+pub fn set_treasury(owner: &Pubkey, sale_name: &str, treasury: &Pubkey) -> Instruction {
+    let (sale, signer) = sale_settings(owner, sale_name);
+
+    instruction(
+        ix_accounts::SetTreasury { sale, signer },
+        ix_data::SetTreasury {
+            sale_name: sale_name.to_string(),
+            treasury: *treasury,
+        },
+    )
+}
+
+/// Makes `operator` able to run the sale alongside the owner, or removes the operator when it's `None`
+pub fn set_operator(owner: &Pubkey, sale_name: &str, operator: Option<&Pubkey>) -> Instruction {
+    let (sale, signer) = sale_settings(owner, sale_name);
+
+    instruction(
+        ix_accounts::SetOperator {
+            sale,
+            signer,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::SetOperator {
+            sale_name: sale_name.to_string(),
+            operator: operator.copied(),
+        },
+    )
+}
+
+/// Proposes `pending_owner` as the sale's next owner, or withdraws the proposal when it's `None`
+pub fn propose_owner(
+    owner: &Pubkey,
+    sale_name: &str,
+    pending_owner: Option<&Pubkey>,
+) -> Instruction {
+    let (sale, signer) = sale_settings(owner, sale_name);
+
+    instruction(
+        ix_accounts::ProposeOwner {
+            sale,
+            signer,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::ProposeOwner {
+            sale_name: sale_name.to_string(),
+            pending_owner: pending_owner.copied(),
+        },
+    )
+}
+
+/// Signed by the sale's pending owner
+pub fn accept_ownership(pending_owner: &Pubkey, sale_name: &str) -> Instruction {
+    instruction(
+        ix_accounts::AcceptOwnership {
+            sale: find_sale_address(sale_name).0,
+            signer: *pending_owner,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::AcceptOwnership {
+            sale_name: sale_name.to_string(),
+        },
+    )
+}
+
+/// `treasury` has to be the sale's treasury, which is the owner unless set otherwise
+pub fn withdraw_proceeds(
+    owner: &Pubkey,
+    sale_name: &str,
+    treasury: &Pubkey,
+    payment: Option<&TokenMint>,
+) -> Instruction {
+    let sale = find_sale_address(sale_name).0;
+    let payment_accounts = PaymentAccounts::new(&sale, treasury, payment);
+
+    instruction(
+        ix_accounts::WithdrawProceeds {
+            sale,
+            signer: *owner,
+            treasury: *treasury,
+            payment_mint: payment_accounts.payment_mint,
+            proceeds_ata: payment_accounts.proceeds_ata,
+            treasury_payment_ata: payment_accounts.signer_payment_ata,
+            payment_token_program: payment_accounts.payment_token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::WithdrawProceeds {
+            sale_name: sale_name.to_string(),
+        },
+    )
+}
+
+pub fn close_whitelist_sale(
+    owner: &Pubkey,
+    sale_name: &str,
+    token: &TokenMint,
+    payment: Option<&TokenMint>,
+    sale_mode: SaleMode,
+) -> Instruction {
+    let sale = find_sale_address(sale_name).0;
+    let payment_accounts = PaymentAccounts::new(&sale, owner, payment);
+    let is_vault = sale_mode == SaleMode::Vault;
+
+    instruction(
+        ix_accounts::CloseWhitelistSale {
+            sale,
+            vault_ata: is_vault
+                .then(|| get_vault_address(&sale, &token.mint, &token.token_program)),
+            signer: *owner,
+            token_program: token.token_program,
+            payment_token_program: payment_accounts.payment_token_program,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            token_mint: token.mint,
+            signer_ata: is_vault.then(|| token.associated_address(owner)),
+            payment_mint: payment_accounts.payment_mint,
+            proceeds_ata: payment_accounts.proceeds_ata,
+            signer_payment_ata: payment_accounts.signer_payment_ata,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ID,
+        },
+        ix_data::CloseWhitelistSale {
+            sale_name: sale_name.to_string(),
+        },
+    )
+}
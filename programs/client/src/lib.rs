@@ -0,0 +1,13 @@
+//! Off-chain client for the whitelist token sale program.
+//!
+//! Builds the program's instructions and derives its addresses without linking its entrypoint,
+//! so it can be used by backends and bots alongside any RPC client.
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+
+pub use whitelist_token_sale::{
+    constants, error::WhitelistError, events, lottery::RandomnessSource, merkle, pricing, state,
+    voucher, ID,
+};
@@ -0,0 +1,62 @@
+use anchor_lang::{prelude::Pubkey, solana_program::bpf_loader_upgradeable};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use whitelist_token_sale::{
+    constants::{PDA_SEED_ALLOWANCE, PDA_SEED_CONFIG, PDA_SEED_REFERRER, PDA_SEED_SALE},
+    ID,
+};
+
+/// Seed of the PDA signing the program's event CPIs
+const PDA_SEED_EVENT_AUTHORITY: &[u8] = b"__event_authority";
+
+pub fn find_sale_address(sale_name: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()], &ID)
+}
+
+pub fn find_allowance_address(sale_name: &str, buyer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PDA_SEED_ALLOWANCE.as_ref(),
+            sale_name.as_bytes(),
+            buyer.as_ref(),
+        ],
+        &ID,
+    )
+}
+
+pub fn find_referrer_stats_address(sale_name: &str, referrer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PDA_SEED_REFERRER.as_ref(),
+            sale_name.as_bytes(),
+            referrer.as_ref(),
+        ],
+        &ID,
+    )
+}
+
+pub fn find_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PDA_SEED_CONFIG.as_ref()], &ID)
+}
+
+/// Program data account of the program, holding its upgrade authority
+pub fn get_program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+pub fn find_event_authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PDA_SEED_EVENT_AUTHORITY], &ID)
+}
+
+/// Vault of a vault sale, holding the tokens for sale
+pub fn get_vault_address(sale: &Pubkey, token_mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(sale, token_mint, token_program)
+}
+
+/// Account of a sale paid in a payment mint, collecting the payments
+pub fn get_proceeds_address(
+    sale: &Pubkey,
+    payment_mint: &Pubkey,
+    payment_token_program: &Pubkey,
+) -> Pubkey {
+    get_associated_token_address_with_program_id(sale, payment_mint, payment_token_program)
+}
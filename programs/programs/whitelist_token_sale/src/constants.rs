@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[constant]
+pub const PDA_SEED_SALE: &[u8; 4] = b"sale";
+
+#[constant]
+pub const PDA_SEED_ALLOWANCE: &[u8; 9] = b"allowance";
+
+#[constant]
+pub const PDA_SEED_REFERRER: &[u8; 8] = b"referrer";
+
+#[constant]
+pub const PDA_SEED_CONFIG: &[u8; 6] = b"config";
+
+/// Maximum number of phases a sale can be split into
+pub const MAX_SALE_PHASES: usize = 4;
+
+#[constant]
+pub const BPS_DENOMINATOR: u16 = 10_000;
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum WhitelistError {
+    #[msg("Only the sale owner can perform this action")]
+    OnlyOwner,
+    #[msg("Only the sale owner or its operator can perform this action")]
+    OnlyOperator,
+    #[msg("Whitelist registration is closed")]
+    WhitelistRegistrationClosed,
+    #[msg("Whiteliste sale is closed")]
+    SaleClosed,
+    #[msg("Token purchase is larger than remaining allowance")]
+    AllowanceExceeded,
+    #[msg("Mismatch in mints of provided accounts")]
+    MintMismatch,
+    #[msg("The maximum number of registered buyers has been reached")]
+    BuyerLimitReached,
+    #[msg("Buyer has not been approved for this whitelist")]
+    NotWhitelisted,
+    #[msg("Buyers can't register themselves for a curated whitelist")]
+    SelfRegistrationDisabled,
+    #[msg("Whitelist mode can't be changed once buyers are registered")]
+    WhitelistModeLocked,
+    #[msg("This action isn't available in the sale's whitelist mode")]
+    WhitelistModeMismatch,
+    #[msg("Remaining accounts don't match the expected layout")]
+    InvalidRemainingAccounts,
+    #[msg("Merkle proof doesn't match the sale's whitelist root")]
+    InvalidMerkleProof,
+    #[msg("Missing or invalid whitelist voucher")]
+    InvalidVoucher,
+    #[msg("Whitelist voucher has expired")]
+    VoucherExpired,
+    #[msg("Payment mint and token accounts are required for this sale")]
+    MissingPaymentAccounts,
+    #[msg("Time window must end after it starts")]
+    InvalidTimeWindow,
+    #[msg("Phases can only be added or edited before they start")]
+    PhaseAlreadyStarted,
+    #[msg("Phase doesn't exist or the sale has no room for more phases")]
+    InvalidPhase,
+    #[msg("Phase overlaps another phase of the sale")]
+    PhaseOverlap,
+    #[msg("All tokens of the current phase have been sold")]
+    PhaseSoldOut,
+    #[msg("Vesting schedule is invalid")]
+    InvalidVestingSchedule,
+    #[msg("Vesting schedule can't be changed once buyers have held back or claimed tokens")]
+    VestingLocked,
+    #[msg("Sale can't be closed while buyers have unclaimed tokens, refunds or rebates")]
+    ClaimsOutstanding,
+    #[msg("There are no unlocked tokens to claim")]
+    NothingToClaim,
+    #[msg("Soft cap can't be changed once the sale has raised funds")]
+    SoftCapLocked,
+    #[msg("Soft cap hasn't been reached")]
+    SoftCapNotReached,
+    #[msg("Sale hasn't been finalized")]
+    SaleNotFinalized,
+    #[msg("Refunds are only available when the soft cap was missed by the deadline")]
+    RefundUnavailable,
+    #[msg("All tokens for sale have been sold")]
+    SaleSoldOut,
+    #[msg("Purchase would exceed the sale's hard cap")]
+    HardCapReached,
+    #[msg("Sale caps must cover what has been sold or raised and fit the tokens available")]
+    InvalidSaleCaps,
+    #[msg("Pricing mode is invalid or conflicts with the sale's phases")]
+    InvalidPricingMode,
+    #[msg("Pricing mode can't be changed once tokens are sold")]
+    PricingModeLocked,
+    #[msg("Rebates are only available once a uniform clearing auction is over")]
+    RebateUnavailable,
+    #[msg("Purchase cost is too large to be represented")]
+    PriceOverflow,
+    #[msg("This action isn't available with the sale's commit mode setting")]
+    CommitModeMismatch,
+    #[msg("Commit mode and its supply can't be changed once tokens are committed or settled")]
+    CommitModeLocked,
+    #[msg("Commit mode can't be combined with phases, pricing modes or a soft cap")]
+    CommitModeConflict,
+    #[msg("Randomness account doesn't match the sale's randomness source")]
+    InvalidRandomnessSource,
+    #[msg("Randomness hasn't been provided yet")]
+    RandomnessUnavailable,
+    #[msg("Lottery has already been drawn")]
+    LotteryAlreadyDrawn,
+    #[msg("Lottery hasn't been drawn yet")]
+    LotteryNotDrawn,
+    #[msg("Vault token accounts are required for this sale")]
+    MissingVaultAccounts,
+    #[msg("Sale account doesn't need to be migrated")]
+    SaleAlreadyMigrated,
+    #[msg("Allowance account doesn't need to be migrated")]
+    AllowanceAlreadyMigrated,
+    #[msg("Account doesn't match the sale's treasury")]
+    TreasuryMismatch,
+    #[msg("There are no proceeds available to withdraw")]
+    NothingToWithdraw,
+    #[msg("Only the program's upgrade authority can perform this action")]
+    OnlyUpgradeAuthority,
+    #[msg("Only the program config's admin can perform this action")]
+    OnlyAdmin,
+    #[msg("Fee can't be more than 100%")]
+    InvalidFee,
+    #[msg("Account doesn't match the sale's fee recipient")]
+    FeeRecipientMismatch,
+    #[msg("Fee recipient accounts are required for this sale")]
+    MissingFeeAccounts,
+    #[msg("Referral and platform fees can't add up to more than 100%")]
+    InvalidReferralFee,
+    #[msg("Buyers can't refer themselves")]
+    SelfReferral,
+    #[msg("Referrers have to register for the sale before the buyers they refer")]
+    ReferrerNotRegistered,
+    #[msg("Referrer accounts are missing or don't match the buyer's referrer")]
+    ReferrerMismatch,
+    #[msg("Referral rewards are only available once the sale's soft cap is reached")]
+    ReferralRewardsUnavailable,
+    #[msg("Only the sale's pending owner can accept ownership")]
+    OnlyPendingOwner,
+    #[msg("Randomness source can't be changed once lottery tickets are registered")]
+    RandomnessSourceLocked,
+}
@@ -0,0 +1,213 @@
+use crate::state::SaleMode;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct SaleCreated {
+    pub sale: Pubkey,
+    pub owner: Pubkey,
+    pub sale_mode: SaleMode,
+    pub token_mint: Pubkey,
+    pub payment_mint: Option<Pubkey>,
+    pub lamports_per_token: u64,
+    pub max_tokens_per_buyer: u64,
+    pub max_buyers: u64,
+    pub total_tokens_for_sale: u64,
+    /// Platform fee taken from each purchase, in basis points
+    pub fee_bps: u16,
+    /// Share of referred buyers' payments rewarded to their referrer, in basis points
+    pub referral_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SaleStateUpdated {
+    pub sale: Pubkey,
+    pub owner: Pubkey,
+    /// Signer of the update, either the owner or the sale's operator
+    pub actor: Pubkey,
+    pub is_registration_open: bool,
+    pub is_sale_open: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BuyerRegistered {
+    pub sale: Pubkey,
+    pub buyer: Pubkey,
+    /// Lottery registrations are only approved once their ticket wins the draw
+    pub is_approved: bool,
+    pub allocation: Option<u64>,
+    pub referrer: Option<Pubkey>,
+    pub num_buyers: u64,
+    pub num_tickets: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensPurchased {
+    pub sale: Pubkey,
+    pub buyer: Pubkey,
+    /// Whole tokens bought
+    pub amount: u64,
+    /// Paid in lamports, or in base units of the sale's payment mint
+    pub cost: u64,
+    /// Average price per token of this purchase
+    pub price: u64,
+    /// Share of the cost paid to the platform's fee recipient
+    pub fee: u64,
+    /// Share of the cost rewarded to the buyer's referrer
+    pub referral_reward: u64,
+    /// Whether the tokens are held back until `claim_vested`
+    pub is_held: bool,
+    pub buyer_tokens_bought: u64,
+    pub tokens_sold: u64,
+    pub lamports_raised: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SaleClosed {
+    pub sale: Pubkey,
+    pub owner: Pubkey,
+    pub num_buyers: u64,
+    pub tokens_sold: u64,
+    pub lamports_raised: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OwnershipProposed {
+    pub sale: Pubkey,
+    pub owner: Pubkey,
+    /// Cleared when the proposal is withdrawn
+    pub pending_owner: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OwnershipTransferred {
+    pub sale: Pubkey,
+    pub previous_owner: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OperatorUpdated {
+    pub sale: Pubkey,
+    pub owner: Pubkey,
+    /// Cleared when the operator is removed
+    pub operator: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BuyerRemoved {
+    pub sale: Pubkey,
+    pub buyer: Pubkey,
+    pub num_buyers: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LotteryTicketSettled {
+    pub sale: Pubkey,
+    pub buyer: Pubkey,
+    pub ticket_number: u64,
+    /// Winners are approved, while losing tickets are closed to give their rent back
+    pub is_winner: bool,
+    pub num_buyers: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensClaimed {
+    pub sale: Pubkey,
+    pub buyer: Pubkey,
+    /// Base units of the token released by this claim
+    pub amount: u64,
+    /// Base units released to the buyer so far
+    pub buyer_tokens_claimed: u64,
+    pub tokens_owed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub sale: Pubkey,
+    pub buyer: Pubkey,
+    /// Paid back in lamports, or in base units of the sale's payment mint
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RebateClaimed {
+    pub sale: Pubkey,
+    pub buyer: Pubkey,
+    /// Paid back in lamports, or in base units of the sale's payment mint
+    pub amount: u64,
+    pub clearing_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralRewardsClaimed {
+    pub sale: Pubkey,
+    pub referrer: Pubkey,
+    /// Paid in lamports, or in base units of the sale's payment mint
+    pub amount: u64,
+    pub rewards_claimed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PaymentCommitted {
+    pub sale: Pubkey,
+    pub buyer: Pubkey,
+    /// Paid in lamports, or in base units of the sale's payment mint
+    pub amount: u64,
+    pub buyer_committed: u64,
+    pub total_committed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommitmentSettled {
+    pub sale: Pubkey,
+    pub buyer: Pubkey,
+    /// Whole tokens allocated, held back until `claim_vested`
+    pub tokens: u64,
+    /// Share of the commitment paid for the allocated tokens
+    pub cost: u64,
+    /// Rest of the commitment, paid back to the buyer
+    pub refund: u64,
+    /// Share of the cost paid to the platform's fee recipient
+    pub fee: u64,
+    /// Share of the cost owed to the buyer's referrer
+    pub referral_reward: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SaleFinalized {
+    pub sale: Pubkey,
+    /// Signer of the finalization, either the owner or anyone once the deadline or commit window has passed
+    pub actor: Pubkey,
+    pub tokens_sold: u64,
+    pub lamports_raised: u64,
+    pub total_committed: u64,
+    /// Fees held back from purchases while the soft cap was pending, paid to the fee recipient
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProceedsWithdrawn {
+    pub sale: Pubkey,
+    pub treasury: Pubkey,
+    /// Paid in lamports, or in base units of the sale's payment mint
+    pub amount: u64,
+    pub lamports_withdrawn: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,75 @@
+pub mod accept_ownership;
+pub mod add_to_whitelist;
+pub mod buy_tokens;
+pub mod claim_rebate;
+pub mod claim_referral_rewards;
+pub mod claim_refund;
+pub mod claim_vested;
+pub mod close_whitelist_sale;
+pub mod commit;
+pub mod create_whitelist_sale;
+pub mod draw_lottery;
+pub mod finalize_sale;
+pub mod initialize_config;
+pub mod migrate_allowance;
+pub mod migrate_whitelist_sale;
+pub mod propose_owner;
+pub mod register_for_whitelist;
+pub mod register_with_merkle_proof;
+pub mod remove_from_whitelist;
+pub mod set_commit_mode;
+pub mod set_operator;
+pub mod set_pricing_mode;
+pub mod set_randomness_source;
+pub mod set_sale_caps;
+pub mod set_sale_phase;
+pub mod set_sale_windows;
+pub mod set_soft_cap;
+pub mod set_treasury;
+pub mod set_vesting_schedule;
+pub mod set_whitelist_mode;
+pub mod set_whitelist_signer;
+pub mod settle_commitment;
+pub mod settle_lottery_ticket;
+pub mod update_config;
+pub mod update_merkle_root;
+pub mod update_sale_state;
+pub mod withdraw_proceeds;
+
+pub use accept_ownership::*;
+pub use add_to_whitelist::*;
+pub use buy_tokens::*;
+pub use claim_rebate::*;
+pub use claim_referral_rewards::*;
+pub use claim_refund::*;
+pub use claim_vested::*;
+pub use close_whitelist_sale::*;
+pub use commit::*;
+pub use create_whitelist_sale::*;
+pub use draw_lottery::*;
+pub use finalize_sale::*;
+pub use initialize_config::*;
+pub use migrate_allowance::*;
+pub use migrate_whitelist_sale::*;
+pub use propose_owner::*;
+pub use register_for_whitelist::*;
+pub use register_with_merkle_proof::*;
+pub use remove_from_whitelist::*;
+pub use set_commit_mode::*;
+pub use set_operator::*;
+pub use set_pricing_mode::*;
+pub use set_randomness_source::*;
+pub use set_sale_caps::*;
+pub use set_sale_phase::*;
+pub use set_sale_windows::*;
+pub use set_soft_cap::*;
+pub use set_treasury::*;
+pub use set_vesting_schedule::*;
+pub use set_whitelist_mode::*;
+pub use set_whitelist_signer::*;
+pub use settle_commitment::*;
+pub use settle_lottery_ticket::*;
+pub use update_config::*;
+pub use update_merkle_root::*;
+pub use update_sale_state::*;
+pub use withdraw_proceeds::*;
@@ -0,0 +1,49 @@
+use crate::{
+    constants::PDA_SEED_SALE, error::WhitelistError, events::OwnershipTransferred,
+    state::WhitelistSale,
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct AcceptOwnership<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/// Makes the pending owner the sale's owner, with every permission of the previous one
+pub fn handle_accept_ownership(ctx: Context<AcceptOwnership>, _sale_name: String) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let signer = &ctx.accounts.signer;
+
+    require!(
+        sale.pending_owner.eq(&Some(signer.key())),
+        WhitelistError::OnlyPendingOwner
+    );
+
+    let previous_owner = sale.owner;
+    sale.owner = signer.key();
+    sale.pending_owner = None;
+
+    // Proceeds follow the owner, unless they were sent somewhere else
+    if sale.treasury.eq(&previous_owner) {
+        sale.treasury = sale.owner;
+    }
+
+    emit_cpi!(OwnershipTransferred {
+        sale: sale.key(),
+        previous_owner,
+        owner: sale.owner,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
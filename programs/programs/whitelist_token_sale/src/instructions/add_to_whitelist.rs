@@ -0,0 +1,222 @@
+use crate::{
+    constants::{MAX_SALE_PHASES, PDA_SEED_ALLOWANCE, PDA_SEED_SALE},
+    error::WhitelistError,
+    events::BuyerRegistered,
+    state::{Allowance, WhitelistMode, WhitelistSale},
+};
+use anchor_lang::{
+    prelude::*,
+    system_program::{
+        allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+    },
+};
+use std::mem::size_of;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct AddToWhitelist<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: Only used as the seed of the buyer's allowance PDA
+    pub buyer: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer=signer,
+        space=size_of::<Allowance>() + 8,
+        seeds=[PDA_SEED_ALLOWANCE.as_ref(), sale_name.as_bytes(), buyer.key().as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Approves `buyer`, along with any additional buyers passed as
+/// `(buyer, allowance)` pairs through the remaining accounts
+pub fn handle_add_to_whitelist<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddToWhitelist<'info>>,
+    sale_name: String,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+
+    require!(
+        sale.is_operator(ctx.accounts.signer.key),
+        WhitelistError::OnlyOperator
+    );
+
+    require!(
+        sale.whitelist_mode == WhitelistMode::Curated,
+        WhitelistError::WhitelistModeMismatch
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let allowance = &mut ctx.accounts.allowance;
+
+    allowance.buyer = ctx.accounts.buyer.key();
+
+    sale.approve_buyer(allowance)?;
+
+    emit_cpi!(buyer_registered(sale, allowance, now));
+
+    let pairs = ctx.remaining_accounts.chunks_exact(2);
+
+    require!(
+        pairs.remainder().is_empty(),
+        WhitelistError::InvalidRemainingAccounts
+    );
+
+    for pair in pairs {
+        let (buyer, allowance_info) = (&pair[0], &pair[1]);
+
+        let (allowance_address, bump) = Pubkey::find_program_address(
+            &[
+                PDA_SEED_ALLOWANCE.as_ref(),
+                sale_name.as_bytes(),
+                buyer.key.as_ref(),
+            ],
+            ctx.program_id,
+        );
+
+        require_keys_eq!(
+            allowance_info.key(),
+            allowance_address,
+            WhitelistError::InvalidRemainingAccounts
+        );
+
+        // The named allowance is only written back once the instruction finishes
+        require_keys_neq!(
+            allowance_info.key(),
+            ctx.accounts.allowance.key(),
+            WhitelistError::InvalidRemainingAccounts
+        );
+
+        // Allowances that don't exist yet are created the same way `init_if_needed` would
+        if allowance_info.owner.eq(&System::id()) {
+            let seeds = [
+                PDA_SEED_ALLOWANCE.as_ref(),
+                sale_name.as_bytes(),
+                buyer.key.as_ref(),
+                &[bump],
+            ];
+
+            create_allowance_account(
+                &ctx.accounts.signer,
+                allowance_info,
+                &ctx.accounts.system_program,
+                &seeds,
+            )?;
+
+            let new_allowance = Allowance {
+                buyer: buyer.key(),
+                tokens_bought: 0,
+                is_approved: false,
+                allocation: None,
+                phase_tokens_bought: [0; MAX_SALE_PHASES],
+                tokens_held: 0,
+                tokens_claimed: 0,
+                lamports_paid: 0,
+                lamports_committed: 0,
+                ticket_number: 0,
+                referrer: None,
+            };
+
+            new_allowance.try_serialize(&mut &mut allowance_info.try_borrow_mut_data()?[..])?;
+        }
+
+        let mut allowance = Account::<Allowance>::try_from(allowance_info)?;
+
+        sale.approve_buyer(&mut allowance)?;
+
+        emit_cpi!(buyer_registered(sale, &allowance, now));
+
+        allowance.exit(ctx.program_id)?;
+    }
+
+    Ok(())
+}
+
+fn buyer_registered(
+    sale: &Account<WhitelistSale>,
+    allowance: &Allowance,
+    now: i64,
+) -> BuyerRegistered {
+    BuyerRegistered {
+        sale: sale.key(),
+        buyer: allowance.buyer,
+        is_approved: allowance.is_approved,
+        allocation: allowance.allocation,
+        referrer: allowance.referrer,
+        num_buyers: sale.num_buyers,
+        num_tickets: sale.num_tickets,
+        timestamp: now,
+    }
+}
+
+fn create_allowance_account<'info>(
+    payer: &Signer<'info>,
+    allowance: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let space = size_of::<Allowance>() + 8;
+    let rent_exempt_lamports = Rent::get()?.minimum_balance(space);
+    let signer_seeds = &[seeds];
+
+    // Someone may have already sent lamports to the PDA, in which case it can't be created directly
+    if allowance.lamports() == 0 {
+        let cpi_ctx = CpiContext::new(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: payer.to_account_info(),
+                to: allowance.clone(),
+            },
+        )
+        .with_signer(signer_seeds);
+
+        return create_account(cpi_ctx, rent_exempt_lamports, space as u64, &crate::ID);
+    }
+
+    let top_up_lamports = rent_exempt_lamports.saturating_sub(allowance.lamports());
+
+    if top_up_lamports > 0 {
+        let cpi_ctx = CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: payer.to_account_info(),
+                to: allowance.clone(),
+            },
+        );
+
+        transfer(cpi_ctx, top_up_lamports)?;
+    }
+
+    let allocate_ctx = CpiContext::new(
+        system_program.to_account_info(),
+        Allocate {
+            account_to_allocate: allowance.clone(),
+        },
+    )
+    .with_signer(signer_seeds);
+
+    allocate(allocate_ctx, space as u64)?;
+
+    let assign_ctx = CpiContext::new(
+        system_program.to_account_info(),
+        Assign {
+            account_to_assign: allowance.clone(),
+        },
+    )
+    .with_signer(signer_seeds);
+
+    assign(assign_ctx, &crate::ID)
+}
@@ -0,0 +1,342 @@
+use crate::{
+    constants::{PDA_SEED_ALLOWANCE, PDA_SEED_SALE},
+    error::WhitelistError,
+    events::TokensPurchased,
+    state::{Allowance, ReferrerStats, SaleMode, WhitelistSale},
+};
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_safe_math::SafeMath;
+use anchor_spl::{
+    associated_token::{AssociatedToken, ID as ASSOCIATED_TOKEN_PROGRAM_ID},
+    token_interface::{
+        mint_to, transfer_checked, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+    },
+};
+use std::mem::size_of;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct BuyTokens<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    /// Created on the fly for buyers purchasing in phases that don't require the whitelist
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = size_of::<Allowance>() + 8,
+        seeds=[PDA_SEED_ALLOWANCE.as_ref(), sale_name.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    /// Only used by vault sales
+    #[account(
+        mut,
+        associated_token::mint=token_mint,
+        associated_token::authority=sale,
+        associated_token::token_program=token_program
+    )]
+    pub vault_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint=token_mint,
+        associated_token::authority=signer,
+        associated_token::token_program=token_program,
+    )]
+    pub signer_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mutable for mint sales, which mint the tokens bought
+    #[account(mut, address=sale.token_mint @ WhitelistError::MintMismatch)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account()]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=signer,
+        associated_token::token_program=payment_token_program,
+    )]
+    pub signer_payment_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=sale,
+        associated_token::token_program=payment_token_program,
+    )]
+    pub proceeds_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Address is checked against the sale's fee recipient, only receives payments.
+    /// Only used by sales created with a platform fee.
+    #[account(mut, address=sale.fee_recipient @ WhitelistError::FeeRecipientMismatch)]
+    pub fee_recipient: Option<UncheckedAccount<'info>>,
+
+    /// Only used by sales created with a platform fee, when paid in the payment mint
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=fee_recipient,
+        associated_token::token_program=payment_token_program,
+    )]
+    pub fee_payment_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Only used by buyers who registered with a referrer, accruing the referrer's reward
+    #[account(mut)]
+    pub referrer_stats: Option<Account<'info, ReferrerStats>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Token program of the payment mint, which can differ from the sold mint's
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+
+    #[account(address=ASSOCIATED_TOKEN_PROGRAM_ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_buy_tokens(ctx: Context<BuyTokens>, sale_name: String, amount: u64) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(sale.is_sale_active(now), WhitelistError::SaleClosed);
+    require!(!sale.is_commit_mode, WhitelistError::CommitModeMismatch);
+
+    let allowance = &mut ctx.accounts.allowance;
+    allowance.buyer = ctx.accounts.signer.key();
+    allowance.tokens_bought = allowance.tokens_bought.safe_add(amount)?;
+
+    // Ensure buyer hasn't gone over their allowance, either sale-wide or in the active phase, and price the purchase
+    let transfer_to_vault_amount = if sale.num_phases == 0 {
+        require!(allowance.is_approved, WhitelistError::NotWhitelisted);
+        require!(
+            allowance.tokens_bought <= sale.max_tokens_for(allowance),
+            WhitelistError::AllowanceExceeded
+        );
+
+        sale.purchase_cost(amount, now)?
+    } else {
+        sale.buy_in_phase(allowance, now, amount)?
+            .safe_mul(amount)?
+    };
+
+    allowance.lamports_paid = allowance.lamports_paid.safe_add(transfer_to_vault_amount)?;
+    sale.record_purchase(amount, transfer_to_vault_amount)?;
+
+    // The platform's share of the payment goes straight to the fee recipient, unless the purchase can still be
+    // refunded, in which case it stays with the sale until the soft cap is finalized
+    let fee = sale.fee_for(transfer_to_vault_amount)?;
+    let fee_paid = if sale.defers_fees() {
+        sale.fees_owed = sale.fees_owed.safe_add(fee)?;
+        0
+    } else {
+        sale.fees_paid = sale.fees_paid.safe_add(fee)?;
+        fee
+    };
+
+    // The referrer's reward stays with the sale until they claim it
+    let referral_reward = match allowance.referrer {
+        Some(referrer) => {
+            let Some(referrer_stats) = &mut ctx.accounts.referrer_stats else {
+                return err!(WhitelistError::ReferrerMismatch);
+            };
+            require!(
+                referrer_stats.sale.eq(&sale.key()) && referrer_stats.referrer.eq(&referrer),
+                WhitelistError::ReferrerMismatch
+            );
+
+            let referral_reward = sale.referral_reward_for(transfer_to_vault_amount)?;
+            referrer_stats.rewards_earned =
+                referrer_stats.rewards_earned.safe_add(referral_reward)?;
+            sale.referral_rewards_owed = sale.referral_rewards_owed.safe_add(referral_reward)?;
+
+            referral_reward
+        }
+        None => 0,
+    };
+
+    // First transfer payment to vault, either as SOL or in the sale's payment mint
+    match sale.payment_mint {
+        None => {
+            let transfer_to_vault_context = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.signer.to_account_info(),
+                    to: sale.to_account_info(),
+                },
+            );
+
+            transfer(
+                transfer_to_vault_context,
+                transfer_to_vault_amount.safe_sub(fee_paid)?,
+            )?;
+
+            if fee_paid > 0 {
+                let Some(fee_recipient) = &ctx.accounts.fee_recipient else {
+                    return err!(WhitelistError::MissingFeeAccounts);
+                };
+
+                let transfer_fee_context = CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.signer.to_account_info(),
+                        to: fee_recipient.to_account_info(),
+                    },
+                );
+
+                transfer(transfer_fee_context, fee_paid)?;
+            }
+        }
+        Some(sale_payment_mint) => {
+            let (
+                Some(payment_mint),
+                Some(signer_payment_ata),
+                Some(proceeds_ata),
+                Some(payment_token_program),
+            ) = (
+                &ctx.accounts.payment_mint,
+                &ctx.accounts.signer_payment_ata,
+                &ctx.accounts.proceeds_ata,
+                &ctx.accounts.payment_token_program,
+            )
+            else {
+                return err!(WhitelistError::MissingPaymentAccounts);
+            };
+
+            require_keys_eq!(
+                payment_mint.key(),
+                sale_payment_mint,
+                WhitelistError::MintMismatch
+            );
+
+            let transfer_to_vault_context = CpiContext::new(
+                payment_token_program.to_account_info(),
+                TransferChecked {
+                    from: signer_payment_ata.to_account_info(),
+                    to: proceeds_ata.to_account_info(),
+                    authority: ctx.accounts.signer.to_account_info(),
+                    mint: payment_mint.to_account_info(),
+                },
+            );
+
+            transfer_checked(
+                transfer_to_vault_context,
+                transfer_to_vault_amount.safe_sub(fee_paid)?,
+                payment_mint.decimals,
+            )?;
+
+            if fee_paid > 0 {
+                let Some(fee_payment_ata) = &ctx.accounts.fee_payment_ata else {
+                    return err!(WhitelistError::MissingFeeAccounts);
+                };
+
+                let transfer_fee_context = CpiContext::new(
+                    payment_token_program.to_account_info(),
+                    TransferChecked {
+                        from: signer_payment_ata.to_account_info(),
+                        to: fee_payment_ata.to_account_info(),
+                        authority: ctx.accounts.signer.to_account_info(),
+                        mint: payment_mint.to_account_info(),
+                    },
+                );
+
+                transfer_checked(transfer_fee_context, fee_paid, payment_mint.decimals)?;
+            }
+        }
+    }
+
+    let amount_with_decimals: u64 =
+        amount.safe_mul(10_u64.safe_pow(ctx.accounts.token_mint.decimals.into())?)?;
+
+    emit_cpi!(TokensPurchased {
+        sale: ctx.accounts.sale.key(),
+        buyer: ctx.accounts.signer.key(),
+        amount,
+        cost: transfer_to_vault_amount,
+        price: transfer_to_vault_amount
+            .checked_div(amount)
+            .unwrap_or_default(),
+        fee,
+        referral_reward,
+        is_held: ctx.accounts.sale.holds_tokens(),
+        buyer_tokens_bought: ctx.accounts.allowance.tokens_bought,
+        tokens_sold: ctx.accounts.sale.tokens_sold,
+        lamports_raised: ctx.accounts.sale.lamports_raised,
+        timestamp: now,
+    });
+
+    // Vesting purchases, and those made before a soft cap is reached, are held back and released through `claim_vested`
+    if ctx.accounts.sale.holds_tokens() {
+        let allowance = &mut ctx.accounts.allowance;
+        allowance.tokens_held = allowance.tokens_held.safe_add(amount)?;
+
+        let sale = &mut ctx.accounts.sale;
+        sale.tokens_owed = sale.tokens_owed.safe_add(amount_with_decimals)?;
+
+        return Ok(());
+    }
+
+    let seeds = [
+        PDA_SEED_SALE.as_ref(),
+        sale_name.as_bytes(),
+        &[ctx.bumps.sale],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Then transfer or mint tokens to signer's ATA
+    match ctx.accounts.sale.sale_mode {
+        SaleMode::Vault => {
+            let Some(vault_ata) = &ctx.accounts.vault_ata else {
+                return err!(WhitelistError::MissingVaultAccounts);
+            };
+
+            let transfer_to_buyer = TransferChecked {
+                from: vault_ata.to_account_info(),
+                to: ctx.accounts.signer_ata.to_account_info(),
+                authority: ctx.accounts.sale.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_to_buyer,
+            )
+            .with_signer(signer_seeds);
+
+            transfer_checked(
+                cpi_ctx,
+                amount_with_decimals,
+                ctx.accounts.token_mint.decimals,
+            )
+        }
+        SaleMode::Mint => {
+            let mint_to_data = MintTo {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.signer_ata.to_account_info(),
+                authority: ctx.accounts.sale.to_account_info(),
+            };
+
+            let cpi_ctx =
+                CpiContext::new(ctx.accounts.token_program.to_account_info(), mint_to_data)
+                    .with_signer(signer_seeds);
+
+            mint_to(cpi_ctx, amount_with_decimals)
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{
+    constants::{PDA_SEED_ALLOWANCE, PDA_SEED_SALE},
+    error::WhitelistError,
+    events::RebateClaimed,
+    pricing::PricingMode,
+    state::{Allowance, WhitelistSale},
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct ClaimRebate<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(
+        mut,
+        seeds=[PDA_SEED_ALLOWANCE.as_ref(), sale_name.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account()]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=signer,
+        associated_token::token_program=payment_token_program,
+    )]
+    pub signer_payment_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=sale,
+        associated_token::token_program=payment_token_program,
+    )]
+    pub proceeds_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program of the payment mint
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_claim_rebate(ctx: Context<ClaimRebate>, sale_name: String) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let allowance = &mut ctx.accounts.allowance;
+
+    let PricingMode::DutchAuction(auction) = sale.pricing_mode else {
+        return err!(WhitelistError::RebateUnavailable);
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // The clearing price is only final once no more tokens can be bought at a lower price
+    require!(
+        auction.uniform_clearing
+            && (auction.has_ended(now) || sale.tokens_sold >= sale.total_tokens_for_sale),
+        WhitelistError::RebateUnavailable
+    );
+
+    // Buyers of a sale that misses its soft cap get a full refund instead
+    if sale.soft_cap > 0 {
+        require!(sale.is_finalized, WhitelistError::RebateUnavailable);
+    }
+
+    let cost_at_clearing_price = allowance.tokens_bought.safe_mul(sale.clearing_price)?;
+    let rebate_amount = allowance
+        .lamports_paid
+        .saturating_sub(cost_at_clearing_price);
+    require!(rebate_amount > 0, WhitelistError::NothingToClaim);
+
+    allowance.lamports_paid = cost_at_clearing_price;
+    sale.lamports_raised = sale.lamports_raised.safe_sub(rebate_amount)?;
+
+    // Platform fees were paid out at purchase or when the soft cap was finalized, and referrers keep their reward, so neither share of the
+    // rebated payment is returned. Buyers end up paying the clearing price plus those shares of the rebate
+    let referral_reward = match allowance.referrer {
+        Some(_) => sale.referral_reward_for(rebate_amount)?,
+        None => 0,
+    };
+    let rebate_amount = rebate_amount
+        .safe_sub(sale.fee_for(rebate_amount)?)?
+        .safe_sub(referral_reward)?;
+
+    match sale.payment_mint {
+        None => {
+            // SOL proceeds sit on the program-owned sale account, so they can be moved directly
+            sale.sub_lamports(rebate_amount)?;
+            ctx.accounts.signer.add_lamports(rebate_amount)?;
+        }
+        Some(sale_payment_mint) => {
+            let (
+                Some(payment_mint),
+                Some(signer_payment_ata),
+                Some(proceeds_ata),
+                Some(payment_token_program),
+            ) = (
+                &ctx.accounts.payment_mint,
+                &ctx.accounts.signer_payment_ata,
+                &ctx.accounts.proceeds_ata,
+                &ctx.accounts.payment_token_program,
+            )
+            else {
+                return err!(WhitelistError::MissingPaymentAccounts);
+            };
+
+            require_keys_eq!(
+                payment_mint.key(),
+                sale_payment_mint,
+                WhitelistError::MintMismatch
+            );
+
+            let seeds = [
+                PDA_SEED_SALE.as_ref(),
+                sale_name.as_bytes(),
+                &[ctx.bumps.sale],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let rebate_context = CpiContext::new(
+                payment_token_program.to_account_info(),
+                TransferChecked {
+                    from: proceeds_ata.to_account_info(),
+                    to: signer_payment_ata.to_account_info(),
+                    authority: sale.to_account_info(),
+                    mint: payment_mint.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds);
+
+            transfer_checked(rebate_context, rebate_amount, payment_mint.decimals)?;
+        }
+    }
+
+    emit_cpi!(RebateClaimed {
+        sale: sale.key(),
+        buyer: allowance.buyer,
+        amount: rebate_amount,
+        clearing_price: sale.clearing_price,
+        timestamp: now,
+    });
+
+    Ok(())
+}
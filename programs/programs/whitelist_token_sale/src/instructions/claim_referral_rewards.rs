@@ -0,0 +1,139 @@
+use crate::{
+    constants::{PDA_SEED_REFERRER, PDA_SEED_SALE},
+    error::WhitelistError,
+    events::ReferralRewardsClaimed,
+    state::{ReferrerStats, WhitelistSale},
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(
+        mut,
+        seeds=[PDA_SEED_REFERRER.as_ref(), sale_name.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub referrer_stats: Account<'info, ReferrerStats>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account()]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=signer,
+        associated_token::token_program=payment_token_program,
+    )]
+    pub signer_payment_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=sale,
+        associated_token::token_program=payment_token_program,
+    )]
+    pub proceeds_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program of the payment mint
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Pays the signer the referral rewards they've accrued in the sale and not claimed yet
+pub fn handle_claim_referral_rewards(
+    ctx: Context<ClaimReferralRewards>,
+    sale_name: String,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let referrer_stats = &mut ctx.accounts.referrer_stats;
+
+    require!(
+        sale.are_referral_rewards_claimable(),
+        WhitelistError::ReferralRewardsUnavailable
+    );
+
+    let reward_amount = referrer_stats
+        .rewards_earned
+        .safe_sub(referrer_stats.rewards_claimed)?;
+    require!(reward_amount > 0, WhitelistError::NothingToClaim);
+
+    referrer_stats.rewards_claimed = referrer_stats.rewards_earned;
+    sale.referral_rewards_owed = sale.referral_rewards_owed.safe_sub(reward_amount)?;
+
+    match sale.payment_mint {
+        None => {
+            // SOL proceeds sit on the program-owned sale account, so they can be moved directly
+            sale.sub_lamports(reward_amount)?;
+            ctx.accounts.signer.add_lamports(reward_amount)?;
+        }
+        Some(sale_payment_mint) => {
+            let (
+                Some(payment_mint),
+                Some(signer_payment_ata),
+                Some(proceeds_ata),
+                Some(payment_token_program),
+            ) = (
+                &ctx.accounts.payment_mint,
+                &ctx.accounts.signer_payment_ata,
+                &ctx.accounts.proceeds_ata,
+                &ctx.accounts.payment_token_program,
+            )
+            else {
+                return err!(WhitelistError::MissingPaymentAccounts);
+            };
+
+            require_keys_eq!(
+                payment_mint.key(),
+                sale_payment_mint,
+                WhitelistError::MintMismatch
+            );
+
+            let seeds = [
+                PDA_SEED_SALE.as_ref(),
+                sale_name.as_bytes(),
+                &[ctx.bumps.sale],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let reward_context = CpiContext::new(
+                payment_token_program.to_account_info(),
+                TransferChecked {
+                    from: proceeds_ata.to_account_info(),
+                    to: signer_payment_ata.to_account_info(),
+                    authority: sale.to_account_info(),
+                    mint: payment_mint.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds);
+
+            transfer_checked(reward_context, reward_amount, payment_mint.decimals)?;
+        }
+    }
+
+    emit_cpi!(ReferralRewardsClaimed {
+        sale: sale.key(),
+        referrer: ctx.accounts.signer.key(),
+        amount: reward_amount,
+        rewards_claimed: referrer_stats.rewards_claimed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
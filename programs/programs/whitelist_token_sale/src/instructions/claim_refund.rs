@@ -0,0 +1,144 @@
+use crate::{
+    constants::{PDA_SEED_ALLOWANCE, PDA_SEED_SALE},
+    error::WhitelistError,
+    events::RefundClaimed,
+    state::{Allowance, WhitelistSale},
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct ClaimRefund<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(
+        mut,
+        seeds=[PDA_SEED_ALLOWANCE.as_ref(), sale_name.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(address=sale.token_mint @ WhitelistError::MintMismatch)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account()]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=signer,
+        associated_token::token_program=payment_token_program,
+    )]
+    pub signer_payment_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=sale,
+        associated_token::token_program=payment_token_program,
+    )]
+    pub proceeds_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program of the payment mint
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_claim_refund(ctx: Context<ClaimRefund>, sale_name: String) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let allowance = &mut ctx.accounts.allowance;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(sale.is_refundable(now), WhitelistError::RefundUnavailable);
+
+    require!(allowance.lamports_paid > 0, WhitelistError::NothingToClaim);
+
+    // Platform fees are only paid once the soft cap is finalized, so the whole payment is refunded
+    let refund_amount = allowance.lamports_paid;
+
+    // The refunded purchases are no longer owed to the buyer
+    let tokens_held: u64 = allowance
+        .tokens_held
+        .safe_mul(10_u64.safe_pow(ctx.accounts.token_mint.decimals.into())?)?
+        .safe_sub(allowance.tokens_claimed)?;
+    sale.tokens_owed = sale.tokens_owed.safe_sub(tokens_held)?;
+
+    allowance.lamports_paid = 0;
+    allowance.tokens_held = 0;
+    allowance.tokens_claimed = 0;
+
+    match sale.payment_mint {
+        None => {
+            // SOL proceeds sit on the program-owned sale account, so they can be moved directly
+            sale.sub_lamports(refund_amount)?;
+            ctx.accounts.signer.add_lamports(refund_amount)?;
+        }
+        Some(sale_payment_mint) => {
+            let (
+                Some(payment_mint),
+                Some(signer_payment_ata),
+                Some(proceeds_ata),
+                Some(payment_token_program),
+            ) = (
+                &ctx.accounts.payment_mint,
+                &ctx.accounts.signer_payment_ata,
+                &ctx.accounts.proceeds_ata,
+                &ctx.accounts.payment_token_program,
+            )
+            else {
+                return err!(WhitelistError::MissingPaymentAccounts);
+            };
+
+            require_keys_eq!(
+                payment_mint.key(),
+                sale_payment_mint,
+                WhitelistError::MintMismatch
+            );
+
+            let seeds = [
+                PDA_SEED_SALE.as_ref(),
+                sale_name.as_bytes(),
+                &[ctx.bumps.sale],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let refund_context = CpiContext::new(
+                payment_token_program.to_account_info(),
+                TransferChecked {
+                    from: proceeds_ata.to_account_info(),
+                    to: signer_payment_ata.to_account_info(),
+                    authority: sale.to_account_info(),
+                    mint: payment_mint.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds);
+
+            transfer_checked(refund_context, refund_amount, payment_mint.decimals)?;
+        }
+    }
+
+    emit_cpi!(RefundClaimed {
+        sale: sale.key(),
+        buyer: allowance.buyer,
+        amount: refund_amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use crate::{
+    constants::{PDA_SEED_ALLOWANCE, PDA_SEED_SALE},
+    error::WhitelistError,
+    events::TokensClaimed,
+    state::{Allowance, SaleMode, WhitelistSale},
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::{
+    associated_token::{AssociatedToken, ID as ASSOCIATED_TOKEN_PROGRAM_ID},
+    token_interface::{
+        mint_to, transfer_checked, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct ClaimVested<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(
+        mut,
+        seeds=[PDA_SEED_ALLOWANCE.as_ref(), sale_name.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    /// Only used by vault sales
+    #[account(
+        mut,
+        associated_token::mint=token_mint,
+        associated_token::authority=sale,
+        associated_token::token_program=token_program
+    )]
+    pub vault_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint=token_mint,
+        associated_token::authority=signer,
+        associated_token::token_program=token_program,
+    )]
+    pub signer_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mutable for mint sales, which only mint held back tokens once they're claimed
+    #[account(mut, address=sale.token_mint @ WhitelistError::MintMismatch)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    #[account(address=ASSOCIATED_TOKEN_PROGRAM_ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_claim_vested(ctx: Context<ClaimVested>, sale_name: String) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let allowance = &mut ctx.accounts.allowance;
+
+    if sale.soft_cap > 0 {
+        require!(sale.is_finalized, WhitelistError::SaleNotFinalized);
+    }
+
+    let total_held: u64 = allowance
+        .tokens_held
+        .safe_mul(10_u64.safe_pow(ctx.accounts.token_mint.decimals.into())?)?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // Without a vesting schedule, held back tokens are released all at once
+    let unlocked = match sale.vesting {
+        Some(vesting) => vesting.unlocked_amount(total_held, now)?,
+        None => total_held,
+    };
+
+    let claim_amount = unlocked.safe_sub(allowance.tokens_claimed)?;
+    require!(claim_amount > 0, WhitelistError::NothingToClaim);
+
+    allowance.tokens_claimed = unlocked;
+    sale.tokens_owed = sale.tokens_owed.safe_sub(claim_amount)?;
+    sale.tokens_claimed = sale.tokens_claimed.safe_add(claim_amount)?;
+
+    let seeds = [
+        PDA_SEED_SALE.as_ref(),
+        sale_name.as_bytes(),
+        &[ctx.bumps.sale],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    match sale.sale_mode {
+        SaleMode::Vault => {
+            let Some(vault_ata) = &ctx.accounts.vault_ata else {
+                return err!(WhitelistError::MissingVaultAccounts);
+            };
+
+            let transfer_to_buyer = TransferChecked {
+                from: vault_ata.to_account_info(),
+                to: ctx.accounts.signer_ata.to_account_info(),
+                authority: sale.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_to_buyer,
+            )
+            .with_signer(signer_seeds);
+
+            transfer_checked(cpi_ctx, claim_amount, ctx.accounts.token_mint.decimals)?;
+        }
+        SaleMode::Mint => {
+            let mint_to_data = MintTo {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.signer_ata.to_account_info(),
+                authority: sale.to_account_info(),
+            };
+
+            let cpi_ctx =
+                CpiContext::new(ctx.accounts.token_program.to_account_info(), mint_to_data)
+                    .with_signer(signer_seeds);
+
+            mint_to(cpi_ctx, claim_amount)?;
+        }
+    }
+
+    emit_cpi!(TokensClaimed {
+        sale: sale.key(),
+        buyer: allowance.buyer,
+        amount: claim_amount,
+        buyer_tokens_claimed: allowance.tokens_claimed,
+        tokens_owed: sale.tokens_owed,
+        timestamp: now,
+    });
+
+    Ok(())
+}
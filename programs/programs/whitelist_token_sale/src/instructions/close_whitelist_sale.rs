@@ -0,0 +1,217 @@
+use crate::{
+    constants::PDA_SEED_SALE,
+    error::WhitelistError,
+    events::SaleClosed,
+    state::{SaleMode, WhitelistSale},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{AssociatedToken, ID as ASSOCIATED_TOKEN_PROGRAM_ID},
+    token_interface::{
+        close_account, set_authority, transfer_checked, CloseAccount, Mint, SetAuthority,
+        TokenAccount, TokenInterface, TransferChecked,
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct CloseWhitelistSale<'info> {
+    #[account(
+        mut,
+        close=signer,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    /// Only used by vault sales
+    #[account(
+        mut,
+        associated_token::mint=token_mint,
+        associated_token::authority=sale,
+        associated_token::token_program=token_program
+    )]
+    pub vault_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Token program of the payment mint, which can differ from the sold mint's
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+
+    #[account(address=ASSOCIATED_TOKEN_PROGRAM_ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// Mutable for mint sales, which hand its mint authority back to the owner
+    #[account(mut, address=sale.token_mint @ WhitelistError::MintMismatch)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Only used by vault sales, receiving the tokens left in the vault
+    #[account(
+        mut,
+        associated_token::mint=token_mint,
+        associated_token::authority=signer,
+        associated_token::token_program=token_program
+    )]
+    pub signer_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account()]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=sale,
+        associated_token::token_program=payment_token_program
+    )]
+    pub proceeds_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer=signer,
+        associated_token::mint=payment_mint,
+        associated_token::authority=signer,
+        associated_token::token_program=payment_token_program
+    )]
+    pub signer_payment_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_close_whitelist_sale(
+    ctx: Context<CloseWhitelistSale>,
+    _sale_name: String,
+) -> Result<()> {
+    require!(
+        ctx.accounts.signer.key().eq(&ctx.accounts.sale.owner.key()),
+        WhitelistError::OnlyOwner
+    );
+
+    require!(
+        ctx.accounts.sale.tokens_owed == 0 && ctx.accounts.sale.committed_unsettled == 0,
+        WhitelistError::ClaimsOutstanding
+    );
+
+    // Referral rewards and rebates are void once buyers are refunded
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        (ctx.accounts.sale.referral_rewards_owed == 0
+            && ctx.accounts.sale.unclaimed_rebates(now)? == 0)
+            || ctx.accounts.sale.is_refundable(now),
+        WhitelistError::ClaimsOutstanding
+    );
+
+    let sale = &ctx.accounts.sale;
+    emit_cpi!(SaleClosed {
+        sale: sale.key(),
+        owner: sale.owner,
+        num_buyers: sale.num_buyers,
+        tokens_sold: sale.tokens_sold,
+        lamports_raised: sale.lamports_raised,
+        timestamp: now,
+    });
+
+    let seeds = [
+        PDA_SEED_SALE.as_ref(),
+        _sale_name.as_bytes(),
+        &[ctx.bumps.sale],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    match ctx.accounts.sale.sale_mode {
+        SaleMode::Vault => {
+            let (Some(vault_ata), Some(signer_ata)) =
+                (&ctx.accounts.vault_ata, &ctx.accounts.signer_ata)
+            else {
+                return err!(WhitelistError::MissingVaultAccounts);
+            };
+
+            let transfer_to_owner = TransferChecked {
+                from: vault_ata.to_account_info(),
+                to: signer_ata.to_account_info(),
+                authority: ctx.accounts.sale.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_to_owner,
+            )
+            .with_signer(signer_seeds);
+
+            transfer_checked(cpi_ctx, vault_ata.amount, ctx.accounts.token_mint.decimals)?;
+        }
+        SaleMode::Mint => {
+            // Set sale owner's account as mint authority
+            let set_mint_authority = SetAuthority {
+                account_or_mint: ctx.accounts.token_mint.to_account_info(),
+                current_authority: ctx.accounts.sale.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                set_mint_authority,
+            )
+            .with_signer(signer_seeds);
+
+            set_authority(
+                cpi_ctx,
+                anchor_spl::token_interface::spl_token_2022::instruction::AuthorityType::MintTokens,
+                Some(ctx.accounts.signer.key()),
+            )?;
+        }
+    }
+
+    // SOL proceeds are swept along with the sale account's rent, token proceeds need to be moved
+    let Some(sale_payment_mint) = ctx.accounts.sale.payment_mint else {
+        return Ok(());
+    };
+
+    let (
+        Some(payment_mint),
+        Some(proceeds_ata),
+        Some(signer_payment_ata),
+        Some(payment_token_program),
+    ) = (
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.proceeds_ata,
+        &ctx.accounts.signer_payment_ata,
+        &ctx.accounts.payment_token_program,
+    )
+    else {
+        return err!(WhitelistError::MissingPaymentAccounts);
+    };
+
+    require_keys_eq!(
+        payment_mint.key(),
+        sale_payment_mint,
+        WhitelistError::MintMismatch
+    );
+
+    let transfer_proceeds = TransferChecked {
+        from: proceeds_ata.to_account_info(),
+        to: signer_payment_ata.to_account_info(),
+        authority: ctx.accounts.sale.to_account_info(),
+        mint: payment_mint.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(payment_token_program.to_account_info(), transfer_proceeds)
+        .with_signer(signer_seeds);
+
+    transfer_checked(cpi_ctx, proceeds_ata.amount, payment_mint.decimals)?;
+
+    // The proceeds account would be left without an authority once the sale account is closed
+    let close_proceeds = CloseAccount {
+        account: proceeds_ata.to_account_info(),
+        destination: ctx.accounts.signer.to_account_info(),
+        authority: ctx.accounts.sale.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(payment_token_program.to_account_info(), close_proceeds)
+        .with_signer(signer_seeds);
+
+    close_account(cpi_ctx)
+}
@@ -0,0 +1,149 @@
+use crate::{
+    constants::{PDA_SEED_ALLOWANCE, PDA_SEED_SALE},
+    error::WhitelistError,
+    events::PaymentCommitted,
+    state::{Allowance, WhitelistSale},
+};
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct Commit<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(
+        mut,
+        seeds=[PDA_SEED_ALLOWANCE.as_ref(), sale_name.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account()]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=signer,
+        associated_token::token_program=payment_token_program,
+    )]
+    pub signer_payment_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=sale,
+        associated_token::token_program=payment_token_program,
+    )]
+    pub proceeds_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program of the payment mint
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_commit(ctx: Context<Commit>, _sale_name: String, lamports: u64) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let allowance = &mut ctx.accounts.allowance;
+
+    require!(sale.is_commit_mode, WhitelistError::CommitModeMismatch);
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // Finalizing the sale freezes the total that allocations are computed from
+    require!(
+        !sale.is_finalized && sale.is_sale_active(now),
+        WhitelistError::SaleClosed
+    );
+
+    require!(allowance.is_approved, WhitelistError::NotWhitelisted);
+
+    // Buyers can't commit more than their allowance would cost
+    allowance.lamports_committed = allowance.lamports_committed.safe_add(lamports)?;
+    require!(
+        allowance.lamports_committed
+            <= sale
+                .max_tokens_for(allowance)
+                .safe_mul(sale.lamports_per_token)?,
+        WhitelistError::AllowanceExceeded
+    );
+
+    sale.total_committed = sale.total_committed.safe_add(lamports)?;
+    sale.committed_unsettled = sale.committed_unsettled.safe_add(lamports)?;
+
+    match sale.payment_mint {
+        None => {
+            let transfer_to_vault_context = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.signer.to_account_info(),
+                    to: sale.to_account_info(),
+                },
+            );
+
+            transfer(transfer_to_vault_context, lamports)?;
+        }
+        Some(sale_payment_mint) => {
+            let (
+                Some(payment_mint),
+                Some(signer_payment_ata),
+                Some(proceeds_ata),
+                Some(payment_token_program),
+            ) = (
+                &ctx.accounts.payment_mint,
+                &ctx.accounts.signer_payment_ata,
+                &ctx.accounts.proceeds_ata,
+                &ctx.accounts.payment_token_program,
+            )
+            else {
+                return err!(WhitelistError::MissingPaymentAccounts);
+            };
+
+            require_keys_eq!(
+                payment_mint.key(),
+                sale_payment_mint,
+                WhitelistError::MintMismatch
+            );
+
+            let transfer_to_vault_context = CpiContext::new(
+                payment_token_program.to_account_info(),
+                TransferChecked {
+                    from: signer_payment_ata.to_account_info(),
+                    to: proceeds_ata.to_account_info(),
+                    authority: ctx.accounts.signer.to_account_info(),
+                    mint: payment_mint.to_account_info(),
+                },
+            );
+
+            transfer_checked(transfer_to_vault_context, lamports, payment_mint.decimals)?;
+        }
+    }
+
+    emit_cpi!(PaymentCommitted {
+        sale: sale.key(),
+        buyer: allowance.buyer,
+        amount: lamports,
+        buyer_committed: allowance.lamports_committed,
+        total_committed: sale.total_committed,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,233 @@
+use crate::{
+    constants::{BPS_DENOMINATOR, MAX_SALE_PHASES, PDA_SEED_CONFIG, PDA_SEED_SALE},
+    error::WhitelistError,
+    events::SaleCreated,
+    lottery::RandomnessSource,
+    pricing::PricingMode,
+    state::{ProgramConfig, SaleMode, SalePhase, WhitelistMode, WhitelistSale},
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::{
+    associated_token::{AssociatedToken, ID as ASSOCIATED_TOKEN_PROGRAM_ID},
+    token_interface::{
+        set_authority, transfer_checked, Mint, SetAuthority, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+use std::mem::size_of;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct CreateWhitelistSale<'info> {
+    #[account(
+        init,
+        payer=signer,
+        space=size_of::<WhitelistSale>() + 8,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: Fixed by its seeds, so that fees can't be avoided, and only read if the config has been created
+    #[account(seeds=[PDA_SEED_CONFIG.as_ref()], bump)]
+    pub config: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Token program of the payment mint, which can differ from the sold mint's
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+
+    #[account(address=ASSOCIATED_TOKEN_PROGRAM_ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// Mutable for mint sales, which take over its mint authority
+    #[account(mut)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Only used by vault sales, holding the tokens for sale
+    #[account(
+        init,
+        payer=signer,
+        associated_token::mint=token_mint,
+        associated_token::authority=sale,
+        associated_token::token_program=token_program
+    )]
+    pub vault_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Only used by vault sales, funding the vault
+    #[account(
+        mut,
+        associated_token::mint=token_mint,
+        associated_token::authority=signer,
+        associated_token::token_program=token_program
+    )]
+    pub signer_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Set to have buyers pay in this mint instead of SOL
+    #[account()]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer=signer,
+        associated_token::mint=payment_mint,
+        associated_token::authority=sale,
+        associated_token::token_program=payment_token_program
+    )]
+    pub proceeds_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_whitelist_sale(
+    ctx: Context<CreateWhitelistSale>,
+    _sale_name: String,
+    lamports_per_token: u64,
+    max_tokens_per_buyer: u64,
+    max_buyers: u64,
+    sale_mode: SaleMode,
+    referral_bps: u16,
+) -> Result<()> {
+    let sale_total_amount: u64 = max_buyers.safe_mul(max_tokens_per_buyer)?;
+
+    // Set sale account fields
+    let sale = &mut ctx.accounts.sale;
+
+    sale.token_mint = ctx.accounts.token_mint.key();
+    sale.owner = ctx.accounts.signer.key.key();
+    sale.pending_owner = None;
+    sale.operator = None;
+    sale.lamports_per_token = lamports_per_token;
+    sale.max_tokens_per_buyer = max_tokens_per_buyer;
+    sale.max_buyers = max_buyers;
+    sale.is_registration_open = true;
+    sale.is_sale_open = false;
+    sale.num_buyers = 0;
+    sale.registration_start = None;
+    sale.registration_end = None;
+    sale.sale_start = None;
+    sale.sale_end = None;
+    sale.phases = [SalePhase::default(); MAX_SALE_PHASES];
+    sale.num_phases = 0;
+    sale.vesting = None;
+    sale.tokens_owed = 0;
+    sale.soft_cap = 0;
+    sale.deadline = 0;
+    sale.is_finalized = false;
+    sale.lamports_raised = 0;
+    sale.total_tokens_for_sale = sale_total_amount;
+    sale.tokens_sold = 0;
+    sale.hard_cap_lamports = None;
+    sale.pricing_mode = PricingMode::Fixed;
+    sale.clearing_price = 0;
+    sale.is_commit_mode = false;
+    sale.total_committed = 0;
+    sale.committed_unsettled = 0;
+    sale.num_tickets = 0;
+    sale.randomness_source = RandomnessSource::SlotHashes;
+    sale.is_lottery_drawn = false;
+    sale.lottery_seed = [0; 32];
+    sale.sale_mode = sale_mode;
+    sale.treasury = sale.owner;
+    sale.lamports_withdrawn = 0;
+    sale.fees_paid = 0;
+
+    // Later config changes don't apply to the sale, and no fee is taken until the config is created
+    let config = &ctx.accounts.config;
+    if config.owner.eq(&crate::ID) {
+        let config = ProgramConfig::try_deserialize(&mut &config.try_borrow_data()?[..])?;
+        sale.fee_bps = config.fee_bps;
+        sale.fee_recipient = config.fee_recipient;
+    } else {
+        sale.fee_bps = 0;
+        sale.fee_recipient = Pubkey::default();
+    }
+
+    require!(
+        sale.fee_bps.safe_add(referral_bps)? <= BPS_DENOMINATOR,
+        WhitelistError::InvalidReferralFee
+    );
+    sale.referral_bps = referral_bps;
+    sale.referral_rewards_owed = 0;
+
+    sale.whitelist_mode = WhitelistMode::Open;
+    sale.merkle_root = [0; 32];
+    sale.whitelist_signer = Pubkey::default();
+    sale.payment_mint = ctx.accounts.payment_mint.as_ref().map(|mint| mint.key());
+
+    require!(
+        sale.payment_mint.is_none() || ctx.accounts.proceeds_ata.is_some(),
+        WhitelistError::MissingPaymentAccounts
+    );
+
+    emit_cpi!(SaleCreated {
+        sale: sale.key(),
+        owner: sale.owner,
+        sale_mode,
+        token_mint: sale.token_mint,
+        payment_mint: sale.payment_mint,
+        lamports_per_token,
+        max_tokens_per_buyer,
+        max_buyers,
+        total_tokens_for_sale: sale.total_tokens_for_sale,
+        fee_bps: sale.fee_bps,
+        referral_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    match sale_mode {
+        SaleMode::Vault => {
+            let (Some(vault_ata), Some(signer_ata)) =
+                (&ctx.accounts.vault_ata, &ctx.accounts.signer_ata)
+            else {
+                return err!(WhitelistError::MissingVaultAccounts);
+            };
+
+            // Transfer funds into vault
+            let transfer_from_buyer = TransferChecked {
+                from: signer_ata.to_account_info(),
+                to: vault_ata.to_account_info(),
+                authority: ctx.accounts.signer.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_from_buyer,
+            );
+
+            // We treat token amounts as integers, therefore we have to multiply by 10^mint.decimals
+            let amount_with_decimals: u64 = sale_total_amount
+                .safe_mul(10_u64.safe_pow(ctx.accounts.token_mint.decimals.into())?)?;
+
+            transfer_checked(
+                cpi_ctx,
+                amount_with_decimals,
+                ctx.accounts.token_mint.decimals,
+            )
+        }
+        SaleMode::Mint => {
+            // Set sale PDA as mint authority
+            let set_mint_authority = SetAuthority {
+                account_or_mint: ctx.accounts.token_mint.to_account_info(),
+                current_authority: ctx.accounts.signer.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                set_mint_authority,
+            );
+
+            set_authority(
+                cpi_ctx,
+                anchor_spl::token_interface::spl_token_2022::instruction::AuthorityType::MintTokens,
+                Some(ctx.accounts.sale.key()),
+            )
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{
+    constants::PDA_SEED_SALE,
+    error::WhitelistError,
+    state::{WhitelistMode, WhitelistSale},
+};
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct DrawLottery<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: Checked against the sale's randomness source when read
+    pub randomness_account: UncheckedAccount<'info>,
+}
+
+pub fn handle_draw_lottery(ctx: Context<DrawLottery>, _sale_name: String) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let signer = &ctx.accounts.signer;
+
+    require!(sale.is_operator(signer.key), WhitelistError::OnlyOperator);
+
+    require!(
+        sale.whitelist_mode == WhitelistMode::Lottery,
+        WhitelistError::WhitelistModeMismatch
+    );
+
+    require!(!sale.is_lottery_drawn, WhitelistError::LotteryAlreadyDrawn);
+
+    // No more tickets can be registered once the draw is known
+    require!(
+        !sale.is_registration_active(Clock::get()?.unix_timestamp),
+        WhitelistError::WhitelistRegistrationClosed
+    );
+
+    let randomness = sale
+        .randomness_source
+        .randomness()
+        .read(&ctx.accounts.randomness_account)?;
+
+    // Sales drawing from the same randomness still get different results
+    sale.lottery_seed = hashv(&[&randomness, sale.key().as_ref()]).to_bytes();
+    sale.is_lottery_drawn = true;
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use crate::{
+    constants::PDA_SEED_SALE, error::WhitelistError, events::SaleFinalized, state::WhitelistSale,
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct FinalizeSale<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account()]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=sale,
+        associated_token::token_program=payment_token_program,
+    )]
+    pub proceeds_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Address is checked against the sale's fee recipient, only receives payments.
+    /// Only used by sales created with a platform fee.
+    #[account(mut, address=sale.fee_recipient @ WhitelistError::FeeRecipientMismatch)]
+    pub fee_recipient: Option<UncheckedAccount<'info>>,
+
+    /// Only used by sales created with a platform fee, when paid in the payment mint
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=fee_recipient,
+        associated_token::token_program=payment_token_program,
+    )]
+    pub fee_payment_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program of the payment mint
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn handle_finalize_sale(ctx: Context<FinalizeSale>, sale_name: String) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let signer = &ctx.accounts.signer;
+    let now = Clock::get()?.unix_timestamp;
+
+    // The owner can finalize as soon as the soft cap is reached. Past the deadline, or once commitments
+    // are closed, anyone can, so buyers' tokens and the proceeds don't depend on the owner showing up
+    require!(
+        sale.owner.eq(signer.key)
+            || (sale.soft_cap > 0 && now >= sale.deadline)
+            || sale.has_commit_window_ended(now),
+        WhitelistError::OnlyOwner
+    );
+
+    require!(
+        sale.lamports_raised >= sale.soft_cap,
+        WhitelistError::SoftCapNotReached
+    );
+
+    sale.is_finalized = true;
+
+    // Purchases can no longer be refunded, so the fees held back from them go to the fee recipient
+    let fees_owed = sale.fees_owed;
+    sale.fees_owed = 0;
+    sale.fees_paid = sale.fees_paid.safe_add(fees_owed)?;
+
+    if fees_owed > 0 {
+        match sale.payment_mint {
+            None => {
+                let Some(fee_recipient) = &ctx.accounts.fee_recipient else {
+                    return err!(WhitelistError::MissingFeeAccounts);
+                };
+
+                // SOL proceeds sit on the program-owned sale account, so they can be moved directly
+                sale.sub_lamports(fees_owed)?;
+                fee_recipient.add_lamports(fees_owed)?;
+            }
+            Some(sale_payment_mint) => {
+                let (
+                    Some(payment_mint),
+                    Some(proceeds_ata),
+                    Some(fee_payment_ata),
+                    Some(payment_token_program),
+                ) = (
+                    &ctx.accounts.payment_mint,
+                    &ctx.accounts.proceeds_ata,
+                    &ctx.accounts.fee_payment_ata,
+                    &ctx.accounts.payment_token_program,
+                )
+                else {
+                    return err!(WhitelistError::MissingFeeAccounts);
+                };
+
+                require_keys_eq!(
+                    payment_mint.key(),
+                    sale_payment_mint,
+                    WhitelistError::MintMismatch
+                );
+
+                let seeds = [
+                    PDA_SEED_SALE.as_ref(),
+                    sale_name.as_bytes(),
+                    &[ctx.bumps.sale],
+                ];
+                let signer_seeds = &[&seeds[..]];
+
+                let fee_context = CpiContext::new(
+                    payment_token_program.to_account_info(),
+                    TransferChecked {
+                        from: proceeds_ata.to_account_info(),
+                        to: fee_payment_ata.to_account_info(),
+                        authority: sale.to_account_info(),
+                        mint: payment_mint.to_account_info(),
+                    },
+                )
+                .with_signer(signer_seeds);
+
+                transfer_checked(fee_context, fees_owed, payment_mint.decimals)?;
+            }
+        }
+    }
+
+    emit_cpi!(SaleFinalized {
+        sale: sale.key(),
+        actor: signer.key(),
+        tokens_sold: sale.tokens_sold,
+        lamports_raised: sale.lamports_raised,
+        total_committed: sale.total_committed,
+        fee: fees_owed,
+        timestamp: now,
+    });
+
+    Ok(())
+}
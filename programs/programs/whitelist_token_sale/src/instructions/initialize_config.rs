@@ -0,0 +1,51 @@
+use crate::{
+    constants::{BPS_DENOMINATOR, PDA_SEED_CONFIG},
+    error::WhitelistError,
+    program::WhitelistTokenSale,
+    state::ProgramConfig,
+};
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer=signer,
+        space=size_of::<ProgramConfig>() + 8,
+        seeds=[PDA_SEED_CONFIG.as_ref()],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(constraint=program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, WhitelistTokenSale>,
+
+    #[account(
+        constraint=program_data.upgrade_authority_address == Some(signer.key())
+            @ WhitelistError::OnlyUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the program config, which only the upgrade authority can do so that it can't be front-run
+pub fn handle_initialize_config(
+    ctx: Context<InitializeConfig>,
+    admin: Pubkey,
+    fee_recipient: Pubkey,
+    fee_bps: u16,
+) -> Result<()> {
+    require!(fee_bps <= BPS_DENOMINATOR, WhitelistError::InvalidFee);
+
+    let config = &mut ctx.accounts.config;
+    config.admin = admin;
+    config.fee_recipient = fee_recipient;
+    config.fee_bps = fee_bps;
+
+    Ok(())
+}
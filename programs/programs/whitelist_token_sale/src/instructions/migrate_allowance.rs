@@ -0,0 +1,107 @@
+use crate::{
+    constants::{MAX_SALE_PHASES, PDA_SEED_ALLOWANCE, PDA_SEED_SALE},
+    error::WhitelistError,
+    state::{Allowance, WhitelistSale},
+};
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+use anchor_safe_math::SafeMath;
+use std::mem::size_of;
+
+#[derive(Accounts)]
+#[instruction(sale_name: String, buyer: Pubkey)]
+pub struct MigrateAllowance<'info> {
+    /// Has to be migrated first
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    /// CHECK: Allowances of the vault program only hold `tokens_bought`, so it's read by hand before being extended
+    #[account(
+        mut,
+        owner=crate::ID,
+        seeds=[PDA_SEED_ALLOWANCE.as_ref(), sale_name.as_bytes(), buyer.as_ref()],
+        bump
+    )]
+    pub allowance: UncheckedAccount<'info>,
+
+    /// Pays for the extra rent, whether it's the buyer or anyone else
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Extends an allowance created by the former vault program to the current layout.
+/// Buyers registered with the vault program were approved, and paid the sale's fixed price for their tokens,
+/// which are added to the sale's totals as they weren't tracked then.
+pub fn handle_migrate_allowance(
+    ctx: Context<MigrateAllowance>,
+    _sale_name: String,
+    buyer: Pubkey,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let allowance = &ctx.accounts.allowance;
+    let space = size_of::<Allowance>() + 8;
+
+    require!(
+        allowance.data_len() < space,
+        WhitelistError::AllowanceAlreadyMigrated
+    );
+
+    // Discriminator followed by `tokens_bought`
+    let tokens_bought = {
+        let data = allowance.try_borrow_data()?;
+        require!(
+            data.len() >= 16 && data[..8] == Allowance::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+
+        u64::from_le_bytes(data[8..16].try_into().unwrap())
+    };
+
+    let rent = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(allowance.lamports());
+
+    if rent > 0 {
+        let transfer_rent_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.signer.to_account_info(),
+                to: allowance.to_account_info(),
+            },
+        );
+
+        transfer(transfer_rent_context, rent)?;
+    }
+
+    allowance.realloc(space, true)?;
+
+    let lamports_paid = tokens_bought.safe_mul(sale.lamports_per_token)?;
+    let migrated = Allowance {
+        buyer,
+        tokens_bought,
+        is_approved: true,
+        allocation: None,
+        phase_tokens_bought: [0; MAX_SALE_PHASES],
+        tokens_held: 0,
+        tokens_claimed: 0,
+        lamports_paid,
+        lamports_committed: 0,
+        ticket_number: 0,
+        referrer: None,
+    };
+    migrated.try_serialize(&mut &mut allowance.try_borrow_mut_data()?[..])?;
+
+    sale.tokens_sold = sale.tokens_sold.safe_add(tokens_bought)?;
+    sale.lamports_raised = sale.lamports_raised.safe_add(lamports_paid)?;
+
+    Ok(())
+}
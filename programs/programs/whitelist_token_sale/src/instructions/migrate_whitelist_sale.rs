@@ -0,0 +1,70 @@
+use crate::{constants::PDA_SEED_SALE, error::WhitelistError, state::WhitelistSale};
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_safe_math::SafeMath;
+use std::mem::size_of;
+
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct MigrateWhitelistSale<'info> {
+    /// CHECK: Sales created before `sale_mode` was added can be too short to deserialize, so it's deserialized once extended
+    #[account(
+        mut,
+        owner=crate::ID,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Extends a sale created by the former vault program to the current account size.
+/// The program took over the vault program's address, and the added bytes are zeroed, so they read as `SaleMode::Vault`.
+/// The vault program's sales were always sized for every buyer buying their maximum, which becomes their supply.
+pub fn handle_migrate_whitelist_sale(
+    ctx: Context<MigrateWhitelistSale>,
+    _sale_name: String,
+) -> Result<()> {
+    let sale = &ctx.accounts.sale;
+    let space = size_of::<WhitelistSale>() + 8;
+
+    require!(sale.data_len() < space, WhitelistError::SaleAlreadyMigrated);
+
+    let rent = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(sale.lamports());
+
+    if rent > 0 {
+        let transfer_rent_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.signer.to_account_info(),
+                to: sale.to_account_info(),
+            },
+        );
+
+        transfer(transfer_rent_context, rent)?;
+    }
+
+    sale.realloc(space, true)?;
+
+    // Also checks the discriminator
+    let mut migrated = WhitelistSale::try_deserialize(&mut &sale.try_borrow_data()?[..])?;
+    require!(
+        migrated.owner.eq(ctx.accounts.signer.key),
+        WhitelistError::OnlyOwner
+    );
+
+    migrated.total_tokens_for_sale = migrated
+        .max_buyers
+        .safe_mul(migrated.max_tokens_per_buyer)?;
+    migrated.try_serialize(&mut &mut sale.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::{
+    constants::PDA_SEED_SALE, error::WhitelistError, events::OwnershipProposed,
+    state::WhitelistSale,
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct ProposeOwner<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/// Proposes a new owner, who only takes over once it calls `accept_ownership`.
+/// A new proposal replaces the previous one, and `None` withdraws it.
+pub fn handle_propose_owner(
+    ctx: Context<ProposeOwner>,
+    _sale_name: String,
+    pending_owner: Option<Pubkey>,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let signer = &ctx.accounts.signer;
+
+    require!(sale.owner.eq(signer.key), WhitelistError::OnlyOwner);
+
+    sale.pending_owner = pending_owner;
+
+    emit_cpi!(OwnershipProposed {
+        sale: sale.key(),
+        owner: sale.owner,
+        pending_owner,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
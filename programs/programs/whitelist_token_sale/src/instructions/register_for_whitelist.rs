@@ -0,0 +1,174 @@
+use crate::{
+    constants::{PDA_SEED_ALLOWANCE, PDA_SEED_REFERRER, PDA_SEED_SALE},
+    error::WhitelistError,
+    events::BuyerRegistered,
+    state::{Allowance, ReferrerStats, WhitelistMode, WhitelistSale},
+    voucher::Voucher,
+};
+use anchor_lang::{prelude::*, solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID};
+use anchor_safe_math::SafeMath;
+use std::mem::size_of;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct RegisterForWhitelist<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init,
+        payer=signer,
+        space=size_of::<Allowance>() + 8,
+        seeds=[PDA_SEED_ALLOWANCE.as_ref(), sale_name.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    /// CHECK: Address is checked, only read when the sale's whitelist uses vouchers
+    #[account(address=INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// CHECK: Only recorded as the buyer's referrer
+    pub referrer: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Fixed by its seeds, only checked to be owned by the program, as referrers have to be registered
+    #[account(
+        seeds=[PDA_SEED_ALLOWANCE.as_ref(), sale_name.as_bytes(), referrer.key_or_default().as_ref()],
+        bump
+    )]
+    pub referrer_allowance: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        init_if_needed,
+        payer=signer,
+        space=size_of::<ReferrerStats>() + 8,
+        seeds=[PDA_SEED_REFERRER.as_ref(), sale_name.as_bytes(), referrer.key_or_default().as_ref()],
+        bump
+    )]
+    pub referrer_stats: Option<Account<'info, ReferrerStats>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Optional accounts can't be referenced directly in seeds. Calling a method on the field
+/// itself, rather than passing it to a function, keeps the seeds resolvable in the IDL.
+trait KeyOrDefault {
+    fn key_or_default(&self) -> Pubkey;
+}
+
+impl KeyOrDefault for Option<UncheckedAccount<'_>> {
+    fn key_or_default(&self) -> Pubkey {
+        self.as_ref()
+            .map(|account| account.key())
+            .unwrap_or_default()
+    }
+}
+
+pub fn handle_register_for_whitelist(
+    ctx: Context<RegisterForWhitelist>,
+    _sale_name: String,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+
+    require!(
+        matches!(
+            sale.whitelist_mode,
+            WhitelistMode::Open | WhitelistMode::Voucher | WhitelistMode::Lottery
+        ),
+        WhitelistError::SelfRegistrationDisabled
+    );
+
+    require!(
+        sale.is_registration_active(Clock::get()?.unix_timestamp),
+        WhitelistError::WhitelistRegistrationClosed
+    );
+
+    let allowance = &mut ctx.accounts.allowance;
+
+    allowance.buyer = ctx.accounts.signer.key();
+    allowance.tokens_bought = 0;
+    allowance.referrer = None;
+
+    if let Some(referrer) = &ctx.accounts.referrer {
+        let (Some(referrer_allowance), Some(referrer_stats)) = (
+            &ctx.accounts.referrer_allowance,
+            &mut ctx.accounts.referrer_stats,
+        ) else {
+            return err!(WhitelistError::ReferrerMismatch);
+        };
+
+        require_keys_neq!(
+            referrer.key(),
+            allowance.buyer,
+            WhitelistError::SelfReferral
+        );
+
+        // Referrers have to register before the buyers they refer. Allowances are never closed, so
+        // every referrer registered before the buyers they referred, and referrals can't form a cycle
+        require!(
+            referrer_allowance.owner.eq(&crate::ID),
+            WhitelistError::ReferrerNotRegistered
+        );
+
+        referrer_stats.sale = sale.key();
+        referrer_stats.referrer = referrer.key();
+        referrer_stats.num_referrals = referrer_stats.num_referrals.safe_add(1)?;
+
+        allowance.referrer = Some(referrer.key());
+    }
+
+    // Lottery tickets only get approved if they win the draw
+    if sale.whitelist_mode == WhitelistMode::Lottery {
+        require!(!sale.is_lottery_drawn, WhitelistError::LotteryAlreadyDrawn);
+
+        allowance.ticket_number = sale.num_tickets;
+        sale.num_tickets = sale.num_tickets.safe_add(1)?;
+    } else {
+        if sale.whitelist_mode == WhitelistMode::Voucher {
+            require_keys_neq!(
+                sale.whitelist_signer,
+                Pubkey::default(),
+                WhitelistError::InvalidVoucher
+            );
+
+            let voucher =
+                Voucher::load_verified(&ctx.accounts.instructions_sysvar, &sale.whitelist_signer)?;
+
+            require_keys_eq!(voucher.sale, sale.key(), WhitelistError::InvalidVoucher);
+            require_keys_eq!(
+                voucher.buyer,
+                allowance.buyer,
+                WhitelistError::InvalidVoucher
+            );
+            require!(
+                Clock::get()?.unix_timestamp <= voucher.expires_at,
+                WhitelistError::VoucherExpired
+            );
+
+            allowance.allocation = Some(voucher.allocation);
+        }
+
+        sale.approve_buyer(allowance)?;
+    }
+
+    emit_cpi!(BuyerRegistered {
+        sale: sale.key(),
+        buyer: allowance.buyer,
+        is_approved: allowance.is_approved,
+        allocation: allowance.allocation,
+        referrer: allowance.referrer,
+        num_buyers: sale.num_buyers,
+        num_tickets: sale.num_tickets,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use crate::{
+    constants::{PDA_SEED_ALLOWANCE, PDA_SEED_SALE},
+    error::WhitelistError,
+    events::BuyerRegistered,
+    merkle,
+    state::{Allowance, WhitelistMode, WhitelistSale},
+};
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct RegisterWithMerkleProof<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init,
+        payer=signer,
+        space=size_of::<Allowance>() + 8,
+        seeds=[PDA_SEED_ALLOWANCE.as_ref(), sale_name.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the signer's allowance once they prove they're part of the sale's Merkle whitelist.
+/// Meant to be sent along with the buyer's first `buy_tokens`, so the owner never pays for allowances.
+pub fn handle_register_with_merkle_proof(
+    ctx: Context<RegisterWithMerkleProof>,
+    _sale_name: String,
+    allocation: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+
+    require!(
+        sale.whitelist_mode == WhitelistMode::Merkle,
+        WhitelistError::WhitelistModeMismatch
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        sale.is_registration_active(now) || sale.is_sale_active(now),
+        WhitelistError::WhitelistRegistrationClosed
+    );
+
+    let leaf = merkle::leaf(ctx.accounts.signer.key, allocation);
+
+    require!(
+        merkle::verify(&proof, &sale.merkle_root, leaf),
+        WhitelistError::InvalidMerkleProof
+    );
+
+    let allowance = &mut ctx.accounts.allowance;
+
+    allowance.buyer = ctx.accounts.signer.key();
+    allowance.tokens_bought = 0;
+    allowance.allocation = (allocation > 0).then_some(allocation);
+
+    sale.approve_buyer(allowance)?;
+
+    emit_cpi!(BuyerRegistered {
+        sale: sale.key(),
+        buyer: allowance.buyer,
+        is_approved: allowance.is_approved,
+        allocation: allowance.allocation,
+        referrer: None,
+        num_buyers: sale.num_buyers,
+        num_tickets: sale.num_tickets,
+        timestamp: now,
+    });
+
+    Ok(())
+}
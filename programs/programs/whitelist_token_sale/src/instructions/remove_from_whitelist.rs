@@ -0,0 +1,98 @@
+use crate::{
+    constants::{PDA_SEED_ALLOWANCE, PDA_SEED_SALE},
+    error::WhitelistError,
+    events::BuyerRemoved,
+    state::{Allowance, WhitelistMode, WhitelistSale},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct RemoveFromWhitelist<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds=[PDA_SEED_ALLOWANCE.as_ref(), sale_name.as_bytes(), allowance.buyer.as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+}
+
+/// Revokes the allowance, along with any additional allowances passed through the remaining accounts.
+/// Allowances are kept around so that tokens already bought still count if the buyer is re-added.
+pub fn handle_remove_from_whitelist<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RemoveFromWhitelist<'info>>,
+    sale_name: String,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+
+    require!(
+        sale.is_operator(ctx.accounts.signer.key),
+        WhitelistError::OnlyOperator
+    );
+
+    require!(
+        sale.whitelist_mode == WhitelistMode::Curated,
+        WhitelistError::WhitelistModeMismatch
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+
+    sale.revoke_buyer(&mut ctx.accounts.allowance)?;
+
+    emit_cpi!(BuyerRemoved {
+        sale: sale.key(),
+        buyer: ctx.accounts.allowance.buyer,
+        num_buyers: sale.num_buyers,
+        timestamp: now,
+    });
+
+    for allowance_info in ctx.remaining_accounts.iter() {
+        let mut allowance = Account::<Allowance>::try_from(allowance_info)?;
+
+        let (allowance_address, _bump) = Pubkey::find_program_address(
+            &[
+                PDA_SEED_ALLOWANCE.as_ref(),
+                sale_name.as_bytes(),
+                allowance.buyer.as_ref(),
+            ],
+            ctx.program_id,
+        );
+
+        require_keys_eq!(
+            allowance_info.key(),
+            allowance_address,
+            WhitelistError::InvalidRemainingAccounts
+        );
+
+        // The named allowance is only written back once the instruction finishes
+        require_keys_neq!(
+            allowance_info.key(),
+            ctx.accounts.allowance.key(),
+            WhitelistError::InvalidRemainingAccounts
+        );
+
+        sale.revoke_buyer(&mut allowance)?;
+
+        emit_cpi!(BuyerRemoved {
+            sale: sale.key(),
+            buyer: allowance.buyer,
+            num_buyers: sale.num_buyers,
+            timestamp: now,
+        });
+
+        allowance.exit(ctx.program_id)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use crate::{
+    constants::PDA_SEED_SALE, error::WhitelistError, pricing::PricingMode, state::WhitelistSale,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct SetCommitMode<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+pub fn handle_set_commit_mode(
+    ctx: Context<SetCommitMode>,
+    _sale_name: String,
+    is_commit_mode: bool,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let signer = &ctx.accounts.signer;
+
+    require!(sale.owner.eq(signer.key), WhitelistError::OnlyOwner);
+
+    require!(
+        sale.tokens_sold == 0 && sale.total_committed == 0,
+        WhitelistError::CommitModeLocked
+    );
+
+    // Commitments are allocated at the sale's fixed price
+    if is_commit_mode {
+        require!(
+            sale.pricing_mode == PricingMode::Fixed && sale.num_phases == 0 && sale.soft_cap == 0,
+            WhitelistError::CommitModeConflict
+        );
+    }
+
+    sale.is_commit_mode = is_commit_mode;
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::{
+    constants::PDA_SEED_SALE, error::WhitelistError, events::OperatorUpdated, state::WhitelistSale,
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct SetOperator<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/// Sets the key that can run the sale day to day, or removes it when `None`
+pub fn handle_set_operator(
+    ctx: Context<SetOperator>,
+    _sale_name: String,
+    operator: Option<Pubkey>,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let signer = &ctx.accounts.signer;
+
+    require!(sale.owner.eq(signer.key), WhitelistError::OnlyOwner);
+
+    sale.operator = operator;
+
+    emit_cpi!(OperatorUpdated {
+        sale: sale.key(),
+        owner: sale.owner,
+        operator,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
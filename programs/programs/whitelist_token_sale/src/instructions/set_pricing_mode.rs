@@ -0,0 +1,44 @@
+use crate::{
+    constants::PDA_SEED_SALE, error::WhitelistError, pricing::PricingMode, state::WhitelistSale,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct SetPricingMode<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+pub fn handle_set_pricing_mode(
+    ctx: Context<SetPricingMode>,
+    _sale_name: String,
+    pricing_mode: PricingMode,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let signer = &ctx.accounts.signer;
+
+    require!(sale.owner.eq(signer.key), WhitelistError::OnlyOwner);
+
+    require!(sale.tokens_sold == 0, WhitelistError::PricingModeLocked);
+
+    require!(!sale.is_commit_mode, WhitelistError::CommitModeConflict);
+
+    // Phases have their own fixed prices
+    require!(
+        pricing_mode.is_valid() && (pricing_mode == PricingMode::Fixed || sale.num_phases == 0),
+        WhitelistError::InvalidPricingMode
+    );
+
+    sale.pricing_mode = pricing_mode;
+    sale.clearing_price = 0;
+
+    Ok(())
+}
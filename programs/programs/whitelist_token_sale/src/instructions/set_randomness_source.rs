@@ -0,0 +1,42 @@
+use crate::{
+    constants::PDA_SEED_SALE, error::WhitelistError, lottery::RandomnessSource,
+    state::WhitelistSale,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct SetRandomnessSource<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+pub fn handle_set_randomness_source(
+    ctx: Context<SetRandomnessSource>,
+    _sale_name: String,
+    randomness_source: RandomnessSource,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let signer = &ctx.accounts.signer;
+
+    require!(sale.owner.eq(signer.key), WhitelistError::OnlyOwner);
+
+    require!(!sale.is_lottery_drawn, WhitelistError::LotteryAlreadyDrawn);
+
+    // Ticket holders registered knowing where the draw's randomness would come from
+    require!(
+        sale.num_tickets == 0,
+        WhitelistError::RandomnessSourceLocked
+    );
+
+    sale.randomness_source = randomness_source;
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use crate::{
+    constants::PDA_SEED_SALE,
+    error::WhitelistError,
+    pricing::PricingMode,
+    state::{SaleMode, WhitelistSale},
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct SetSaleCaps<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+pub fn handle_set_sale_caps(
+    ctx: Context<SetSaleCaps>,
+    _sale_name: String,
+    total_tokens_for_sale: u64,
+    hard_cap_lamports: Option<u64>,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let signer = &ctx.accounts.signer;
+
+    require!(sale.owner.eq(signer.key), WhitelistError::OnlyOwner);
+
+    // Allocations are computed from the supply once commitments are settled
+    require!(
+        !(sale.is_commit_mode && sale.is_finalized),
+        WhitelistError::CommitModeLocked
+    );
+
+    require!(
+        sale.tokens_sold <= total_tokens_for_sale,
+        WhitelistError::InvalidSaleCaps
+    );
+
+    // Once a uniform-price auction sells out, its clearing price is final and only the rebates down to it
+    // stay reserved. More supply would let the price fall again, owing rebates that may have been withdrawn
+    if let PricingMode::DutchAuction(auction) = sale.pricing_mode {
+        require!(
+            !auction.uniform_clearing
+                || sale.tokens_sold < sale.total_tokens_for_sale
+                || total_tokens_for_sale == sale.total_tokens_for_sale,
+            WhitelistError::InvalidSaleCaps
+        );
+    }
+
+    // The vault only holds the tokens deposited at creation
+    if sale.sale_mode == SaleMode::Vault {
+        let tokens_deposited = sale.max_buyers.safe_mul(sale.max_tokens_per_buyer)?;
+
+        require!(
+            total_tokens_for_sale <= tokens_deposited,
+            WhitelistError::InvalidSaleCaps
+        );
+    }
+
+    if let Some(hard_cap_lamports) = hard_cap_lamports {
+        require!(
+            sale.lamports_raised <= hard_cap_lamports,
+            WhitelistError::InvalidSaleCaps
+        );
+    }
+
+    sale.total_tokens_for_sale = total_tokens_for_sale;
+    sale.hard_cap_lamports = hard_cap_lamports;
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use crate::{
+    constants::PDA_SEED_SALE,
+    error::WhitelistError,
+    pricing::PricingMode,
+    state::{SalePhase, WhitelistSale},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct SetSalePhase<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/// Edits the phase at `index`, or appends a new phase when `index` is the current number of phases
+#[allow(clippy::too_many_arguments)]
+pub fn handle_set_sale_phase(
+    ctx: Context<SetSalePhase>,
+    _sale_name: String,
+    index: u8,
+    start: i64,
+    end: i64,
+    lamports_per_token: u64,
+    max_tokens_per_buyer: u64,
+    max_tokens: Option<u64>,
+    requires_whitelist: bool,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let signer = &ctx.accounts.signer;
+
+    require!(sale.owner.eq(signer.key), WhitelistError::OnlyOwner);
+
+    // Phases have their own fixed prices
+    require!(
+        sale.pricing_mode == PricingMode::Fixed,
+        WhitelistError::InvalidPricingMode
+    );
+
+    require!(!sale.is_commit_mode, WhitelistError::CommitModeConflict);
+
+    let index = usize::from(index);
+    let is_new_phase = index == usize::from(sale.num_phases);
+    require!(
+        index < sale.phases.len() && index <= usize::from(sale.num_phases),
+        WhitelistError::InvalidPhase
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(start < end, WhitelistError::InvalidTimeWindow);
+    require!(now < start, WhitelistError::PhaseAlreadyStarted);
+    if !is_new_phase {
+        require!(
+            now < sale.phases[index].start,
+            WhitelistError::PhaseAlreadyStarted
+        );
+    }
+
+    let phase = SalePhase {
+        start,
+        end,
+        lamports_per_token,
+        max_tokens_per_buyer,
+        max_tokens,
+        tokens_sold: 0,
+        requires_whitelist,
+    };
+
+    // Phases can't overlap so that at most one of them is active at any time
+    require!(
+        sale.active_phases()
+            .iter()
+            .enumerate()
+            .all(|(other_index, other)| other_index == index || !phase.overlaps(other)),
+        WhitelistError::PhaseOverlap
+    );
+
+    sale.phases[index] = phase;
+    if is_new_phase {
+        sale.num_phases += 1;
+    }
+
+    Ok(())
+}
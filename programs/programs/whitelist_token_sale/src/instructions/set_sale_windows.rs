@@ -0,0 +1,46 @@
+use crate::{constants::PDA_SEED_SALE, error::WhitelistError, state::WhitelistSale};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct SetSaleWindows<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+pub fn handle_set_sale_windows(
+    ctx: Context<SetSaleWindows>,
+    _sale_name: String,
+    registration_start: Option<i64>,
+    registration_end: Option<i64>,
+    sale_start: Option<i64>,
+    sale_end: Option<i64>,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let signer = &ctx.accounts.signer;
+
+    require!(sale.owner.eq(signer.key), WhitelistError::OnlyOwner);
+
+    for (start, end) in [
+        (registration_start, registration_end),
+        (sale_start, sale_end),
+    ] {
+        if let (Some(start), Some(end)) = (start, end) {
+            require!(start < end, WhitelistError::InvalidTimeWindow);
+        }
+    }
+
+    sale.registration_start = registration_start;
+    sale.registration_end = registration_end;
+    sale.sale_start = sale_start;
+    sale.sale_end = sale_end;
+
+    Ok(())
+}
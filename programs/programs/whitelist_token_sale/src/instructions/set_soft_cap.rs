@@ -0,0 +1,45 @@
+use crate::{constants::PDA_SEED_SALE, error::WhitelistError, state::WhitelistSale};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct SetSoftCap<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+pub fn handle_set_soft_cap(
+    ctx: Context<SetSoftCap>,
+    _sale_name: String,
+    soft_cap: u64,
+    deadline: i64,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let signer = &ctx.accounts.signer;
+
+    require!(sale.owner.eq(signer.key), WhitelistError::OnlyOwner);
+
+    // Buyers who already paid did so under the previous terms
+    require!(sale.lamports_raised == 0, WhitelistError::SoftCapLocked);
+
+    require!(!sale.is_commit_mode, WhitelistError::CommitModeConflict);
+
+    if soft_cap > 0 {
+        require!(
+            Clock::get()?.unix_timestamp < deadline,
+            WhitelistError::InvalidTimeWindow
+        );
+    }
+
+    sale.soft_cap = soft_cap;
+    sale.deadline = deadline;
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::{
+    constants::{BPS_DENOMINATOR, PDA_SEED_SALE},
+    error::WhitelistError,
+    state::{VestingSchedule, WhitelistSale},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct SetVestingSchedule<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+pub fn handle_set_vesting_schedule(
+    ctx: Context<SetVestingSchedule>,
+    _sale_name: String,
+    vesting: Option<VestingSchedule>,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let signer = &ctx.accounts.signer;
+
+    require!(sale.owner.eq(signer.key), WhitelistError::OnlyOwner);
+
+    // Buyers' claims are computed from the current schedule, so it has to stay put once any are owed or made.
+    // Another schedule could unlock less than buyers already claimed
+    require!(
+        sale.tokens_owed == 0 && sale.tokens_claimed == 0,
+        WhitelistError::VestingLocked
+    );
+
+    if let Some(vesting) = vesting {
+        require!(
+            vesting.tge_unlock_bps <= BPS_DENOMINATOR
+                && vesting.cliff_seconds >= 0
+                && vesting.duration_seconds >= 0,
+            WhitelistError::InvalidVestingSchedule
+        );
+    }
+
+    sale.vesting = vesting;
+
+    Ok(())
+}
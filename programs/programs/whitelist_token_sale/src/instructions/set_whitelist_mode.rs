@@ -0,0 +1,41 @@
+use crate::{
+    constants::PDA_SEED_SALE,
+    error::WhitelistError,
+    state::{WhitelistMode, WhitelistSale},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct SetWhitelistMode<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+pub fn handle_set_whitelist_mode(
+    ctx: Context<SetWhitelistMode>,
+    _sale_name: String,
+    whitelist_mode: WhitelistMode,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let signer = &ctx.accounts.signer;
+
+    require!(sale.owner.eq(signer.key), WhitelistError::OnlyOwner);
+
+    // Switching modes after buyers got in would leave them approved under different rules
+    require!(
+        sale.num_buyers == 0 && sale.num_tickets == 0,
+        WhitelistError::WhitelistModeLocked
+    );
+
+    sale.whitelist_mode = whitelist_mode;
+
+    Ok(())
+}
@@ -3,7 +3,7 @@ use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 #[instruction(sale_name: String)]
-pub struct UpdateSaleState<'info> {
+pub struct SetWhitelistSigner<'info> {
     #[account(
         mut,
         seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
@@ -15,19 +15,18 @@ pub struct UpdateSaleState<'info> {
     pub signer: Signer<'info>,
 }
 
-pub fn handle_update_sale_state(
-    ctx: Context<UpdateSaleState>,
+/// Vouchers signed by the previous whitelist signer stop being accepted right away
+pub fn handle_set_whitelist_signer(
+    ctx: Context<SetWhitelistSigner>,
     _sale_name: String,
-    is_registration_open: bool,
-    is_sale_open: bool,
+    whitelist_signer: Pubkey,
 ) -> Result<()> {
     let sale = &mut ctx.accounts.sale;
     let signer = &ctx.accounts.signer;
 
     require!(sale.owner.eq(signer.key), WhitelistError::OnlyOwner);
 
-    sale.is_registration_open = is_registration_open;
-    sale.is_sale_open = is_sale_open;
+    sale.whitelist_signer = whitelist_signer;
 
     Ok(())
 }
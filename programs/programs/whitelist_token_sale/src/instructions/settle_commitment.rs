@@ -0,0 +1,220 @@
+use crate::{
+    constants::{PDA_SEED_ALLOWANCE, PDA_SEED_SALE},
+    error::WhitelistError,
+    events::CommitmentSettled,
+    state::{Allowance, ReferrerStats, WhitelistSale},
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct SettleCommitment<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(
+        mut,
+        seeds=[PDA_SEED_ALLOWANCE.as_ref(), sale_name.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(address=sale.token_mint @ WhitelistError::MintMismatch)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account()]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=signer,
+        associated_token::token_program=payment_token_program,
+    )]
+    pub signer_payment_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=sale,
+        associated_token::token_program=payment_token_program,
+    )]
+    pub proceeds_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Address is checked against the sale's fee recipient, only receives payments.
+    /// Only used by sales created with a platform fee.
+    #[account(mut, address=sale.fee_recipient @ WhitelistError::FeeRecipientMismatch)]
+    pub fee_recipient: Option<UncheckedAccount<'info>>,
+
+    /// Only used by sales created with a platform fee, when paid in the payment mint
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=fee_recipient,
+        associated_token::token_program=payment_token_program,
+    )]
+    pub fee_payment_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Only used by buyers who registered with a referrer, accruing the referrer's reward
+    #[account(mut)]
+    pub referrer_stats: Option<Account<'info, ReferrerStats>>,
+
+    /// Token program of the payment mint
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_settle_commitment(ctx: Context<SettleCommitment>, sale_name: String) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let allowance = &mut ctx.accounts.allowance;
+
+    require!(sale.is_commit_mode, WhitelistError::CommitModeMismatch);
+    require!(sale.is_finalized, WhitelistError::SaleNotFinalized);
+    require!(
+        allowance.lamports_committed > 0,
+        WhitelistError::NothingToClaim
+    );
+
+    // Allocated tokens are held back for the buyer to claim through `claim_vested`
+    let (tokens, refund_amount) = sale.settle_commitment(allowance)?;
+
+    let tokens_owed: u64 =
+        tokens.safe_mul(10_u64.safe_pow(ctx.accounts.token_mint.decimals.into())?)?;
+    sale.tokens_owed = sale.tokens_owed.safe_add(tokens_owed)?;
+
+    // The platform's share of what the buyer ends up paying goes to the fee recipient, as with purchases
+    let cost = tokens.safe_mul(sale.lamports_per_token)?;
+    let fee = sale.fee_for(cost)?;
+    sale.fees_paid = sale.fees_paid.safe_add(fee)?;
+
+    // The referrer's reward stays with the sale until they claim it, as with purchases
+    let referral_reward = match allowance.referrer {
+        Some(referrer) => {
+            let Some(referrer_stats) = &mut ctx.accounts.referrer_stats else {
+                return err!(WhitelistError::ReferrerMismatch);
+            };
+            require!(
+                referrer_stats.sale.eq(&sale.key()) && referrer_stats.referrer.eq(&referrer),
+                WhitelistError::ReferrerMismatch
+            );
+
+            let referral_reward = sale.referral_reward_for(cost)?;
+            referrer_stats.rewards_earned =
+                referrer_stats.rewards_earned.safe_add(referral_reward)?;
+            sale.referral_rewards_owed = sale.referral_rewards_owed.safe_add(referral_reward)?;
+
+            referral_reward
+        }
+        None => 0,
+    };
+
+    emit_cpi!(CommitmentSettled {
+        sale: sale.key(),
+        buyer: allowance.buyer,
+        tokens,
+        cost,
+        refund: refund_amount,
+        fee,
+        referral_reward,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    if refund_amount == 0 && fee == 0 {
+        return Ok(());
+    }
+
+    match sale.payment_mint {
+        None => {
+            // SOL commitments sit on the program-owned sale account, so they can be moved directly
+            sale.sub_lamports(refund_amount)?;
+            ctx.accounts.signer.add_lamports(refund_amount)?;
+
+            if fee > 0 {
+                let Some(fee_recipient) = &ctx.accounts.fee_recipient else {
+                    return err!(WhitelistError::MissingFeeAccounts);
+                };
+
+                sale.sub_lamports(fee)?;
+                fee_recipient.add_lamports(fee)?;
+            }
+        }
+        Some(sale_payment_mint) => {
+            let (
+                Some(payment_mint),
+                Some(signer_payment_ata),
+                Some(proceeds_ata),
+                Some(payment_token_program),
+            ) = (
+                &ctx.accounts.payment_mint,
+                &ctx.accounts.signer_payment_ata,
+                &ctx.accounts.proceeds_ata,
+                &ctx.accounts.payment_token_program,
+            )
+            else {
+                return err!(WhitelistError::MissingPaymentAccounts);
+            };
+
+            require_keys_eq!(
+                payment_mint.key(),
+                sale_payment_mint,
+                WhitelistError::MintMismatch
+            );
+
+            let seeds = [
+                PDA_SEED_SALE.as_ref(),
+                sale_name.as_bytes(),
+                &[ctx.bumps.sale],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            if refund_amount > 0 {
+                let refund_context = CpiContext::new(
+                    payment_token_program.to_account_info(),
+                    TransferChecked {
+                        from: proceeds_ata.to_account_info(),
+                        to: signer_payment_ata.to_account_info(),
+                        authority: sale.to_account_info(),
+                        mint: payment_mint.to_account_info(),
+                    },
+                )
+                .with_signer(signer_seeds);
+
+                transfer_checked(refund_context, refund_amount, payment_mint.decimals)?;
+            }
+
+            if fee > 0 {
+                let Some(fee_payment_ata) = &ctx.accounts.fee_payment_ata else {
+                    return err!(WhitelistError::MissingFeeAccounts);
+                };
+
+                let fee_context = CpiContext::new(
+                    payment_token_program.to_account_info(),
+                    TransferChecked {
+                        from: proceeds_ata.to_account_info(),
+                        to: fee_payment_ata.to_account_info(),
+                        authority: sale.to_account_info(),
+                        mint: payment_mint.to_account_info(),
+                    },
+                )
+                .with_signer(signer_seeds);
+
+                transfer_checked(fee_context, fee, payment_mint.decimals)?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::{
+    constants::{PDA_SEED_ALLOWANCE, PDA_SEED_SALE},
+    error::WhitelistError,
+    events::LotteryTicketSettled,
+    lottery,
+    state::{Allowance, WhitelistMode, WhitelistSale},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct SettleLotteryTicket<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(
+        mut,
+        seeds=[PDA_SEED_ALLOWANCE.as_ref(), sale_name.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, Allowance>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/// Approves a winning ticket's buyer, or closes a losing ticket to give its rent back
+pub fn handle_settle_lottery_ticket(
+    ctx: Context<SettleLotteryTicket>,
+    _sale_name: String,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let allowance = &mut ctx.accounts.allowance;
+
+    require!(
+        sale.whitelist_mode == WhitelistMode::Lottery,
+        WhitelistError::WhitelistModeMismatch
+    );
+
+    require!(sale.is_lottery_drawn, WhitelistError::LotteryNotDrawn);
+
+    let is_winner = lottery::is_winner(
+        &sale.lottery_seed,
+        allowance.ticket_number,
+        sale.num_tickets,
+        sale.max_buyers,
+    );
+
+    if is_winner {
+        sale.approve_buyer(allowance)?;
+    } else {
+        allowance.close(ctx.accounts.signer.to_account_info())?;
+    }
+
+    emit_cpi!(LotteryTicketSettled {
+        sale: sale.key(),
+        buyer: allowance.buyer,
+        ticket_number: allowance.ticket_number,
+        is_winner,
+        num_buyers: sale.num_buyers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
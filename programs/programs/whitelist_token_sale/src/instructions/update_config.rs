@@ -0,0 +1,40 @@
+use crate::{
+    constants::{BPS_DENOMINATOR, PDA_SEED_CONFIG},
+    error::WhitelistError,
+    state::ProgramConfig,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_CONFIG.as_ref()],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Updates the program config. Sales snapshot the fee when they're created, so existing sales aren't affected.
+pub fn handle_update_config(
+    ctx: Context<UpdateConfig>,
+    admin: Pubkey,
+    fee_recipient: Pubkey,
+    fee_bps: u16,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    require!(
+        config.admin.eq(ctx.accounts.signer.key),
+        WhitelistError::OnlyAdmin
+    );
+    require!(fee_bps <= BPS_DENOMINATOR, WhitelistError::InvalidFee);
+
+    config.admin = admin;
+    config.fee_recipient = fee_recipient;
+    config.fee_bps = fee_bps;
+
+    Ok(())
+}
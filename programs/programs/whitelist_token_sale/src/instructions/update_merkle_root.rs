@@ -0,0 +1,46 @@
+use crate::{
+    constants::PDA_SEED_SALE,
+    error::WhitelistError,
+    state::{WhitelistMode, WhitelistSale},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct UpdateMerkleRoot<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/// Buyers who already registered keep their allowance, even if they aren't part of the new root
+pub fn handle_update_merkle_root(
+    ctx: Context<UpdateMerkleRoot>,
+    _sale_name: String,
+    merkle_root: [u8; 32],
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let signer = &ctx.accounts.signer;
+
+    require!(sale.owner.eq(signer.key), WhitelistError::OnlyOwner);
+
+    require!(
+        sale.whitelist_mode == WhitelistMode::Merkle,
+        WhitelistError::WhitelistModeMismatch
+    );
+
+    require!(
+        sale.is_registration_active(Clock::get()?.unix_timestamp),
+        WhitelistError::WhitelistRegistrationClosed
+    );
+
+    sale.merkle_root = merkle_root;
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use crate::{
+    constants::PDA_SEED_SALE, error::WhitelistError, events::SaleStateUpdated, state::WhitelistSale,
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct UpdateSaleState<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+pub fn handle_update_sale_state(
+    ctx: Context<UpdateSaleState>,
+    _sale_name: String,
+    is_registration_open: bool,
+    is_sale_open: bool,
+) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+    let signer = &ctx.accounts.signer;
+
+    require!(sale.is_operator(signer.key), WhitelistError::OnlyOperator);
+
+    sale.is_registration_open = is_registration_open;
+    sale.is_sale_open = is_sale_open;
+
+    emit_cpi!(SaleStateUpdated {
+        sale: sale.key(),
+        owner: sale.owner,
+        actor: signer.key(),
+        is_registration_open,
+        is_sale_open,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
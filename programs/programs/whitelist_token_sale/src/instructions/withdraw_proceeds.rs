@@ -0,0 +1,149 @@
+use crate::{
+    constants::PDA_SEED_SALE, error::WhitelistError, events::ProceedsWithdrawn,
+    state::WhitelistSale,
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::{
+    associated_token::{AssociatedToken, ID as ASSOCIATED_TOKEN_PROGRAM_ID},
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(sale_name: String)]
+pub struct WithdrawProceeds<'info> {
+    #[account(
+        mut,
+        seeds=[PDA_SEED_SALE.as_ref(), sale_name.as_bytes()],
+        bump
+    )]
+    pub sale: Account<'info, WhitelistSale>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: Address is checked against the sale's treasury, only receives lamports
+    #[account(
+        mut,
+        constraint=treasury.key() == sale.treasury_address() @ WhitelistError::TreasuryMismatch
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account()]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint=payment_mint,
+        associated_token::authority=sale,
+        associated_token::token_program=payment_token_program
+    )]
+    pub proceeds_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer=signer,
+        associated_token::mint=payment_mint,
+        associated_token::authority=treasury,
+        associated_token::token_program=payment_token_program
+    )]
+    pub treasury_payment_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+
+    #[account(address=ASSOCIATED_TOKEN_PROGRAM_ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Withdraws the proceeds collected so far to the treasury, keeping back what buyers can still get back
+pub fn handle_withdraw_proceeds(ctx: Context<WithdrawProceeds>, sale_name: String) -> Result<()> {
+    let sale = &mut ctx.accounts.sale;
+
+    require!(
+        sale.owner.eq(ctx.accounts.signer.key),
+        WhitelistError::OnlyOwner
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let reserved = sale.reserved_payments(now)?;
+
+    let amount = match sale.payment_mint {
+        None => {
+            // SOL proceeds sit on the program-owned sale account, above its rent-exempt minimum
+            let rent_exempt_minimum =
+                Rent::get()?.minimum_balance(sale.to_account_info().data_len());
+            let amount = sale
+                .get_lamports()
+                .saturating_sub(rent_exempt_minimum)
+                .saturating_sub(reserved);
+            require!(amount > 0, WhitelistError::NothingToWithdraw);
+
+            sale.sub_lamports(amount)?;
+            ctx.accounts.treasury.add_lamports(amount)?;
+
+            amount
+        }
+        Some(sale_payment_mint) => {
+            let (
+                Some(payment_mint),
+                Some(proceeds_ata),
+                Some(treasury_payment_ata),
+                Some(payment_token_program),
+            ) = (
+                &ctx.accounts.payment_mint,
+                &ctx.accounts.proceeds_ata,
+                &ctx.accounts.treasury_payment_ata,
+                &ctx.accounts.payment_token_program,
+            )
+            else {
+                return err!(WhitelistError::MissingPaymentAccounts);
+            };
+
+            require_keys_eq!(
+                payment_mint.key(),
+                sale_payment_mint,
+                WhitelistError::MintMismatch
+            );
+
+            let amount = proceeds_ata.amount.saturating_sub(reserved);
+            require!(amount > 0, WhitelistError::NothingToWithdraw);
+
+            let seeds = [
+                PDA_SEED_SALE.as_ref(),
+                sale_name.as_bytes(),
+                &[ctx.bumps.sale],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            let withdraw_context = CpiContext::new(
+                payment_token_program.to_account_info(),
+                TransferChecked {
+                    from: proceeds_ata.to_account_info(),
+                    to: treasury_payment_ata.to_account_info(),
+                    authority: sale.to_account_info(),
+                    mint: payment_mint.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds);
+
+            transfer_checked(withdraw_context, amount, payment_mint.decimals)?;
+
+            amount
+        }
+    };
+
+    sale.lamports_withdrawn = sale.lamports_withdrawn.safe_add(amount)?;
+
+    emit_cpi!(ProceedsWithdrawn {
+        sale: sale.key(),
+        treasury: ctx.accounts.treasury.key(),
+        amount,
+        lamports_withdrawn: sale.lamports_withdrawn,
+        timestamp: now,
+    });
+
+    Ok(())
+}
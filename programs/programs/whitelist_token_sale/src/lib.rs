@@ -0,0 +1,328 @@
+pub mod constants;
+pub mod error;
+pub mod events;
+pub mod instructions;
+pub mod lottery;
+pub mod merkle;
+pub mod pricing;
+pub mod state;
+pub mod voucher;
+
+use anchor_lang::prelude::*;
+use lottery::RandomnessSource;
+use pricing::PricingMode;
+use state::{SaleMode, VestingSchedule, WhitelistMode};
+
+pub use instructions::*;
+
+declare_id!("4KSUrirLpTrGMmgKNCcXtv7wsJ2kdfPTZJdtU4k4ABHs");
+
+#[program]
+pub mod whitelist_token_sale {
+    use create_whitelist_sale::CreateWhitelistSale;
+
+    use super::*;
+
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        admin: Pubkey,
+        fee_recipient: Pubkey,
+        fee_bps: u16,
+    ) -> Result<()> {
+        initialize_config::handle_initialize_config(ctx, admin, fee_recipient, fee_bps)
+    }
+
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        admin: Pubkey,
+        fee_recipient: Pubkey,
+        fee_bps: u16,
+    ) -> Result<()> {
+        update_config::handle_update_config(ctx, admin, fee_recipient, fee_bps)
+    }
+
+    pub fn create_whitelist_sale(
+        ctx: Context<CreateWhitelistSale>,
+        sale_name: String,
+        lamports_per_token: u64,
+        max_tokens_per_buyer: u64,
+        max_buyers: u64,
+        sale_mode: SaleMode,
+        referral_bps: u16,
+    ) -> Result<()> {
+        create_whitelist_sale::handle_create_whitelist_sale(
+            ctx,
+            sale_name,
+            lamports_per_token,
+            max_tokens_per_buyer,
+            max_buyers,
+            sale_mode,
+            referral_bps,
+        )
+    }
+
+    pub fn migrate_whitelist_sale(
+        ctx: Context<MigrateWhitelistSale>,
+        sale_name: String,
+    ) -> Result<()> {
+        migrate_whitelist_sale::handle_migrate_whitelist_sale(ctx, sale_name)
+    }
+
+    pub fn migrate_allowance(
+        ctx: Context<MigrateAllowance>,
+        sale_name: String,
+        buyer: Pubkey,
+    ) -> Result<()> {
+        migrate_allowance::handle_migrate_allowance(ctx, sale_name, buyer)
+    }
+
+    pub fn update_sale_state(
+        ctx: Context<UpdateSaleState>,
+        sale_name: String,
+        is_registration_open: bool,
+        is_sale_open: bool,
+    ) -> Result<()> {
+        update_sale_state::handle_update_sale_state(
+            ctx,
+            sale_name,
+            is_registration_open,
+            is_sale_open,
+        )
+    }
+
+    pub fn set_sale_windows(
+        ctx: Context<SetSaleWindows>,
+        sale_name: String,
+        registration_start: Option<i64>,
+        registration_end: Option<i64>,
+        sale_start: Option<i64>,
+        sale_end: Option<i64>,
+    ) -> Result<()> {
+        set_sale_windows::handle_set_sale_windows(
+            ctx,
+            sale_name,
+            registration_start,
+            registration_end,
+            sale_start,
+            sale_end,
+        )
+    }
+
+    pub fn set_sale_caps(
+        ctx: Context<SetSaleCaps>,
+        sale_name: String,
+        total_tokens_for_sale: u64,
+        hard_cap_lamports: Option<u64>,
+    ) -> Result<()> {
+        set_sale_caps::handle_set_sale_caps(
+            ctx,
+            sale_name,
+            total_tokens_for_sale,
+            hard_cap_lamports,
+        )
+    }
+
+    pub fn set_commit_mode(
+        ctx: Context<SetCommitMode>,
+        sale_name: String,
+        is_commit_mode: bool,
+    ) -> Result<()> {
+        set_commit_mode::handle_set_commit_mode(ctx, sale_name, is_commit_mode)
+    }
+
+    pub fn set_pricing_mode(
+        ctx: Context<SetPricingMode>,
+        sale_name: String,
+        pricing_mode: PricingMode,
+    ) -> Result<()> {
+        set_pricing_mode::handle_set_pricing_mode(ctx, sale_name, pricing_mode)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_sale_phase(
+        ctx: Context<SetSalePhase>,
+        sale_name: String,
+        index: u8,
+        start: i64,
+        end: i64,
+        lamports_per_token: u64,
+        max_tokens_per_buyer: u64,
+        max_tokens: Option<u64>,
+        requires_whitelist: bool,
+    ) -> Result<()> {
+        set_sale_phase::handle_set_sale_phase(
+            ctx,
+            sale_name,
+            index,
+            start,
+            end,
+            lamports_per_token,
+            max_tokens_per_buyer,
+            max_tokens,
+            requires_whitelist,
+        )
+    }
+
+    pub fn set_soft_cap(
+        ctx: Context<SetSoftCap>,
+        sale_name: String,
+        soft_cap: u64,
+        deadline: i64,
+    ) -> Result<()> {
+        set_soft_cap::handle_set_soft_cap(ctx, sale_name, soft_cap, deadline)
+    }
+
+    pub fn finalize_sale(ctx: Context<FinalizeSale>, sale_name: String) -> Result<()> {
+        finalize_sale::handle_finalize_sale(ctx, sale_name)
+    }
+
+    pub fn set_vesting_schedule(
+        ctx: Context<SetVestingSchedule>,
+        sale_name: String,
+        vesting: Option<VestingSchedule>,
+    ) -> Result<()> {
+        set_vesting_schedule::handle_set_vesting_schedule(ctx, sale_name, vesting)
+    }
+
+    pub fn set_whitelist_mode(
+        ctx: Context<SetWhitelistMode>,
+        sale_name: String,
+        whitelist_mode: WhitelistMode,
+    ) -> Result<()> {
+        set_whitelist_mode::handle_set_whitelist_mode(ctx, sale_name, whitelist_mode)
+    }
+
+    pub fn add_to_whitelist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddToWhitelist<'info>>,
+        sale_name: String,
+    ) -> Result<()> {
+        add_to_whitelist::handle_add_to_whitelist(ctx, sale_name)
+    }
+
+    pub fn remove_from_whitelist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RemoveFromWhitelist<'info>>,
+        sale_name: String,
+    ) -> Result<()> {
+        remove_from_whitelist::handle_remove_from_whitelist(ctx, sale_name)
+    }
+
+    pub fn set_whitelist_signer(
+        ctx: Context<SetWhitelistSigner>,
+        sale_name: String,
+        whitelist_signer: Pubkey,
+    ) -> Result<()> {
+        set_whitelist_signer::handle_set_whitelist_signer(ctx, sale_name, whitelist_signer)
+    }
+
+    pub fn update_merkle_root(
+        ctx: Context<UpdateMerkleRoot>,
+        sale_name: String,
+        merkle_root: [u8; 32],
+    ) -> Result<()> {
+        update_merkle_root::handle_update_merkle_root(ctx, sale_name, merkle_root)
+    }
+
+    pub fn set_randomness_source(
+        ctx: Context<SetRandomnessSource>,
+        sale_name: String,
+        randomness_source: RandomnessSource,
+    ) -> Result<()> {
+        set_randomness_source::handle_set_randomness_source(ctx, sale_name, randomness_source)
+    }
+
+    pub fn draw_lottery(ctx: Context<DrawLottery>, sale_name: String) -> Result<()> {
+        draw_lottery::handle_draw_lottery(ctx, sale_name)
+    }
+
+    pub fn settle_lottery_ticket(
+        ctx: Context<SettleLotteryTicket>,
+        sale_name: String,
+    ) -> Result<()> {
+        settle_lottery_ticket::handle_settle_lottery_ticket(ctx, sale_name)
+    }
+
+    pub fn register_for_whitelist(
+        ctx: Context<RegisterForWhitelist>,
+        sale_name: String,
+    ) -> Result<()> {
+        register_for_whitelist::handle_register_for_whitelist(ctx, sale_name)
+    }
+
+    pub fn register_with_merkle_proof(
+        ctx: Context<RegisterWithMerkleProof>,
+        sale_name: String,
+        allocation: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        register_with_merkle_proof::handle_register_with_merkle_proof(
+            ctx, sale_name, allocation, proof,
+        )
+    }
+
+    pub fn buy_tokens(ctx: Context<BuyTokens>, sale_name: String, amount: u64) -> Result<()> {
+        buy_tokens::handle_buy_tokens(ctx, sale_name, amount)
+    }
+
+    pub fn commit(ctx: Context<Commit>, sale_name: String, lamports: u64) -> Result<()> {
+        commit::handle_commit(ctx, sale_name, lamports)
+    }
+
+    pub fn settle_commitment(ctx: Context<SettleCommitment>, sale_name: String) -> Result<()> {
+        settle_commitment::handle_settle_commitment(ctx, sale_name)
+    }
+
+    pub fn claim_vested(ctx: Context<ClaimVested>, sale_name: String) -> Result<()> {
+        claim_vested::handle_claim_vested(ctx, sale_name)
+    }
+
+    pub fn claim_rebate(ctx: Context<ClaimRebate>, sale_name: String) -> Result<()> {
+        claim_rebate::handle_claim_rebate(ctx, sale_name)
+    }
+
+    pub fn claim_referral_rewards(
+        ctx: Context<ClaimReferralRewards>,
+        sale_name: String,
+    ) -> Result<()> {
+        claim_referral_rewards::handle_claim_referral_rewards(ctx, sale_name)
+    }
+
+    pub fn claim_refund(ctx: Context<ClaimRefund>, sale_name: String) -> Result<()> {
+        claim_refund::handle_claim_refund(ctx, sale_name)
+    }
+
+    pub fn set_treasury(
+        ctx: Context<SetTreasury>,
+        sale_name: String,
+        treasury: Pubkey,
+    ) -> Result<()> {
+        set_treasury::handle_set_treasury(ctx, sale_name, treasury)
+    }
+
+    pub fn set_operator(
+        ctx: Context<SetOperator>,
+        sale_name: String,
+        operator: Option<Pubkey>,
+    ) -> Result<()> {
+        set_operator::handle_set_operator(ctx, sale_name, operator)
+    }
+
+    pub fn propose_owner(
+        ctx: Context<ProposeOwner>,
+        sale_name: String,
+        pending_owner: Option<Pubkey>,
+    ) -> Result<()> {
+        propose_owner::handle_propose_owner(ctx, sale_name, pending_owner)
+    }
+
+    pub fn accept_ownership(ctx: Context<AcceptOwnership>, sale_name: String) -> Result<()> {
+        accept_ownership::handle_accept_ownership(ctx, sale_name)
+    }
+
+    pub fn withdraw_proceeds(ctx: Context<WithdrawProceeds>, sale_name: String) -> Result<()> {
+        withdraw_proceeds::handle_withdraw_proceeds(ctx, sale_name)
+    }
+
+    pub fn close_whitelist_sale(ctx: Context<CloseWhitelistSale>, sale_name: String) -> Result<()> {
+        close_whitelist_sale::handle_close_whitelist_sale(ctx, sale_name)
+    }
+}
@@ -0,0 +1,135 @@
+use crate::error::WhitelistError;
+use anchor_lang::{
+    prelude::*,
+    solana_program::{hash::hashv, sysvar::slot_hashes},
+};
+
+/// Where a sale's lottery draw gets its randomness from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RandomnessSource {
+    /// Most recent slot hash. Validators can influence it, so it's only meant for tests and low-stakes sales.
+    SlotHashes,
+    /// 32 bytes of randomness at `offset` in an account written by a randomness oracle.
+    /// The account has to be owned by the oracle's `program`, so buyers can check where the randomness comes from.
+    Oracle {
+        program: Pubkey,
+        account: Pubkey,
+        offset: u32,
+    },
+}
+
+/// Reads randomness from the account passed to the draw
+pub trait Randomness {
+    fn read(&self, account: &AccountInfo) -> Result<[u8; 32]>;
+}
+
+pub struct SlotHashesRandomness;
+
+pub struct OracleRandomness {
+    pub program: Pubkey,
+    pub account: Pubkey,
+    pub offset: usize,
+}
+
+impl Randomness for SlotHashesRandomness {
+    fn read(&self, account: &AccountInfo) -> Result<[u8; 32]> {
+        require_keys_eq!(
+            *account.key,
+            slot_hashes::ID,
+            WhitelistError::InvalidRandomnessSource
+        );
+
+        // The sysvar is a u64 length followed by (slot, hash) entries, most recent first
+        let data = account.try_borrow_data()?;
+        let hash = data
+            .get(16..48)
+            .ok_or(WhitelistError::InvalidRandomnessSource)?;
+
+        Ok(hash.try_into().unwrap())
+    }
+}
+
+impl Randomness for OracleRandomness {
+    fn read(&self, account: &AccountInfo) -> Result<[u8; 32]> {
+        require_keys_eq!(
+            *account.key,
+            self.account,
+            WhitelistError::InvalidRandomnessSource
+        );
+        require_keys_eq!(
+            *account.owner,
+            self.program,
+            WhitelistError::InvalidRandomnessSource
+        );
+
+        let data = account.try_borrow_data()?;
+        let value: [u8; 32] = data
+            .get(self.offset..self.offset + 32)
+            .ok_or(WhitelistError::InvalidRandomnessSource)?
+            .try_into()
+            .unwrap();
+
+        // Oracles leave the value zeroed until they fulfill the request
+        require!(value != [0; 32], WhitelistError::RandomnessUnavailable);
+
+        Ok(value)
+    }
+}
+
+impl RandomnessSource {
+    pub fn randomness(&self) -> Box<dyn Randomness> {
+        match *self {
+            RandomnessSource::SlotHashes => Box::new(SlotHashesRandomness),
+            RandomnessSource::Oracle {
+                program,
+                account,
+                offset,
+            } => Box::new(OracleRandomness {
+                program,
+                account,
+                offset: offset as usize,
+            }),
+        }
+    }
+}
+
+const FEISTEL_ROUNDS: u8 = 4;
+
+/// Whether `ticket` is among the `num_winners` winning tickets out of `num_tickets`.
+/// Tickets are shuffled with a permutation keyed by `seed`, which can be evaluated for each ticket on its own,
+/// and the first `num_winners` positions win.
+pub fn is_winner(seed: &[u8; 32], ticket: u64, num_tickets: u64, num_winners: u64) -> bool {
+    shuffled_position(seed, ticket, num_tickets) < num_winners
+}
+
+/// Position of `ticket` in a permutation of `0..num_tickets` keyed by `seed`.
+/// A Feistel network permutes the smallest even power of two covering all tickets,
+/// and positions outside of the tickets are walked past until one lands inside.
+fn shuffled_position(seed: &[u8; 32], ticket: u64, num_tickets: u64) -> u64 {
+    let mut half_bits = 1;
+    while half_bits < 32 && (1_u64 << (2 * half_bits)) < num_tickets {
+        half_bits += 1;
+    }
+
+    let mut position = feistel(seed, ticket, half_bits);
+    while position >= num_tickets {
+        position = feistel(seed, position, half_bits);
+    }
+
+    position
+}
+
+fn feistel(seed: &[u8; 32], value: u64, half_bits: u32) -> u64 {
+    let mask = (1_u64 << half_bits) - 1;
+    let mut left = (value >> half_bits) & mask;
+    let mut right = value & mask;
+
+    for round in 0..FEISTEL_ROUNDS {
+        let hash = hashv(&[seed, &[round], &right.to_le_bytes()]).to_bytes();
+        let round_value = u64::from_le_bytes(hash[..8].try_into().unwrap());
+
+        (left, right) = (right, left ^ (round_value & mask));
+    }
+
+    (left << half_bits) | right
+}
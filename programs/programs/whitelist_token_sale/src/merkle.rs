@@ -0,0 +1,25 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+// Leaves and nodes are domain-separated so that a node can never be passed off as a leaf
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Hashes a whitelist entry. An `allocation` of 0 means the sale's `max_tokens_per_buyer` applies.
+pub fn leaf(buyer: &Pubkey, allocation: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, buyer.as_ref(), &allocation.to_le_bytes()]).to_bytes()
+}
+
+/// Hashes two sibling nodes. Siblings are sorted first, so proofs don't need to carry their position.
+pub fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed_root = proof
+        .iter()
+        .fold(leaf, |hash, sibling| node(&hash, sibling));
+
+    computed_root.eq(root)
+}
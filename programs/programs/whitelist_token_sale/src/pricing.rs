@@ -0,0 +1,193 @@
+use crate::{constants::BPS_DENOMINATOR, error::WhitelistError};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+// Prices are rounded up, so that rounding always works against the buyer
+
+// Fixed-point scale of the exponential curve's intermediate values
+const CURVE_SCALE: u128 = 1_000_000_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PricingMode {
+    /// Every token costs the sale's `lamports_per_token`
+    Fixed,
+    DutchAuction(DutchAuction),
+    LinearCurve(LinearCurve),
+    ExponentialCurve(ExponentialCurve),
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DecayCurve {
+    /// Price falls linearly from the start price to the floor price
+    Linear,
+    /// Price's excess over the floor price halves every `half_life_seconds`, linearly in between
+    Exponential { half_life_seconds: i64 },
+}
+
+/// Price falls over time from `start_price` towards `floor_price`, which it reaches when the auction ends.
+/// With uniform clearing, every buyer ends up paying the last price tokens sold at.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DutchAuction {
+    pub start_price: u64,
+    pub floor_price: u64,
+    pub start_timestamp: i64,
+    pub end_timestamp: i64,
+    pub curve: DecayCurve,
+    pub uniform_clearing: bool,
+}
+
+/// Bonding curve where the n-th token sold (counting from 0) costs `base_price + slope * n`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LinearCurve {
+    pub base_price: u64,
+    pub slope: u64,
+}
+
+/// Bonding curve where each token sold costs `growth_bps` more than the previous one,
+/// i.e. the n-th token sold (counting from 0) costs `base_price * (1 + growth_bps / 10000)^n`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ExponentialCurve {
+    pub base_price: u64,
+    pub growth_bps: u16,
+}
+
+impl PricingMode {
+    pub fn is_valid(&self) -> bool {
+        match self {
+            PricingMode::Fixed => true,
+            PricingMode::DutchAuction(auction) => {
+                auction.floor_price <= auction.start_price
+                    && auction.start_timestamp < auction.end_timestamp
+                    && match auction.curve {
+                        DecayCurve::Linear => true,
+                        DecayCurve::Exponential { half_life_seconds } => half_life_seconds > 0,
+                    }
+            }
+            PricingMode::LinearCurve(_) => true,
+            PricingMode::ExponentialCurve(curve) => curve.growth_bps > 0,
+        }
+    }
+}
+
+impl DutchAuction {
+    pub fn has_ended(&self, now: i64) -> bool {
+        now >= self.end_timestamp
+    }
+
+    /// Price per token at `now`, which has to be within the auction.
+    /// Tokens can't be bought once it has ended, so that the clearing price is final.
+    pub fn price_at(&self, now: i64) -> Result<u64> {
+        require!(
+            now >= self.start_timestamp && !self.has_ended(now),
+            WhitelistError::SaleClosed
+        );
+
+        let excess = u128::from(self.start_price.safe_sub(self.floor_price)?);
+        let elapsed = now.abs_diff(self.start_timestamp) as u128;
+
+        let remaining_excess = match self.curve {
+            DecayCurve::Linear => {
+                let duration = self.end_timestamp.abs_diff(self.start_timestamp) as u128;
+
+                excess
+                    .safe_mul(duration.safe_sub(elapsed)?)?
+                    .div_ceil(duration)
+            }
+            DecayCurve::Exponential { half_life_seconds } => {
+                let half_life = half_life_seconds as u128;
+                let halvings = elapsed.safe_div(half_life)?;
+
+                // The excess fits in a u64, so it's gone after 64 halvings
+                if halvings >= 64 {
+                    0
+                } else {
+                    let excess = excess.div_ceil(1 << halvings);
+                    let decrease = excess
+                        .safe_div(2)?
+                        .safe_mul(elapsed % half_life)?
+                        .safe_div(half_life)?;
+
+                    excess.safe_sub(decrease)?
+                }
+            }
+        };
+
+        Ok(self.floor_price.safe_add(remaining_excess as u64)?)
+    }
+}
+
+impl LinearCurve {
+    /// Exact cost of buying `amount` tokens once `tokens_sold` tokens have been sold
+    pub fn cost(&self, tokens_sold: u64, amount: u64) -> Result<u64> {
+        let tokens_sold = u128::from(tokens_sold);
+        let amount = u128::from(amount);
+
+        // Sum of the tokens' positions on the curve, i.e. tokens_sold + ... + (tokens_sold + amount - 1)
+        let positions = tokens_sold
+            .safe_mul(amount)?
+            .safe_add(amount.safe_mul(amount.saturating_sub(1))?.safe_div(2)?)?;
+
+        let cost = u128::from(self.base_price)
+            .safe_mul(amount)?
+            .safe_add(u128::from(self.slope).safe_mul(positions)?)?;
+
+        Ok(u64::try_from(cost).map_err(|_| WhitelistError::PriceOverflow)?)
+    }
+}
+
+impl ExponentialCurve {
+    /// Cost of buying `amount` tokens once `tokens_sold` tokens have been sold, rounded up.
+    /// Summed as the geometric series `base_price * (q^(tokens_sold + amount) - q^tokens_sold) / (q - 1)`.
+    pub fn cost(&self, tokens_sold: u64, amount: u64) -> Result<u64> {
+        let growth_bps = u128::from(self.growth_bps);
+        let ratio = CURVE_SCALE.safe_add(
+            CURVE_SCALE
+                .safe_mul(growth_bps)?
+                .safe_div(BPS_DENOMINATOR.into())?,
+        )?;
+
+        // The upper power is rounded up and the lower one down, so that their difference is rounded up
+        let upper = pow_scaled(ratio, tokens_sold.safe_add(amount)?, true)?;
+        let lower = pow_scaled(ratio, tokens_sold, false)?;
+
+        let series = upper
+            .safe_sub(lower)?
+            .safe_mul(BPS_DENOMINATOR.into())?
+            .div_ceil(growth_bps);
+
+        let cost = u128::from(self.base_price)
+            .safe_mul(series)?
+            .div_ceil(CURVE_SCALE);
+
+        Ok(u64::try_from(cost).map_err(|_| WhitelistError::PriceOverflow)?)
+    }
+}
+
+/// Raises a `CURVE_SCALE` fixed-point number to `exponent`, rounding every step in the same direction
+fn pow_scaled(base: u128, mut exponent: u64, round_up: bool) -> Result<u128> {
+    let mul_scaled = |a: u128, b: u128| -> Result<u128> {
+        let product = a.safe_mul(b)?;
+
+        Ok(if round_up {
+            product.div_ceil(CURVE_SCALE)
+        } else {
+            product.safe_div(CURVE_SCALE)?
+        })
+    };
+
+    let mut base = base;
+    let mut result = CURVE_SCALE;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_scaled(result, base)?;
+        }
+
+        exponent >>= 1;
+        if exponent > 0 {
+            base = mul_scaled(base, base)?;
+        }
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,492 @@
+use crate::{
+    constants::{BPS_DENOMINATOR, MAX_SALE_PHASES},
+    error::WhitelistError,
+    lottery::RandomnessSource,
+    pricing::PricingMode,
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// How a sale supplies the tokens it sells
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SaleMode {
+    /// Tokens are deposited into a vault owned by the sale at creation, and transferred out of it
+    Vault,
+    /// The sale becomes the mint authority of the token, and mints tokens as they're sold
+    Mint,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WhitelistMode {
+    /// Anyone can register themselves while registration is open
+    Open,
+    /// Only the sale owner can add buyers to the whitelist
+    Curated,
+    /// Buyers prove their spot against a Merkle root set by the sale owner
+    Merkle,
+    /// Buyers register with a voucher signed off-chain by the sale's whitelist signer
+    Voucher,
+    /// Anyone can register a lottery ticket, and a random draw decides which tickets get approved
+    Lottery,
+}
+
+/// A round of the sale with its own price and limits, e.g. a guaranteed round followed by a public one
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct SalePhase {
+    /// Unix timestamps bounding the phase, start inclusive and end exclusive
+    pub start: i64,
+    pub end: i64,
+    pub lamports_per_token: u64,
+    pub max_tokens_per_buyer: u64,
+    /// Total number of tokens sold in this phase across all buyers, uncapped when not set
+    pub max_tokens: Option<u64>,
+    pub tokens_sold: u64,
+    pub requires_whitelist: bool,
+}
+
+impl SalePhase {
+    pub fn is_active(&self, now: i64) -> bool {
+        is_within_window(now, Some(self.start), Some(self.end))
+    }
+
+    pub fn overlaps(&self, other: &SalePhase) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+}
+
+/// Releases purchased tokens gradually instead of delivering them at purchase
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct VestingSchedule {
+    /// Unix timestamp of the token generation event, before which nothing is unlocked
+    pub tge_timestamp: i64,
+    /// Share of the tokens unlocked at TGE, in basis points
+    pub tge_unlock_bps: u16,
+    /// Seconds after TGE before the rest starts unlocking
+    pub cliff_seconds: i64,
+    /// Seconds over which the rest unlocks linearly once the cliff has passed
+    pub duration_seconds: i64,
+}
+
+impl VestingSchedule {
+    /// Portion of `total` that is unlocked at `now`, rounded down
+    pub fn unlocked_amount(&self, total: u64, now: i64) -> Result<u64> {
+        if now < self.tge_timestamp {
+            return Ok(0);
+        }
+
+        let total = u128::from(total);
+        let tge_amount = total
+            .safe_mul(self.tge_unlock_bps.into())?
+            .safe_div(BPS_DENOMINATOR.into())?;
+
+        let cliff_end = self.tge_timestamp.saturating_add(self.cliff_seconds);
+        if now < cliff_end {
+            return Ok(tge_amount as u64);
+        }
+
+        let elapsed = now.saturating_sub(cliff_end);
+        if elapsed >= self.duration_seconds {
+            return Ok(total as u64);
+        }
+
+        let linear_amount = total
+            .safe_sub(tge_amount)?
+            .safe_mul(elapsed as u128)?
+            .safe_div(self.duration_seconds as u128)?;
+
+        Ok(tge_amount.safe_add(linear_amount)? as u64)
+    }
+}
+
+#[account]
+pub struct WhitelistSale {
+    pub token_mint: Pubkey,
+    pub owner: Pubkey,
+    pub lamports_per_token: u64,
+    pub max_tokens_per_buyer: u64,
+    pub max_buyers: u64,
+    pub num_buyers: u64,
+    pub is_registration_open: bool,
+    pub is_sale_open: bool,
+    pub whitelist_mode: WhitelistMode,
+    pub merkle_root: [u8; 32],
+    pub whitelist_signer: Pubkey,
+    /// Mint that buyers pay in. Buyers pay in SOL when it isn't set.
+    pub payment_mint: Option<Pubkey>,
+    /// Optional unix timestamps bounding when registration and purchasing are possible.
+    /// Both are only possible when their flag is set and the current time is within their window.
+    pub registration_start: Option<i64>,
+    pub registration_end: Option<i64>,
+    pub sale_start: Option<i64>,
+    pub sale_end: Option<i64>,
+    /// Sale rounds, of which only the first `num_phases` are in use.
+    /// When there are none, the sale-wide price and limits above apply.
+    pub phases: [SalePhase; MAX_SALE_PHASES],
+    pub num_phases: u8,
+    /// Tokens are delivered right away when not set
+    pub vesting: Option<VestingSchedule>,
+    /// Tokens held back by the sale but not yet claimed by buyers, in base units
+    pub tokens_owed: u64,
+    /// Minimum raise, in lamports or the payment mint's base units, for the sale to go through.
+    /// Purchased tokens are held back until the owner finalizes the sale. Disabled when zero.
+    pub soft_cap: u64,
+    /// Unix timestamp at which purchasing ends when there is a soft cap
+    pub deadline: i64,
+    pub is_finalized: bool,
+    /// Payments received from buyers, in lamports or the payment mint's base units
+    pub lamports_raised: u64,
+    /// Maximum number of tokens sold across all buyers and phases
+    pub total_tokens_for_sale: u64,
+    pub tokens_sold: u64,
+    /// Maximum raise, in lamports or the payment mint's base units, uncapped when not set
+    pub hard_cap_lamports: Option<u64>,
+    /// How the price of tokens is determined when the sale has no phases
+    pub pricing_mode: PricingMode,
+    /// Last price per token paid in a Dutch auction, which buyers are rebated down to with uniform clearing
+    pub clearing_price: u64,
+    /// Buyers commit payments during the sale instead of buying, and are allocated tokens pro-rata once it's finalized
+    pub is_commit_mode: bool,
+    /// Payments committed by all buyers, in lamports or the payment mint's base units
+    pub total_committed: u64,
+    /// Committed payments that haven't been settled yet
+    pub committed_unsettled: u64,
+    /// Number of lottery tickets registered, each of which has the next ticket number
+    pub num_tickets: u64,
+    pub randomness_source: RandomnessSource,
+    pub is_lottery_drawn: bool,
+    /// Seed of the lottery draw, which decides the winning tickets
+    pub lottery_seed: [u8; 32],
+    /// Fields from here on are appended, so sales created before they existed only need to be extended by
+    /// `migrate_whitelist_sale`, which leaves them zeroed
+    pub sale_mode: SaleMode,
+    /// Receives the proceeds withdrawn during the sale, the owner when not set
+    pub treasury: Pubkey,
+    /// Proceeds withdrawn to the treasury, in lamports or the payment mint's base units
+    pub lamports_withdrawn: u64,
+    /// Platform fee taken from each purchase, snapshotted from the program config when the sale is created
+    pub fee_bps: u16,
+    pub fee_recipient: Pubkey,
+    /// Fees paid to the fee recipient, in lamports or the payment mint's base units
+    pub fees_paid: u64,
+    /// Share of referred buyers' payments rewarded to their referrer
+    pub referral_bps: u16,
+    /// Referral rewards accrued but not yet claimed, in lamports or the payment mint's base units
+    pub referral_rewards_owed: u64,
+    /// Proposed by the owner with `propose_owner`, becomes the owner once it accepts
+    pub pending_owner: Option<Pubkey>,
+    /// Can open and close registration and the sale, and manage the whitelist, but not the sale's economics
+    pub operator: Option<Pubkey>,
+    /// Fees of purchases made while the soft cap is pending, paid to the fee recipient once the sale is finalized
+    pub fees_owed: u64,
+    /// Held back tokens released to buyers through `claim_vested`, in base units
+    pub tokens_claimed: u64,
+}
+
+/// Settings shared by every sale, created by the program's upgrade authority
+#[account]
+pub struct ProgramConfig {
+    /// Can update the config once it's created
+    pub admin: Pubkey,
+    pub fee_recipient: Pubkey,
+    /// Share of each payment going to the fee recipient, in basis points
+    pub fee_bps: u16,
+}
+
+#[account]
+pub struct Allowance {
+    pub buyer: Pubkey,
+    pub tokens_bought: u64,
+    pub is_approved: bool,
+    /// Overrides `max_tokens_per_buyer` for this buyer when set
+    pub allocation: Option<u64>,
+    /// Tokens bought in each of the sale's phases
+    pub phase_tokens_bought: [u64; MAX_SALE_PHASES],
+    /// Tokens bought but held back by the sale until they're claimed
+    pub tokens_held: u64,
+    /// Held back tokens already claimed, in base units
+    pub tokens_claimed: u64,
+    /// Payments made by the buyer, in lamports or the payment mint's base units
+    pub lamports_paid: u64,
+    /// Payment committed by the buyer in commit mode that hasn't been settled yet
+    pub lamports_committed: u64,
+    /// Buyer's ticket when the sale's whitelist is a lottery
+    pub ticket_number: u64,
+    /// Earns a share of the buyer's payments when set
+    pub referrer: Option<Pubkey>,
+}
+
+/// Referral rewards of a referrer in a sale
+#[account]
+pub struct ReferrerStats {
+    pub sale: Pubkey,
+    pub referrer: Pubkey,
+    pub num_referrals: u64,
+    /// Rewards accrued from referred purchases, in lamports or the payment mint's base units
+    pub rewards_earned: u64,
+    pub rewards_claimed: u64,
+}
+
+impl WhitelistSale {
+    /// Maximum number of tokens the allowance's buyer can purchase
+    pub fn max_tokens_for(&self, allowance: &Allowance) -> u64 {
+        allowance.allocation.unwrap_or(self.max_tokens_per_buyer)
+    }
+
+    pub fn is_registration_active(&self, now: i64) -> bool {
+        self.is_registration_open
+            && is_within_window(now, self.registration_start, self.registration_end)
+    }
+
+    pub fn is_sale_active(&self, now: i64) -> bool {
+        self.is_sale_open
+            && is_within_window(now, self.sale_start, self.sale_end)
+            && (self.soft_cap == 0 || now < self.deadline)
+    }
+
+    /// Whether commitments can no longer be made because the sale window has ended
+    pub fn has_commit_window_ended(&self, now: i64) -> bool {
+        self.is_commit_mode && self.sale_end.is_some_and(|sale_end| now >= sale_end)
+    }
+
+    /// Whether purchased tokens are held back for buyers to claim later instead of being delivered right away
+    pub fn holds_tokens(&self) -> bool {
+        self.vesting.is_some() || self.soft_cap > 0 || self.is_commit_mode
+    }
+
+    /// Whether `key` can perform operator actions, which the owner can too
+    pub fn is_operator(&self, key: &Pubkey) -> bool {
+        self.owner.eq(key) || self.operator.eq(&Some(*key))
+    }
+
+    pub fn treasury_address(&self) -> Pubkey {
+        if self.treasury == Pubkey::default() {
+            self.owner
+        } else {
+            self.treasury
+        }
+    }
+
+    /// Whether purchases can still be refunded, so their platform fee stays with the sale until it's finalized
+    pub fn defers_fees(&self) -> bool {
+        self.soft_cap > 0 && !self.is_finalized
+    }
+
+    /// Platform fee taken from `payment`, rounded down
+    pub fn fee_for(&self, payment: u64) -> Result<u64> {
+        share_of(payment, self.fee_bps)
+    }
+
+    /// Reward of the referrer of a buyer making `payment`, rounded down
+    pub fn referral_reward_for(&self, payment: u64) -> Result<u64> {
+        share_of(payment, self.referral_bps)
+    }
+
+    /// Referral rewards can't be claimed while buyers might still get a refund
+    pub fn are_referral_rewards_claimable(&self) -> bool {
+        self.soft_cap == 0 || self.is_finalized
+    }
+
+    /// Payments that buyers can still get back, through refunds, rebates or settling their commitments,
+    /// and rewards owed to referrers, which have to stay with the sale
+    pub fn reserved_payments(&self, now: i64) -> Result<u64> {
+        // Everything raised is refunded if the soft cap ends up missed, as fees are only paid once it's finalized
+        let refundable = if self.soft_cap > 0 && !self.is_finalized {
+            self.lamports_raised.saturating_sub(self.fees_paid)
+        } else {
+            0
+        };
+
+        Ok(self
+            .committed_unsettled
+            .safe_add(self.referral_rewards_owed)?
+            .safe_add(refundable.max(self.unclaimed_rebates(now)?))?)
+    }
+
+    /// Rebates that buyers of a uniform-price Dutch auction are owed, or can still be owed
+    pub fn unclaimed_rebates(&self, now: i64) -> Result<u64> {
+        match self.pricing_mode {
+            PricingMode::DutchAuction(auction) if auction.uniform_clearing => {
+                // The clearing price can still fall to the floor price while tokens can be bought
+                let lowest_price =
+                    if auction.has_ended(now) || self.tokens_sold >= self.total_tokens_for_sale {
+                        self.clearing_price
+                    } else {
+                        auction.floor_price
+                    };
+
+                Ok(self
+                    .lamports_raised
+                    .saturating_sub(self.tokens_sold.safe_mul(lowest_price)?))
+            }
+            _ => Ok(0),
+        }
+    }
+
+    /// Whether the soft cap was missed by the deadline, entitling buyers to refunds
+    pub fn is_refundable(&self, now: i64) -> bool {
+        self.soft_cap > 0 && now >= self.deadline && self.lamports_raised < self.soft_cap
+    }
+
+    pub fn active_phases(&self) -> &[SalePhase] {
+        &self.phases[..self.num_phases as usize]
+    }
+
+    /// Records a purchase of `amount` tokens in the phase active at `now`, returning the phase's price per token.
+    /// Buyers' allocations override the per-buyer cap of phases that require the whitelist.
+    pub fn buy_in_phase(
+        &mut self,
+        allowance: &mut Allowance,
+        now: i64,
+        amount: u64,
+    ) -> Result<u64> {
+        let index = self
+            .active_phases()
+            .iter()
+            .position(|phase| phase.is_active(now))
+            .ok_or(WhitelistError::SaleClosed)?;
+        let phase = &mut self.phases[index];
+
+        let max_tokens_per_buyer = if phase.requires_whitelist {
+            require!(allowance.is_approved, WhitelistError::NotWhitelisted);
+            allowance.allocation.unwrap_or(phase.max_tokens_per_buyer)
+        } else {
+            phase.max_tokens_per_buyer
+        };
+
+        let tokens_bought = &mut allowance.phase_tokens_bought[index];
+        *tokens_bought = tokens_bought.safe_add(amount)?;
+        require!(
+            *tokens_bought <= max_tokens_per_buyer,
+            WhitelistError::AllowanceExceeded
+        );
+
+        phase.tokens_sold = phase.tokens_sold.safe_add(amount)?;
+        if let Some(max_tokens) = phase.max_tokens {
+            require!(
+                phase.tokens_sold <= max_tokens,
+                WhitelistError::PhaseSoldOut
+            );
+        }
+
+        Ok(phase.lamports_per_token)
+    }
+
+    /// Cost of buying `amount` tokens at `now` under the sale's pricing mode
+    pub fn purchase_cost(&mut self, amount: u64, now: i64) -> Result<u64> {
+        match self.pricing_mode {
+            PricingMode::Fixed => Ok(self.lamports_per_token.safe_mul(amount)?),
+            PricingMode::DutchAuction(auction) => {
+                let price = auction.price_at(now)?;
+                self.clearing_price = price;
+
+                Ok(price.safe_mul(amount)?)
+            }
+            PricingMode::LinearCurve(curve) => curve.cost(self.tokens_sold, amount),
+            PricingMode::ExponentialCurve(curve) => curve.cost(self.tokens_sold, amount),
+        }
+    }
+
+    /// Records a purchase of `amount` tokens paid with `payment`, enforcing the sale-wide caps
+    pub fn record_purchase(&mut self, amount: u64, payment: u64) -> Result<()> {
+        self.tokens_sold = self.tokens_sold.safe_add(amount)?;
+        require!(
+            self.tokens_sold <= self.total_tokens_for_sale,
+            WhitelistError::SaleSoldOut
+        );
+
+        self.lamports_raised = self.lamports_raised.safe_add(payment)?;
+        if let Some(hard_cap_lamports) = self.hard_cap_lamports {
+            require!(
+                self.lamports_raised <= hard_cap_lamports,
+                WhitelistError::HardCapReached
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Settles the allowance's commitment, allocating its buyer their share of `total_tokens_for_sale`.
+    /// Buyers get all the tokens they committed to when the sale isn't oversubscribed, and a pro-rata share otherwise.
+    /// Returns the number of tokens allocated and the excess payment to refund.
+    pub fn settle_commitment(&mut self, allowance: &mut Allowance) -> Result<(u64, u64)> {
+        let committed = allowance.lamports_committed;
+        let supply_cost =
+            u128::from(self.total_tokens_for_sale).safe_mul(self.lamports_per_token.into())?;
+
+        let tokens = if u128::from(self.total_committed) <= supply_cost {
+            committed.safe_div(self.lamports_per_token)?
+        } else {
+            // Rounded down, so that allocations never add up to more than the supply
+            u128::from(self.total_tokens_for_sale)
+                .safe_mul(committed.into())?
+                .safe_div(self.total_committed.into())? as u64
+        };
+
+        let cost = tokens.safe_mul(self.lamports_per_token)?;
+        let excess = committed.safe_sub(cost)?;
+
+        allowance.lamports_committed = 0;
+        allowance.tokens_bought = allowance.tokens_bought.safe_add(tokens)?;
+        allowance.tokens_held = allowance.tokens_held.safe_add(tokens)?;
+        allowance.lamports_paid = allowance.lamports_paid.safe_add(cost)?;
+
+        self.committed_unsettled = self.committed_unsettled.safe_sub(committed)?;
+        self.tokens_sold = self.tokens_sold.safe_add(tokens)?;
+        self.lamports_raised = self.lamports_raised.safe_add(cost)?;
+
+        Ok((tokens, excess))
+    }
+
+    /// Marks the allowance as approved, counting it towards `max_buyers` the first time
+    pub fn approve_buyer(&mut self, allowance: &mut Allowance) -> Result<()> {
+        if allowance.is_approved {
+            return Ok(());
+        }
+
+        require!(
+            self.num_buyers < self.max_buyers,
+            WhitelistError::BuyerLimitReached
+        );
+
+        allowance.is_approved = true;
+        self.num_buyers = self.num_buyers.safe_add(1)?;
+
+        Ok(())
+    }
+
+    /// Revokes the allowance's approval, freeing up its spot under `max_buyers`
+    pub fn revoke_buyer(&mut self, allowance: &mut Allowance) -> Result<()> {
+        if !allowance.is_approved {
+            return Ok(());
+        }
+
+        allowance.is_approved = false;
+        self.num_buyers = self.num_buyers.safe_sub(1)?;
+
+        Ok(())
+    }
+}
+
+/// Share of `amount` in basis points, rounded down
+fn share_of(amount: u64, bps: u16) -> Result<u64> {
+    Ok(u128::from(amount)
+        .safe_mul(bps.into())?
+        .safe_div(BPS_DENOMINATOR.into())? as u64)
+}
+
+/// Windows include their start and exclude their end, and are unbounded on sides that aren't set
+fn is_within_window(now: i64, start: Option<i64>, end: Option<i64>) -> bool {
+    if let Some(start) = start {
+        if now < start {
+            return false;
+        }
+    }
+
+    if let Some(end) = end {
+        if now >= end {
+            return false;
+        }
+    }
+
+    true
+}
@@ -0,0 +1,113 @@
+use crate::error::WhitelistError;
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        ed25519_program,
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    },
+};
+
+// Layout of the Ed25519 program's instruction data: a signature count, a padding byte,
+// then one set of seven little-endian u16 offsets per signature
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+// Offsets that point into the Ed25519 instruction itself, rather than another instruction
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+pub const VOUCHER_MESSAGE_SIZE: usize = 32 + 32 + 8 + 8;
+
+/// Allowlist voucher issued off-chain and signed by the sale's `whitelist_signer`
+pub struct Voucher {
+    pub sale: Pubkey,
+    pub buyer: Pubkey,
+    pub allocation: u64,
+    pub expires_at: i64,
+}
+
+impl Voucher {
+    /// Message that the whitelist signer signs: `sale || buyer || allocation || expires_at`
+    pub fn to_message(&self) -> [u8; VOUCHER_MESSAGE_SIZE] {
+        let mut message = [0; VOUCHER_MESSAGE_SIZE];
+
+        message[..32].copy_from_slice(self.sale.as_ref());
+        message[32..64].copy_from_slice(self.buyer.as_ref());
+        message[64..72].copy_from_slice(&self.allocation.to_le_bytes());
+        message[72..].copy_from_slice(&self.expires_at.to_le_bytes());
+
+        message
+    }
+
+    pub fn from_message(message: &[u8]) -> Option<Self> {
+        if message.len() != VOUCHER_MESSAGE_SIZE {
+            return None;
+        }
+
+        Some(Self {
+            sale: Pubkey::try_from(&message[..32]).ok()?,
+            buyer: Pubkey::try_from(&message[32..64]).ok()?,
+            allocation: u64::from_le_bytes(message[64..72].try_into().ok()?),
+            expires_at: i64::from_le_bytes(message[72..].try_into().ok()?),
+        })
+    }
+
+    /// Loads the voucher from the Ed25519 instruction placed right before the current one.
+    /// The Ed25519 program has already verified the signature by the time we get here,
+    /// so we only need to make sure that it was made by `whitelist_signer`.
+    pub fn load_verified(
+        instructions_sysvar: &AccountInfo,
+        whitelist_signer: &Pubkey,
+    ) -> Result<Self> {
+        let current_index = load_current_index_checked(instructions_sysvar)?;
+
+        require!(current_index > 0, WhitelistError::InvalidVoucher);
+
+        let instruction =
+            load_instruction_at_checked((current_index - 1).into(), instructions_sysvar)?;
+
+        require_keys_eq!(
+            instruction.program_id,
+            ed25519_program::ID,
+            WhitelistError::InvalidVoucher
+        );
+
+        let data = &instruction.data;
+
+        require!(
+            data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE && data[0] == 1,
+            WhitelistError::InvalidVoucher
+        );
+
+        let offset = |index: usize| {
+            let start = SIGNATURE_OFFSETS_START + index * 2;
+
+            u16::from_le_bytes([data[start], data[start + 1]])
+        };
+
+        let (signature_instruction, public_key_offset, public_key_instruction) =
+            (offset(1), offset(2) as usize, offset(3));
+        let (message_offset, message_size, message_instruction) =
+            (offset(4) as usize, offset(5) as usize, offset(6));
+
+        require!(
+            signature_instruction == CURRENT_INSTRUCTION
+                && public_key_instruction == CURRENT_INSTRUCTION
+                && message_instruction == CURRENT_INSTRUCTION,
+            WhitelistError::InvalidVoucher
+        );
+
+        let public_key = data
+            .get(public_key_offset..public_key_offset + 32)
+            .ok_or(WhitelistError::InvalidVoucher)?;
+
+        require!(
+            public_key.eq(whitelist_signer.as_ref()),
+            WhitelistError::InvalidVoucher
+        );
+
+        let message = data
+            .get(message_offset..message_offset + message_size)
+            .ok_or(WhitelistError::InvalidVoucher)?;
+
+        Ok(Self::from_message(message).ok_or(WhitelistError::InvalidVoucher)?)
+    }
+}
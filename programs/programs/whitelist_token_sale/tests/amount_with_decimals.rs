@@ -0,0 +1,91 @@
+mod common;
+
+use common::{Svm, TestSale, LAMPORTS_PER_SOL};
+use whitelist_token_sale_client::state::SaleMode;
+
+const LAMPORTS_PER_TOKEN: u64 = 10_000_000;
+
+#[test]
+fn vault_deposits_and_purchases_scale_with_decimals() {
+    for decimals in [0, 6, 9] {
+        let mut svm = Svm::new();
+        let sale = TestSale::create(
+            &mut svm,
+            "decimals",
+            SaleMode::Vault,
+            decimals,
+            LAMPORTS_PER_TOKEN,
+            5,
+            4,
+        )
+        .unwrap();
+        let unit = 10_u64.pow(decimals.into());
+
+        // Sale amounts are whole tokens, moved in base units
+        assert_eq!(svm.token_balance(&sale.vault()), 20 * unit);
+        assert_eq!(svm.token_balance(&sale.token_account(&sale.owner)), 0);
+
+        let buyer = sale.new_buyer(&mut svm);
+        sale.open_sale(&mut svm);
+
+        let sale_lamports = svm.lamports(&sale.address());
+        sale.buy(&mut svm, &buyer, 3).unwrap();
+
+        assert_eq!(svm.token_balance(&sale.token_account(&buyer)), 3 * unit);
+        assert_eq!(svm.token_balance(&sale.vault()), 17 * unit);
+
+        // The price is per whole token, whatever the decimals
+        assert_eq!(
+            svm.lamports(&sale.address()) - sale_lamports,
+            3 * LAMPORTS_PER_TOKEN
+        );
+
+        let state = svm.sale(&sale.name);
+        assert_eq!(state.tokens_sold, 3);
+        assert_eq!(state.lamports_raised, 3 * LAMPORTS_PER_TOKEN);
+        assert_eq!(svm.allowance(&sale.name, &buyer).tokens_bought, 3);
+    }
+}
+
+#[test]
+fn minted_purchases_scale_with_decimals() {
+    for decimals in [0, 6, 9] {
+        let mut svm = Svm::new();
+        let sale = TestSale::create(
+            &mut svm,
+            "decimals",
+            SaleMode::Mint,
+            decimals,
+            LAMPORTS_PER_TOKEN,
+            5,
+            4,
+        )
+        .unwrap();
+        let unit = 10_u64.pow(decimals.into());
+
+        let buyer = sale.new_buyer(&mut svm);
+        sale.open_sale(&mut svm);
+        sale.buy(&mut svm, &buyer, 2).unwrap();
+
+        assert_eq!(svm.token_balance(&sale.token_account(&buyer)), 2 * unit);
+        assert_eq!(svm.mint(&sale.token.mint).supply, 2 * unit);
+    }
+}
+
+#[test]
+fn deposits_overflowing_base_units_are_rejected() {
+    let mut svm = Svm::new();
+
+    // 2 * 10^10 whole tokens fit in a u64, but not once scaled by 10^9
+    let result = TestSale::create(
+        &mut svm,
+        "overflow",
+        SaleMode::Vault,
+        9,
+        LAMPORTS_PER_SOL,
+        10_000_000_000,
+        2,
+    );
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use anchor_lang::solana_program::program_option::COption;
+use common::{assert_error, Svm, TestSale};
+use whitelist_token_sale_client::{instructions, state::SaleMode, WhitelistError};
+
+const LAMPORTS_PER_TOKEN: u64 = 50_000_000;
+
+fn create_sale(svm: &mut Svm, mode: SaleMode) -> TestSale {
+    TestSale::create(svm, "closing", mode, 6, LAMPORTS_PER_TOKEN, 10, 3).unwrap()
+}
+
+#[test]
+fn closing_a_vault_sale_returns_unsold_tokens_and_proceeds() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm, SaleMode::Vault);
+
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+    sale.buy(&mut svm, &buyer, 4).unwrap();
+
+    let sale_lamports = svm.lamports(&sale.address());
+    let owner_lamports = svm.lamports(&sale.owner);
+    sale.close(&mut svm, &sale.owner).unwrap();
+
+    assert!(svm.account(&sale.address()).is_none());
+    assert_eq!(svm.token_balance(&sale.vault()), 0);
+    assert_eq!(
+        svm.token_balance(&sale.token_account(&sale.owner)),
+        26_000_000
+    );
+    assert_eq!(svm.token_balance(&sale.token_account(&buyer)), 4_000_000);
+
+    // Proceeds are swept along with the sale account's rent
+    assert!(sale_lamports > 4 * LAMPORTS_PER_TOKEN);
+    assert_eq!(svm.lamports(&sale.owner), owner_lamports + sale_lamports);
+}
+
+#[test]
+fn closing_an_untouched_vault_sale_returns_the_deposit() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm, SaleMode::Vault);
+
+    sale.close(&mut svm, &sale.owner).unwrap();
+
+    assert!(svm.account(&sale.address()).is_none());
+    assert_eq!(
+        svm.token_balance(&sale.token_account(&sale.owner)),
+        30_000_000
+    );
+}
+
+#[test]
+fn closing_a_mint_sale_hands_back_the_mint_authority() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm, SaleMode::Mint);
+    assert_eq!(
+        svm.mint(&sale.token.mint).mint_authority,
+        COption::Some(sale.address())
+    );
+
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+    sale.buy(&mut svm, &buyer, 7).unwrap();
+
+    let sale_lamports = svm.lamports(&sale.address());
+    let owner_lamports = svm.lamports(&sale.owner);
+    sale.close(&mut svm, &sale.owner).unwrap();
+
+    assert!(svm.account(&sale.address()).is_none());
+    assert_eq!(
+        svm.mint(&sale.token.mint).mint_authority,
+        COption::Some(sale.owner)
+    );
+    assert_eq!(svm.mint(&sale.token.mint).supply, 7_000_000);
+    assert_eq!(svm.lamports(&sale.owner), owner_lamports + sale_lamports);
+}
+
+#[test]
+fn sales_cant_be_closed_with_tokens_owed() {
+    for mode in [SaleMode::Vault, SaleMode::Mint] {
+        let mut svm = Svm::new();
+        let sale = create_sale(&mut svm, mode);
+
+        // Purchases are held back until the soft cap is settled
+        svm.process(
+            instructions::set_soft_cap(
+                &sale.owner,
+                &sale.name,
+                LAMPORTS_PER_TOKEN,
+                svm.now() + 3_600,
+            ),
+            &[&sale.owner],
+        )
+        .unwrap();
+
+        let buyer = sale.new_buyer(&mut svm);
+        sale.open_sale(&mut svm);
+        sale.buy(&mut svm, &buyer, 2).unwrap();
+
+        assert_error(
+            sale.close(&mut svm, &sale.owner),
+            WhitelistError::ClaimsOutstanding,
+        );
+        assert_eq!(svm.sale(&sale.name).tokens_owed, 2_000_000);
+    }
+}
+
+#[test]
+fn vault_sales_cant_be_closed_without_their_vault() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm, SaleMode::Vault);
+
+    // Accounts of a mint sale leave out the vault
+    assert_error(
+        svm.process(
+            instructions::close_whitelist_sale(
+                &sale.owner,
+                &sale.name,
+                &sale.token,
+                None,
+                SaleMode::Mint,
+            ),
+            &[&sale.owner],
+        ),
+        WhitelistError::MissingVaultAccounts,
+    );
+}
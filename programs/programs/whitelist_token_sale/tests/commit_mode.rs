@@ -0,0 +1,158 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::{assert_error, Svm, TestSale};
+use whitelist_token_sale_client::{
+    events::{CommitmentSettled, SaleFinalized},
+    instructions, WhitelistError,
+};
+
+const LAMPORTS_PER_TOKEN: u64 = 100_000_000;
+
+fn create_sale(svm: &mut Svm) -> TestSale {
+    let sale = TestSale::create_referral_sale(svm, "commit-mode");
+
+    svm.process(
+        instructions::set_commit_mode(&sale.owner, &sale.name, true),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    sale
+}
+
+fn commit(svm: &mut Svm, sale: &TestSale, buyer: &Pubkey, lamports: u64) {
+    svm.process(
+        instructions::commit(buyer, &sale.name, None, lamports),
+        &[buyer],
+    )
+    .unwrap();
+}
+
+#[test]
+fn anyone_can_finalize_once_the_commit_window_has_ended() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm);
+
+    let sale_end = svm.now() + 3_600;
+    svm.process(
+        instructions::set_sale_windows(&sale.owner, &sale.name, None, None, None, Some(sale_end)),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+    commit(&mut svm, &sale, &buyer, 3 * LAMPORTS_PER_TOKEN);
+
+    let stranger = svm.funded_wallet();
+    let finalize = |svm: &mut Svm| {
+        svm.process(
+            instructions::finalize_sale(&stranger, &sale.name, None, None),
+            &[&stranger],
+        )
+    };
+
+    // While commitments can still come in, finalizing is up to the owner
+    assert_error(finalize(&mut svm), WhitelistError::OnlyOwner);
+
+    svm.warp_to(sale_end);
+    assert_error(
+        svm.process(
+            instructions::commit(&buyer, &sale.name, None, LAMPORTS_PER_TOKEN),
+            &[&buyer],
+        ),
+        WhitelistError::SaleClosed,
+    );
+
+    finalize(&mut svm).unwrap();
+    assert!(svm.sale(&sale.name).is_finalized);
+
+    let finalized = svm.event::<SaleFinalized>();
+    assert_eq!(finalized.actor, stranger);
+    assert_eq!(finalized.total_committed, 3 * LAMPORTS_PER_TOKEN);
+
+    svm.process(
+        instructions::settle_commitment(&buyer, &sale.name, &sale.token.mint, None, None, None),
+        &[&buyer],
+    )
+    .unwrap();
+    svm.process(
+        instructions::claim_vested(&buyer, &sale.name, &sale.token, sale.mode),
+        &[&buyer],
+    )
+    .unwrap();
+    assert_eq!(svm.token_balance(&sale.token_account(&buyer)), 3_000_000);
+}
+
+#[test]
+fn settled_commitments_reward_the_referrer() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm);
+
+    // The referrer commits too, without having been referred
+    let referrer = sale.new_buyer(&mut svm);
+    let buyer = svm.funded_wallet();
+    sale.register_referred(&mut svm, &buyer, Some(&referrer))
+        .unwrap();
+
+    // 15 tokens committed for the 30 on sale, so both buyers get what they committed to, and the
+    // referred buyer's excess is refunded
+    sale.open_sale(&mut svm);
+    commit(&mut svm, &sale, &buyer, 4 * LAMPORTS_PER_TOKEN + 50_000_000);
+    commit(&mut svm, &sale, &referrer, 10 * LAMPORTS_PER_TOKEN);
+    svm.process(
+        instructions::finalize_sale(&sale.owner, &sale.name, None, None),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    let settle = |buyer, referrer| {
+        instructions::settle_commitment(buyer, &sale.name, &sale.token.mint, None, None, referrer)
+    };
+    assert_error(
+        svm.process(settle(&buyer, None), &[&buyer]),
+        WhitelistError::ReferrerMismatch,
+    );
+
+    let buyer_lamports = svm.lamports(&buyer);
+    svm.process(settle(&buyer, Some(&referrer)), &[&buyer])
+        .unwrap();
+    assert_eq!(svm.lamports(&buyer), buyer_lamports + 50_000_000);
+
+    // 5% of the 0.4 SOL the 4 allocated tokens cost
+    let reward = 20_000_000;
+    let settled = svm.event::<CommitmentSettled>();
+    assert_eq!(settled.tokens, 4);
+    assert_eq!(settled.referral_reward, reward);
+    assert_eq!(
+        svm.referrer_stats(&sale.name, &referrer).rewards_earned,
+        reward
+    );
+
+    svm.process(settle(&referrer, None), &[&referrer]).unwrap();
+    assert_eq!(svm.event::<CommitmentSettled>().referral_reward, 0);
+
+    let state = svm.sale(&sale.name);
+    assert_eq!(state.referral_rewards_owed, reward);
+    assert_eq!(state.lamports_raised, 14 * LAMPORTS_PER_TOKEN);
+
+    // The reward stays with the sale when the owner withdraws
+    svm.process(
+        instructions::withdraw_proceeds(&sale.owner, &sale.name, &sale.owner, None),
+        &[&sale.owner],
+    )
+    .unwrap();
+    assert_eq!(
+        svm.sale(&sale.name).lamports_withdrawn,
+        14 * LAMPORTS_PER_TOKEN - reward
+    );
+
+    let referrer_lamports = svm.lamports(&referrer);
+    svm.process(
+        instructions::claim_referral_rewards(&referrer, &sale.name, None),
+        &[&referrer],
+    )
+    .unwrap();
+    assert_eq!(svm.lamports(&referrer), referrer_lamports + reward);
+}
@@ -0,0 +1,1030 @@
+//! In-process runtime for the integration tests.
+//!
+//! Instructions run natively, against account buffers laid out the way the BPF loader serializes
+//! them, so reallocations and owner changes behave as they do on chain. CPIs are routed through
+//! the syscall stubs to the native SPL token, Token-2022, associated token and system programs, or
+//! back into the sale program for its event CPIs. Transactions verify their Ed25519 precompile
+//! instructions and expose themselves through the instructions sysvar, as vouchers need.
+//!
+//! Like the runtime, account changes are checked after every instruction and around every CPI:
+//! read-only accounts can't change, only an account's owner can change its data or owner or
+//! debit it, and accounts have to stay rent-exempt. It isn't the BPF runtime, though, so compute
+//! limits, stack depth and program-specific memory behaviour aren't covered.
+//!
+//! solana-program-test would run the real runtime, but the only release available to the build,
+//! 1.18.0, pins every Solana crate to `=1.18.0`, while anchor-spl 0.30.1 needs 1.18.11 or later.
+
+#![allow(dead_code)]
+
+use anchor_lang::{
+    event::EVENT_IX_TAG_LE,
+    prelude::*,
+    solana_program::{
+        bpf_loader, bpf_loader_upgradeable,
+        entrypoint::{deserialize, ProgramResult, MAX_PERMITTED_DATA_INCREASE, SUCCESS},
+        instruction::Instruction,
+        program_option::COption,
+        program_pack::Pack,
+        program_stubs::{set_syscall_stubs, SyscallStubs},
+        system_program, sysvar,
+        sysvar::instructions::{
+            construct_instructions_data, store_current_index, BorrowedAccountMeta,
+            BorrowedInstruction,
+        },
+    },
+    Event,
+};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address_with_program_id, spl_associated_token_account,
+    },
+    token::spl_token,
+    token_2022::spl_token_2022,
+};
+use solana_sdk::{ed25519_instruction, ed25519_program, feature_set::FeatureSet};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    sync::Once,
+};
+use whitelist_token_sale_client::{
+    accounts::{deserialize_allowance, deserialize_referrer_stats, deserialize_sale},
+    instructions::{self, TokenMint},
+    pda::{
+        find_allowance_address, find_referrer_stats_address, find_sale_address,
+        get_program_data_address, get_vault_address,
+    },
+    state::{Allowance, ReferrerStats, SaleMode, WhitelistSale},
+    WhitelistError, ID,
+};
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// 5% of referred buyers' payments, as paid by `TestSale::create_referral_sale`
+pub const REFERRAL_BPS: u16 = 500;
+
+type Processor = for<'a> fn(&Pubkey, &'a [AccountInfo<'a>], &[u8]) -> ProgramResult;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Account {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+}
+
+thread_local! {
+    /// Programs currently executing, innermost last
+    static PROGRAM_STACK: RefCell<Vec<Pubkey>> = const { RefCell::new(Vec::new()) };
+    static RETURN_DATA: RefCell<Option<(Pubkey, Vec<u8>)>> = const { RefCell::new(None) };
+    static CLOCK: RefCell<Clock> = RefCell::new(Clock::default());
+    /// Accounts as each executing program last saw them, innermost last
+    static PRE_ACCOUNTS: RefCell<Vec<BTreeMap<Pubkey, Account>>> = const { RefCell::new(Vec::new()) };
+    /// Events the sale program emitted through self-CPIs during the transaction
+    static EVENTS: RefCell<Vec<Vec<u8>>> = const { RefCell::new(Vec::new()) };
+}
+
+fn processor(program_id: &Pubkey) -> Option<Processor> {
+    if *program_id == ID {
+        Some(whitelist_token_sale::entry)
+    } else if *program_id == spl_token::ID {
+        Some(spl_token::processor::Processor::process)
+    } else if *program_id == spl_token_2022::ID {
+        Some(spl_token_2022::processor::Processor::process)
+    } else if *program_id == spl_associated_token_account::ID {
+        Some(spl_associated_token_account::processor::process_instruction)
+    } else if *program_id == system_program::ID {
+        Some(process_system_instruction)
+    } else {
+        None
+    }
+}
+
+/// Serializes the accounts of an instruction the way the BPF loader does
+fn serialize(
+    program_id: &Pubkey,
+    metas: &[AccountMeta],
+    accounts: &BTreeMap<Pubkey, Account>,
+    data: &[u8],
+) -> Vec<u64> {
+    let mut buffer: Vec<u8> = Vec::new();
+    buffer.extend_from_slice(&(metas.len() as u64).to_le_bytes());
+
+    for (index, meta) in metas.iter().enumerate() {
+        if let Some(first) = metas[..index].iter().position(|m| m.pubkey == meta.pubkey) {
+            buffer.push(first as u8);
+            buffer.extend_from_slice(&[0; 7]);
+            continue;
+        }
+
+        // Privileges are merged across duplicates of the account
+        let (is_signer, is_writable) = metas
+            .iter()
+            .filter(|m| m.pubkey == meta.pubkey)
+            .fold((false, false), |(s, w), m| {
+                (s || m.is_signer, w || m.is_writable)
+            });
+        let account = &accounts[&meta.pubkey];
+
+        buffer.push(u8::MAX);
+        buffer.push(is_signer.into());
+        buffer.push(is_writable.into());
+        buffer.push(account.executable.into());
+        buffer.extend_from_slice(&[0; 4]);
+        buffer.extend_from_slice(meta.pubkey.as_ref());
+        buffer.extend_from_slice(account.owner.as_ref());
+        buffer.extend_from_slice(&account.lamports.to_le_bytes());
+        buffer.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+        buffer.extend_from_slice(&account.data);
+        buffer.resize(buffer.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+        buffer.resize(buffer.len().next_multiple_of(8), 0);
+        buffer.extend_from_slice(&u64::MAX.to_le_bytes());
+    }
+
+    buffer.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    buffer.extend_from_slice(data);
+    buffer.extend_from_slice(program_id.as_ref());
+
+    // Backed by u64s so account fields are aligned
+    let mut aligned = vec![0u64; buffer.len().div_ceil(8)];
+    for (word, chunk) in aligned.iter_mut().zip(buffer.chunks(8)) {
+        let mut bytes = [0; 8];
+        bytes[..chunk.len()].copy_from_slice(chunk);
+        *word = u64::from_le_bytes(bytes);
+    }
+
+    aligned
+}
+
+/// Checks the changes `program_id` made to an account the way the runtime does
+fn verify(program_id: &Pubkey, pre: &Account, info: &AccountInfo) -> ProgramResult {
+    let data = info.data.borrow();
+    let is_owner = pre.owner == *program_id;
+
+    if info.lamports() != pre.lamports && !info.is_writable {
+        return Err(ProgramError::InvalidArgument);
+    }
+    if info.lamports() < pre.lamports && !is_owner {
+        return Err(ProgramError::IllegalOwner);
+    }
+    if data[..] != pre.data[..] {
+        if !info.is_writable {
+            return Err(ProgramError::InvalidArgument);
+        }
+        if !is_owner {
+            return Err(ProgramError::IllegalOwner);
+        }
+    }
+    // Ownership can only be handed over by the owner, with the data cleared
+    if *info.owner != pre.owner
+        && (!info.is_writable || !is_owner || data.iter().any(|byte| *byte != 0))
+    {
+        return Err(ProgramError::IllegalOwner);
+    }
+    if info.executable != pre.executable {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(())
+}
+
+/// Runs an instruction against `accounts`, which are only updated if it succeeds
+fn execute(
+    program_id: &Pubkey,
+    metas: &[AccountMeta],
+    accounts: &mut BTreeMap<Pubkey, Account>,
+    data: &[u8],
+) -> ProgramResult {
+    let process = processor(program_id).ok_or(ProgramError::IncorrectProgramId)?;
+    let mut buffer = serialize(program_id, metas, accounts, data);
+
+    PROGRAM_STACK.with_borrow_mut(|stack| stack.push(*program_id));
+    PRE_ACCOUNTS.with_borrow_mut(|stack| stack.push(accounts.clone()));
+    RETURN_DATA.set(None);
+
+    // The account infos point into the buffer, which outlives them
+    let (_, infos, instruction_data) = unsafe { deserialize(buffer.as_mut_ptr() as *mut u8) };
+    let result = process(program_id, &infos, instruction_data);
+
+    PROGRAM_STACK.with_borrow_mut(|stack| stack.pop());
+    let pre_accounts = PRE_ACCOUNTS.with_borrow_mut(|stack| stack.pop().unwrap());
+    result?;
+
+    for info in &infos {
+        verify(program_id, &pre_accounts[info.key], info)?;
+    }
+
+    for info in &infos {
+        accounts.insert(
+            *info.key,
+            Account {
+                lamports: info.lamports(),
+                data: info.data.borrow().to_vec(),
+                owner: *info.owner,
+                executable: info.executable,
+            },
+        );
+    }
+
+    Ok(())
+}
+
+fn process_system_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let read_u64 = |offset: usize| -> std::result::Result<u64, ProgramError> {
+        data.get(offset..offset + 8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+            .ok_or(ProgramError::InvalidInstructionData)
+    };
+    let read_pubkey = |offset: usize| -> std::result::Result<Pubkey, ProgramError> {
+        data.get(offset..offset + 32)
+            .map(|bytes| Pubkey::try_from(bytes).unwrap())
+            .ok_or(ProgramError::InvalidInstructionData)
+    };
+    let signed = |info: &AccountInfo| -> ProgramResult {
+        match info.is_signer {
+            true => Ok(()),
+            false => Err(ProgramError::MissingRequiredSignature),
+        }
+    };
+    // Accounts can only be allocated or assigned while they're unused system accounts
+    let unused = |info: &AccountInfo| -> ProgramResult {
+        if info.owner != &system_program::ID || !info.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        Ok(())
+    };
+    let transfer = |from: &AccountInfo, to: &AccountInfo, lamports: u64| -> ProgramResult {
+        signed(from)?;
+        if from.owner != &system_program::ID || !from.data_is_empty() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let balance = from.lamports();
+        if balance < lamports {
+            return Err(ProgramError::InsufficientFunds);
+        }
+        **from.try_borrow_mut_lamports()? = balance - lamports;
+        **to.try_borrow_mut_lamports()? += lamports;
+        Ok(())
+    };
+
+    let tag = u32::from_le_bytes(
+        data.get(..4)
+            .ok_or(ProgramError::InvalidInstructionData)?
+            .try_into()
+            .unwrap(),
+    );
+    let account = |index: usize| {
+        accounts
+            .get(index)
+            .ok_or(ProgramError::NotEnoughAccountKeys)
+    };
+
+    match tag {
+        // CreateAccount
+        0 => {
+            let (from, to) = (account(0)?, account(1)?);
+            signed(to)?;
+            unused(to)?;
+            if to.lamports() > 0 {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            transfer(from, to, read_u64(4)?)?;
+            to.realloc(read_u64(12)? as usize, true)?;
+            to.assign(&read_pubkey(20)?);
+        }
+        // Assign
+        1 => {
+            let to = account(0)?;
+            let owner = read_pubkey(4)?;
+            if to.owner != &owner {
+                signed(to)?;
+                unused(to)?;
+                to.assign(&owner);
+            }
+        }
+        // Transfer
+        2 => transfer(account(0)?, account(1)?, read_u64(4)?)?,
+        // Allocate
+        8 => {
+            let to = account(0)?;
+            signed(to)?;
+            unused(to)?;
+            to.realloc(read_u64(4)? as usize, true)?;
+        }
+        _ => return Err(ProgramError::InvalidInstructionData),
+    }
+
+    Ok(())
+}
+
+/// Instructions sysvar as the runtime serializes it while the transaction's `index`th instruction runs
+fn instructions_sysvar(instructions: &[Instruction], index: usize) -> Account {
+    let borrowed: Vec<BorrowedInstruction> = instructions
+        .iter()
+        .map(|instruction| BorrowedInstruction {
+            program_id: &instruction.program_id,
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|meta| BorrowedAccountMeta {
+                    pubkey: &meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: &instruction.data,
+        })
+        .collect();
+
+    let mut data = construct_instructions_data(&borrowed);
+    store_current_index(&mut data, index as u16);
+
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: sysvar::ID,
+        executable: false,
+    }
+}
+
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_log(&self, _message: &str) {}
+
+    fn sol_log_data(&self, _fields: &[&[u8]]) {}
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let caller = PROGRAM_STACK.with_borrow(|stack| *stack.last().unwrap());
+        let pda_signers = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &caller))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|_| ProgramError::InvalidSeeds)?;
+
+        let mut accounts = BTreeMap::new();
+        for meta in &instruction.accounts {
+            let info = account_infos
+                .iter()
+                .find(|info| info.key == &meta.pubkey)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+            if meta.is_signer && !info.is_signer && !pda_signers.contains(info.key) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if meta.is_writable && !info.is_writable {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            // The caller's own changes are checked before the callee sees them
+            PRE_ACCOUNTS
+                .with_borrow(|stack| verify(&caller, &stack.last().unwrap()[info.key], info))?;
+
+            accounts.insert(
+                *info.key,
+                Account {
+                    lamports: info.lamports(),
+                    data: info.data.borrow().to_vec(),
+                    owner: *info.owner,
+                    executable: info.executable,
+                },
+            );
+        }
+
+        execute(
+            &instruction.program_id,
+            &instruction.accounts,
+            &mut accounts,
+            &instruction.data,
+        )?;
+
+        if instruction.program_id == ID {
+            if let Some(event) = instruction.data.strip_prefix(&EVENT_IX_TAG_LE) {
+                EVENTS.with_borrow_mut(|events| events.push(event.to_vec()));
+            }
+        }
+
+        for info in account_infos {
+            let Some(account) = accounts.get(info.key) else {
+                continue;
+            };
+
+            **info.try_borrow_mut_lamports()? = account.lamports;
+            info.realloc(account.data.len(), false)?;
+            info.try_borrow_mut_data()?.copy_from_slice(&account.data);
+            if info.owner != &account.owner {
+                info.assign(&account.owner);
+            }
+
+            // The caller is only answerable for what it changes after the call
+            PRE_ACCOUNTS.with_borrow_mut(|stack| {
+                stack.last_mut().unwrap().insert(*info.key, account.clone())
+            });
+        }
+
+        Ok(())
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Clock) = CLOCK.with_borrow(Clock::clone) };
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        RETURN_DATA.with_borrow(Clone::clone)
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        let program_id = PROGRAM_STACK.with_borrow(|stack| *stack.last().unwrap());
+        RETURN_DATA.set(Some((program_id, data.to_vec())));
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        PROGRAM_STACK.with_borrow(|stack| stack.len() as u64)
+    }
+}
+
+/// A bank holding every account, running one instruction at a time
+pub struct Svm {
+    accounts: HashMap<Pubkey, Account>,
+    clock: Clock,
+    /// Events emitted by the last successful transaction
+    events: Vec<Vec<u8>>,
+}
+
+impl Svm {
+    pub fn new() -> Self {
+        static STUBS: Once = Once::new();
+        STUBS.call_once(|| {
+            set_syscall_stubs(Box::new(Stubs));
+        });
+
+        let mut svm = Self {
+            accounts: HashMap::new(),
+            clock: Clock {
+                unix_timestamp: 1_700_000_000,
+                ..Clock::default()
+            },
+            events: vec![],
+        };
+
+        for program_id in [
+            ID,
+            spl_token::ID,
+            spl_token_2022::ID,
+            spl_associated_token_account::ID,
+            system_program::ID,
+        ] {
+            svm.set_account(
+                program_id,
+                Account {
+                    lamports: 1,
+                    data: vec![],
+                    owner: bpf_loader::ID,
+                    executable: true,
+                },
+            );
+        }
+
+        svm
+    }
+
+    /// Redeploys the program as upgradeable, with `authority` as its upgrade authority
+    pub fn set_upgrade_authority(&mut self, authority: &Pubkey) {
+        let program_data = get_program_data_address();
+
+        // Bincode layouts of `UpgradeableLoaderState::Program` and `UpgradeableLoaderState::ProgramData`
+        let mut program = 2_u32.to_le_bytes().to_vec();
+        program.extend_from_slice(program_data.as_ref());
+
+        let mut data = 3_u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&0_u64.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(authority.as_ref());
+
+        self.set_account(
+            ID,
+            Account {
+                lamports: 1,
+                data: program,
+                owner: bpf_loader_upgradeable::ID,
+                executable: true,
+            },
+        );
+        self.set_account(
+            program_data,
+            Account {
+                lamports: 1,
+                data,
+                owner: bpf_loader_upgradeable::ID,
+                executable: false,
+            },
+        );
+    }
+
+    pub fn account(&self, address: &Pubkey) -> Option<&Account> {
+        self.accounts.get(address)
+    }
+
+    pub fn set_account(&mut self, address: Pubkey, account: Account) {
+        self.accounts.insert(address, account);
+    }
+
+    pub fn lamports(&self, address: &Pubkey) -> u64 {
+        self.account(address).map_or(0, |account| account.lamports)
+    }
+
+    pub fn total_lamports(&self) -> u64 {
+        self.accounts.values().map(|account| account.lamports).sum()
+    }
+
+    pub fn airdrop(&mut self, address: &Pubkey, lamports: u64) {
+        self.accounts.entry(*address).or_default().lamports += lamports;
+    }
+
+    /// A new wallet holding 10 SOL
+    pub fn funded_wallet(&mut self) -> Pubkey {
+        let wallet = Pubkey::new_unique();
+        self.airdrop(&wallet, 10 * LAMPORTS_PER_SOL);
+
+        wallet
+    }
+
+    pub fn now(&self) -> i64 {
+        self.clock.unix_timestamp
+    }
+
+    pub fn warp_to(&mut self, unix_timestamp: i64) {
+        self.clock.unix_timestamp = unix_timestamp;
+        self.clock.slot += 1;
+    }
+
+    /// Processes an instruction signed by `signers`, leaving the accounts untouched if it fails
+    pub fn process(&mut self, instruction: Instruction, signers: &[&Pubkey]) -> ProgramResult {
+        self.process_transaction(&[instruction], signers)
+    }
+
+    /// Processes instructions as one transaction signed by `signers`, leaving the accounts untouched
+    /// if any of them fails
+    pub fn process_transaction(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Pubkey],
+    ) -> ProgramResult {
+        if let Some(meta) = instructions
+            .iter()
+            .flat_map(|instruction| &instruction.accounts)
+            .find(|meta| meta.is_signer && !signers.contains(&&meta.pubkey))
+        {
+            panic!("{} has to sign the transaction", meta.pubkey);
+        }
+
+        // Accounts as the transaction left them so far
+        let mut changed: BTreeMap<Pubkey, Account> = BTreeMap::new();
+        CLOCK.set(self.clock.clone());
+        EVENTS.set(vec![]);
+
+        for (index, instruction) in instructions.iter().enumerate() {
+            // Precompiles verify their signatures before any program runs
+            if instruction.program_id == ed25519_program::ID {
+                let datas: Vec<&[u8]> = instructions.iter().map(|ix| &ix.data[..]).collect();
+                ed25519_instruction::verify(&instruction.data, &datas, &FeatureSet::all_enabled())
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                continue;
+            }
+
+            let mut accounts: BTreeMap<Pubkey, Account> = instruction
+                .accounts
+                .iter()
+                .map(|meta| {
+                    let account = if meta.pubkey == sysvar::instructions::ID {
+                        instructions_sysvar(instructions, index)
+                    } else {
+                        changed
+                            .get(&meta.pubkey)
+                            .or_else(|| self.account(&meta.pubkey))
+                            .cloned()
+                            .unwrap_or_default()
+                    };
+                    (meta.pubkey, account)
+                })
+                .collect();
+            let lamports_before: u64 = accounts.values().map(|account| account.lamports).sum();
+
+            execute(
+                &instruction.program_id,
+                &instruction.accounts,
+                &mut accounts,
+                &instruction.data,
+            )?;
+
+            let lamports_after: u64 = accounts.values().map(|account| account.lamports).sum();
+            assert_eq!(
+                lamports_before, lamports_after,
+                "Lamports weren't conserved"
+            );
+
+            accounts.remove(&sysvar::instructions::ID);
+            changed.extend(accounts);
+        }
+
+        for (address, account) in &changed {
+            let rent_exempt = Rent::default().minimum_balance(account.data.len());
+            if account.lamports > 0
+                && account.lamports < rent_exempt
+                && self.account(address) != Some(account)
+            {
+                return Err(ProgramError::AccountNotRentExempt);
+            }
+        }
+
+        for (address, account) in changed {
+            // Like the runtime, drop accounts left without lamports
+            if account.lamports == 0 {
+                self.accounts.remove(&address);
+            } else {
+                self.accounts.insert(address, account);
+            }
+        }
+        self.events = EVENTS.take();
+
+        Ok(())
+    }
+
+    /// Events of type `E` emitted by the last successful transaction
+    pub fn events<E: Event>(&self) -> Vec<E> {
+        self.events
+            .iter()
+            .filter_map(|data| data.strip_prefix(&E::DISCRIMINATOR))
+            .map(|mut data| E::deserialize(&mut data).unwrap())
+            .collect()
+    }
+
+    /// The one event of type `E` emitted by the last successful transaction
+    pub fn event<E: Event>(&self) -> E {
+        let mut events = self.events::<E>();
+        assert_eq!(events.len(), 1, "Expected exactly one event");
+        events.pop().unwrap()
+    }
+
+    pub fn create_mint(&mut self, authority: &Pubkey, decimals: u8) -> TokenMint {
+        self.create_mint_of(&spl_token::ID, authority, decimals)
+    }
+
+    /// Creates a mint owned by `token_program`, either SPL token or Token-2022, without extensions
+    pub fn create_mint_of(
+        &mut self,
+        token_program: &Pubkey,
+        authority: &Pubkey,
+        decimals: u8,
+    ) -> TokenMint {
+        let mint = Pubkey::new_unique();
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            mint_authority: COption::Some(*authority),
+            supply: 0,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        }
+        .pack_into_slice(&mut data);
+
+        self.set_account(
+            mint,
+            Account {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: *token_program,
+                executable: false,
+            },
+        );
+
+        TokenMint {
+            mint,
+            token_program: *token_program,
+        }
+    }
+
+    pub fn mint(&self, mint: &Pubkey) -> spl_token::state::Mint {
+        let account = self.account(mint).expect("Mint doesn't exist");
+        spl_token::state::Mint::unpack(&account.data[..spl_token::state::Mint::LEN]).unwrap()
+    }
+
+    /// Funds `owner`'s associated token account out of thin air, bumping the mint's supply along
+    pub fn mint_tokens(&mut self, token: &TokenMint, owner: &Pubkey, amount: u64) -> Pubkey {
+        let address =
+            get_associated_token_address_with_program_id(owner, &token.mint, &token.token_program);
+        let balance = self.token_balance(&address);
+
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint: token.mint,
+            owner: *owner,
+            amount: balance + amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+
+        self.set_account(
+            address,
+            Account {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: token.token_program,
+                executable: false,
+            },
+        );
+
+        let mint_account = self.accounts.get_mut(&token.mint).unwrap();
+        let base = &mut mint_account.data[..spl_token::state::Mint::LEN];
+        let mut mint = spl_token::state::Mint::unpack(base).unwrap();
+        mint.supply += amount;
+        mint.pack_into_slice(base);
+
+        address
+    }
+
+    /// Balance of a token account, zero if it doesn't exist
+    pub fn token_balance(&self, address: &Pubkey) -> u64 {
+        // Token-2022 accounts carry their extensions after the base state
+        self.account(address).map_or(0, |account| {
+            spl_token::state::Account::unpack(&account.data[..spl_token::state::Account::LEN])
+                .unwrap()
+                .amount
+        })
+    }
+
+    pub fn sale(&self, sale_name: &str) -> WhitelistSale {
+        let account = self
+            .account(&find_sale_address(sale_name).0)
+            .expect("Sale doesn't exist");
+        deserialize_sale(&account.data).unwrap()
+    }
+
+    pub fn allowance(&self, sale_name: &str, buyer: &Pubkey) -> Allowance {
+        let account = self
+            .account(&find_allowance_address(sale_name, buyer).0)
+            .expect("Allowance doesn't exist");
+        deserialize_allowance(&account.data).unwrap()
+    }
+
+    pub fn referrer_stats(&self, sale_name: &str, referrer: &Pubkey) -> ReferrerStats {
+        let account = self
+            .account(&find_referrer_stats_address(sale_name, referrer).0)
+            .expect("Referrer stats don't exist");
+        deserialize_referrer_stats(&account.data).unwrap()
+    }
+}
+
+/// A sale created by a funded owner, with its registration open
+pub struct TestSale {
+    pub name: String,
+    pub owner: Pubkey,
+    pub token: TokenMint,
+    pub mode: SaleMode,
+    pub lamports_per_token: u64,
+    /// Set when the sale was created with a platform fee
+    pub fee_recipient: Option<Pubkey>,
+}
+
+impl TestSale {
+    pub fn create(
+        svm: &mut Svm,
+        name: &str,
+        mode: SaleMode,
+        decimals: u8,
+        lamports_per_token: u64,
+        max_tokens_per_buyer: u64,
+        max_buyers: u64,
+    ) -> std::result::Result<Self, ProgramError> {
+        Self::create_with_referrals(
+            svm,
+            name,
+            mode,
+            decimals,
+            lamports_per_token,
+            max_tokens_per_buyer,
+            max_buyers,
+            0,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_with_referrals(
+        svm: &mut Svm,
+        name: &str,
+        mode: SaleMode,
+        decimals: u8,
+        lamports_per_token: u64,
+        max_tokens_per_buyer: u64,
+        max_buyers: u64,
+        referral_bps: u16,
+    ) -> std::result::Result<Self, ProgramError> {
+        Self::create_of(
+            svm,
+            &spl_token::ID,
+            name,
+            mode,
+            decimals,
+            lamports_per_token,
+            max_tokens_per_buyer,
+            max_buyers,
+            referral_bps,
+        )
+    }
+
+    /// Vault sale of up to 10 tokens each for 3 buyers at 0.1 SOL, paying referrers `REFERRAL_BPS`
+    pub fn create_referral_sale(svm: &mut Svm, name: &str) -> Self {
+        Self::create_with_referrals(
+            svm,
+            name,
+            SaleMode::Vault,
+            6,
+            100_000_000,
+            10,
+            3,
+            REFERRAL_BPS,
+        )
+        .unwrap()
+    }
+
+    /// Sale of a Token-2022 mint
+    pub fn create_token_2022(
+        svm: &mut Svm,
+        name: &str,
+        mode: SaleMode,
+        decimals: u8,
+        lamports_per_token: u64,
+        max_tokens_per_buyer: u64,
+        max_buyers: u64,
+    ) -> std::result::Result<Self, ProgramError> {
+        Self::create_of(
+            svm,
+            &spl_token_2022::ID,
+            name,
+            mode,
+            decimals,
+            lamports_per_token,
+            max_tokens_per_buyer,
+            max_buyers,
+            0,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_of(
+        svm: &mut Svm,
+        token_program: &Pubkey,
+        name: &str,
+        mode: SaleMode,
+        decimals: u8,
+        lamports_per_token: u64,
+        max_tokens_per_buyer: u64,
+        max_buyers: u64,
+        referral_bps: u16,
+    ) -> std::result::Result<Self, ProgramError> {
+        let owner = Pubkey::new_unique();
+        svm.airdrop(&owner, 10 * LAMPORTS_PER_SOL);
+
+        let token = svm.create_mint_of(token_program, &owner, decimals);
+        if mode == SaleMode::Vault {
+            let deposit = max_tokens_per_buyer
+                .saturating_mul(max_buyers)
+                .saturating_mul(10_u64.saturating_pow(decimals.into()));
+            svm.mint_tokens(&token, &owner, deposit);
+        }
+
+        svm.process(
+            instructions::create_whitelist_sale(
+                &owner,
+                name,
+                &token,
+                None,
+                lamports_per_token,
+                max_tokens_per_buyer,
+                max_buyers,
+                mode,
+                referral_bps,
+            ),
+            &[&owner],
+        )?;
+
+        let sale = svm.sale(name);
+
+        Ok(Self {
+            name: name.to_string(),
+            owner,
+            token,
+            mode,
+            lamports_per_token,
+            fee_recipient: (sale.fee_bps > 0).then_some(sale.fee_recipient),
+        })
+    }
+
+    pub fn address(&self) -> Pubkey {
+        find_sale_address(&self.name).0
+    }
+
+    pub fn vault(&self) -> Pubkey {
+        get_vault_address(&self.address(), &self.token.mint, &self.token.token_program)
+    }
+
+    pub fn token_account(&self, wallet: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(
+            wallet,
+            &self.token.mint,
+            &self.token.token_program,
+        )
+    }
+
+    pub fn update_state(
+        &self,
+        svm: &mut Svm,
+        signer: &Pubkey,
+        is_registration_open: bool,
+        is_sale_open: bool,
+    ) -> ProgramResult {
+        svm.process(
+            instructions::update_sale_state(signer, &self.name, is_registration_open, is_sale_open),
+            &[signer],
+        )
+    }
+
+    /// Closes the registration and opens the sale
+    pub fn open_sale(&self, svm: &mut Svm) {
+        self.update_state(svm, &self.owner, false, true).unwrap();
+    }
+
+    /// A funded buyer, registered on the whitelist
+    pub fn new_buyer(&self, svm: &mut Svm) -> Pubkey {
+        let buyer = svm.funded_wallet();
+        self.register(svm, &buyer).unwrap();
+
+        buyer
+    }
+
+    pub fn register(&self, svm: &mut Svm, buyer: &Pubkey) -> ProgramResult {
+        self.register_referred(svm, buyer, None)
+    }
+
+    pub fn register_referred(
+        &self,
+        svm: &mut Svm,
+        buyer: &Pubkey,
+        referrer: Option<&Pubkey>,
+    ) -> ProgramResult {
+        svm.process(
+            instructions::register_for_whitelist(buyer, &self.name, referrer),
+            &[buyer],
+        )
+    }
+
+    /// Referrer recorded on the buyer's allowance, if they're registered with one
+    pub fn referrer(&self, svm: &Svm, buyer: &Pubkey) -> Option<Pubkey> {
+        svm.account(&find_allowance_address(&self.name, buyer).0)?;
+
+        svm.allowance(&self.name, buyer).referrer
+    }
+
+    pub fn buy(&self, svm: &mut Svm, buyer: &Pubkey, amount: u64) -> ProgramResult {
+        svm.process(
+            instructions::buy_tokens(
+                buyer,
+                &self.name,
+                &self.token,
+                None,
+                self.fee_recipient.as_ref(),
+                self.referrer(svm, buyer).as_ref(),
+                self.mode,
+                amount,
+            ),
+            &[buyer],
+        )
+    }
+
+    pub fn close(&self, svm: &mut Svm, signer: &Pubkey) -> ProgramResult {
+        svm.process(
+            instructions::close_whitelist_sale(signer, &self.name, &self.token, None, self.mode),
+            &[signer],
+        )
+    }
+}
+
+pub fn assert_error(result: ProgramResult, error: WhitelistError) {
+    assert_eq!(result, Err(ProgramError::Custom(error.into())));
+}
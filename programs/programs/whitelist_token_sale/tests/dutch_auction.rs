@@ -0,0 +1,182 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::{assert_error, Svm, TestSale, LAMPORTS_PER_SOL};
+use whitelist_token_sale_client::{
+    events::RebateClaimed,
+    instructions,
+    pricing::{DecayCurve, DutchAuction, PricingMode},
+    state::SaleMode,
+    WhitelistError,
+};
+
+const START_PRICE: u64 = 200_000_000;
+const FLOOR_PRICE: u64 = 100_000_000;
+const DURATION: i64 = 1_000;
+
+/// Uniform-price auction falling linearly from `START_PRICE` to `FLOOR_PRICE` over `DURATION` seconds
+fn create_auction(svm: &mut Svm, referral_bps: u16) -> (TestSale, DutchAuction) {
+    let sale = TestSale::create_with_referrals(
+        svm,
+        "auction",
+        SaleMode::Vault,
+        6,
+        START_PRICE,
+        10,
+        3,
+        referral_bps,
+    )
+    .unwrap();
+    let auction = DutchAuction {
+        start_price: START_PRICE,
+        floor_price: FLOOR_PRICE,
+        start_timestamp: svm.now(),
+        end_timestamp: svm.now() + DURATION,
+        curve: DecayCurve::Linear,
+        uniform_clearing: true,
+    };
+
+    svm.process(
+        instructions::set_pricing_mode(&sale.owner, &sale.name, PricingMode::DutchAuction(auction)),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    (sale, auction)
+}
+
+#[test]
+fn sales_cant_be_closed_before_rebates_are_claimed() {
+    let mut svm = Svm::new();
+    let (sale, auction) = create_auction(&mut svm, 0);
+    let early_buyer = sale.new_buyer(&mut svm);
+    let late_buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+
+    sale.buy(&mut svm, &early_buyer, 2).unwrap();
+    svm.warp_to(auction.start_timestamp + DURATION / 2);
+    sale.buy(&mut svm, &late_buyer, 2).unwrap();
+    assert_eq!(svm.sale(&sale.name).clearing_price, 150_000_000);
+
+    svm.warp_to(auction.end_timestamp);
+    assert_error(
+        sale.close(&mut svm, &sale.owner),
+        WhitelistError::ClaimsOutstanding,
+    );
+
+    let buyer_lamports = svm.lamports(&early_buyer);
+    svm.process(
+        instructions::claim_rebate(&early_buyer, &sale.name, None),
+        &[&early_buyer],
+    )
+    .unwrap();
+    assert_eq!(svm.lamports(&early_buyer), buyer_lamports + 100_000_000);
+
+    let rebate = svm.event::<RebateClaimed>();
+    assert_eq!(rebate.buyer, early_buyer);
+    assert_eq!(rebate.amount, 100_000_000);
+    assert_eq!(rebate.clearing_price, 150_000_000);
+
+    // The late buyer paid the clearing price, so nothing else is owed
+    sale.close(&mut svm, &sale.owner).unwrap();
+}
+
+#[test]
+fn rebates_exclude_the_fee_and_referral_shares() {
+    let mut svm = Svm::new();
+
+    // 1% platform fee
+    let upgrade_authority = Pubkey::new_unique();
+    svm.airdrop(&upgrade_authority, LAMPORTS_PER_SOL);
+    svm.set_upgrade_authority(&upgrade_authority);
+    let fee_recipient = Pubkey::new_unique();
+    svm.airdrop(&fee_recipient, LAMPORTS_PER_SOL);
+    svm.process(
+        instructions::initialize_config(
+            &upgrade_authority,
+            &upgrade_authority,
+            &fee_recipient,
+            100,
+        ),
+        &[&upgrade_authority],
+    )
+    .unwrap();
+
+    // 5% referral reward
+    let (sale, auction) = create_auction(&mut svm, 500);
+    let referrer = sale.new_buyer(&mut svm);
+    let buyer = svm.funded_wallet();
+    sale.register_referred(&mut svm, &buyer, Some(&referrer))
+        .unwrap();
+    sale.open_sale(&mut svm);
+
+    sale.buy(&mut svm, &buyer, 2).unwrap();
+    svm.warp_to(auction.start_timestamp + DURATION / 2);
+    sale.buy(&mut svm, &referrer, 2).unwrap();
+    svm.warp_to(auction.end_timestamp);
+
+    // The buyer overpaid 100_000_000, of which 1_000_000 went to the fee recipient and 5_000_000
+    // is owed to the referrer
+    let buyer_lamports = svm.lamports(&buyer);
+    svm.process(
+        instructions::claim_rebate(&buyer, &sale.name, None),
+        &[&buyer],
+    )
+    .unwrap();
+    assert_eq!(svm.lamports(&buyer), buyer_lamports + 94_000_000);
+
+    let state = svm.sale(&sale.name);
+    assert_eq!(state.lamports_raised, 600_000_000);
+    assert_eq!(state.fees_paid, 7_000_000);
+    assert_eq!(state.referral_rewards_owed, 20_000_000);
+    assert_eq!(svm.allowance(&sale.name, &buyer).lamports_paid, 300_000_000);
+}
+
+#[test]
+fn tokens_cant_be_bought_once_the_auction_has_ended() {
+    let mut svm = Svm::new();
+    let (sale, auction) = create_auction(&mut svm, 0);
+    let buyer = sale.new_buyer(&mut svm);
+    let late_buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+    sale.buy(&mut svm, &buyer, 2).unwrap();
+
+    // A late purchase would lower the clearing price after the owner withdrew what wasn't owed back
+    svm.warp_to(auction.end_timestamp);
+    svm.process(
+        instructions::withdraw_proceeds(&sale.owner, &sale.name, &sale.owner, None),
+        &[&sale.owner],
+    )
+    .unwrap();
+    assert_error(
+        sale.buy(&mut svm, &late_buyer, 1),
+        WhitelistError::SaleClosed,
+    );
+    assert_eq!(svm.sale(&sale.name).clearing_price, START_PRICE);
+}
+
+#[test]
+fn sold_out_auctions_cant_be_given_more_supply() {
+    let mut svm = Svm::new();
+    let (sale, _auction) = create_auction(&mut svm, 0);
+    let set_supply = |svm: &mut Svm, total_tokens_for_sale| {
+        svm.process(
+            instructions::set_sale_caps(&sale.owner, &sale.name, total_tokens_for_sale, None),
+            &[&sale.owner],
+        )
+    };
+    set_supply(&mut svm, 4).unwrap();
+
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+    sale.buy(&mut svm, &buyer, 4).unwrap();
+
+    // Only the rebates down to the final clearing price are reserved, so the price can't fall again
+    svm.process(
+        instructions::withdraw_proceeds(&sale.owner, &sale.name, &sale.owner, None),
+        &[&sale.owner],
+    )
+    .unwrap();
+    assert_error(set_supply(&mut svm, 6), WhitelistError::InvalidSaleCaps);
+    set_supply(&mut svm, 4).unwrap();
+}
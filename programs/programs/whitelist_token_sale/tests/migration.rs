@@ -0,0 +1,144 @@
+mod common;
+
+use anchor_lang::{
+    prelude::{Pubkey, Rent},
+    Discriminator,
+};
+use common::{assert_error, Account, Svm, TestSale, LAMPORTS_PER_SOL};
+use whitelist_token_sale_client::{
+    instructions,
+    pda::{find_allowance_address, find_sale_address},
+    state::{Allowance, SaleMode, WhitelistSale},
+    WhitelistError, ID,
+};
+
+const SALE_NAME: &str = "legacy";
+const LAMPORTS_PER_TOKEN: u64 = 100_000_000;
+const MAX_TOKENS_PER_BUYER: u64 = 10;
+const MAX_BUYERS: u64 = 3;
+const DECIMALS: u8 = 6;
+
+fn program_account(data: Vec<u8>, extra_lamports: u64) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()) + extra_lamports,
+        data,
+        owner: ID,
+        executable: false,
+    }
+}
+
+/// Sets up a sale of the former vault program, in its own layout, where `buyer` bought `tokens_bought`
+fn legacy_sale(svm: &mut Svm, buyer: &Pubkey, tokens_bought: u64) -> TestSale {
+    let owner = Pubkey::new_unique();
+    svm.airdrop(&owner, 10 * LAMPORTS_PER_SOL);
+    let token = svm.create_mint(&owner, DECIMALS);
+    let sale = find_sale_address(SALE_NAME).0;
+
+    let unit = 10_u64.pow(DECIMALS.into());
+    svm.mint_tokens(
+        &token,
+        &sale,
+        (MAX_BUYERS * MAX_TOKENS_PER_BUYER - tokens_bought) * unit,
+    );
+    svm.mint_tokens(&token, buyer, tokens_bought * unit);
+    // The owner's token account receives the unsold tokens once the sale is closed
+    svm.mint_tokens(&token, &owner, 0);
+
+    // token_mint, owner, lamports_per_token, max_tokens_per_buyer, max_buyers, num_buyers,
+    // is_registration_open, is_sale_open
+    let mut data = WhitelistSale::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(token.mint.as_ref());
+    data.extend_from_slice(owner.as_ref());
+    for value in [LAMPORTS_PER_TOKEN, MAX_TOKENS_PER_BUYER, MAX_BUYERS, 1] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(&[1, 0]);
+    svm.set_account(
+        sale,
+        program_account(data, tokens_bought * LAMPORTS_PER_TOKEN),
+    );
+
+    let mut data = Allowance::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&tokens_bought.to_le_bytes());
+    svm.set_account(
+        find_allowance_address(SALE_NAME, buyer).0,
+        program_account(data, 0),
+    );
+
+    TestSale {
+        name: SALE_NAME.to_string(),
+        owner,
+        token,
+        mode: SaleMode::Vault,
+        lamports_per_token: LAMPORTS_PER_TOKEN,
+        fee_recipient: None,
+    }
+}
+
+#[test]
+fn legacy_vault_sales_keep_selling_once_migrated() {
+    let mut svm = Svm::new();
+    let buyer = Pubkey::new_unique();
+    svm.airdrop(&buyer, 10 * LAMPORTS_PER_SOL);
+    let sale = legacy_sale(&mut svm, &buyer, 4);
+
+    // Allowances can only be migrated along with their sale
+    assert!(svm
+        .process(
+            instructions::migrate_allowance(&buyer, &sale.name, &buyer),
+            &[&buyer],
+        )
+        .is_err());
+
+    svm.process(
+        instructions::migrate_whitelist_sale(&sale.owner, &sale.name),
+        &[&sale.owner],
+    )
+    .unwrap();
+    svm.process(
+        instructions::migrate_allowance(&buyer, &sale.name, &buyer),
+        &[&buyer],
+    )
+    .unwrap();
+
+    let state = svm.sale(&sale.name);
+    assert_eq!(state.sale_mode, SaleMode::Vault);
+    assert_eq!(
+        state.total_tokens_for_sale,
+        MAX_BUYERS * MAX_TOKENS_PER_BUYER
+    );
+    assert_eq!(state.tokens_sold, 4);
+    assert_eq!(state.lamports_raised, 4 * LAMPORTS_PER_TOKEN);
+
+    let allowance = svm.allowance(&sale.name, &buyer);
+    assert_eq!(allowance.buyer, buyer);
+    assert_eq!(allowance.tokens_bought, 4);
+    assert!(allowance.is_approved);
+    assert_eq!(allowance.lamports_paid, 4 * LAMPORTS_PER_TOKEN);
+
+    assert_error(
+        svm.process(
+            instructions::migrate_allowance(&buyer, &sale.name, &buyer),
+            &[&buyer],
+        ),
+        WhitelistError::AllowanceAlreadyMigrated,
+    );
+
+    let new_buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+
+    // Tokens bought before the migration still count towards the buyer's maximum
+    assert_error(
+        sale.buy(&mut svm, &buyer, 7),
+        WhitelistError::AllowanceExceeded,
+    );
+    sale.buy(&mut svm, &buyer, 6).unwrap();
+    sale.buy(&mut svm, &new_buyer, 10).unwrap();
+
+    let state = svm.sale(&sale.name);
+    assert_eq!(state.tokens_sold, 20);
+    assert_eq!(state.num_buyers, 2);
+    assert_eq!(svm.token_balance(&sale.vault()), 10_000_000);
+
+    sale.close(&mut svm, &sale.owner).unwrap();
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::{assert_error, Svm, TestSale, LAMPORTS_PER_SOL};
+use whitelist_token_sale_client::{
+    events::{BuyerRegistered, BuyerRemoved},
+    instructions,
+    state::{SaleMode, WhitelistMode},
+    WhitelistError,
+};
+
+const LAMPORTS_PER_TOKEN: u64 = 100_000_000;
+
+/// A sale run by an operator, which is funded to pay for allowances
+fn create_sale(svm: &mut Svm) -> (TestSale, Pubkey) {
+    let sale = TestSale::create(
+        svm,
+        "operators",
+        SaleMode::Vault,
+        6,
+        LAMPORTS_PER_TOKEN,
+        10,
+        3,
+    )
+    .unwrap();
+
+    let operator = Pubkey::new_unique();
+    svm.airdrop(&operator, LAMPORTS_PER_SOL);
+    svm.process(
+        instructions::set_operator(&sale.owner, &sale.name, Some(&operator)),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    (sale, operator)
+}
+
+#[test]
+fn only_the_owner_sets_the_operator() {
+    let mut svm = Svm::new();
+    let (sale, operator) = create_sale(&mut svm);
+    assert_eq!(svm.sale(&sale.name).operator, Some(operator));
+
+    assert_error(
+        svm.process(
+            instructions::set_operator(&operator, &sale.name, None),
+            &[&operator],
+        ),
+        WhitelistError::OnlyOwner,
+    );
+
+    svm.process(
+        instructions::set_operator(&sale.owner, &sale.name, None),
+        &[&sale.owner],
+    )
+    .unwrap();
+    assert_error(
+        sale.update_state(&mut svm, &operator, false, true),
+        WhitelistError::OnlyOperator,
+    );
+}
+
+#[test]
+fn operators_run_the_sale_and_its_whitelist() {
+    let mut svm = Svm::new();
+    let (sale, operator) = create_sale(&mut svm);
+
+    svm.process(
+        instructions::set_whitelist_mode(&sale.owner, &sale.name, WhitelistMode::Curated),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    let buyer = Pubkey::new_unique();
+    let other_buyer = Pubkey::new_unique();
+    svm.airdrop(&buyer, LAMPORTS_PER_SOL);
+    svm.process(
+        instructions::add_to_whitelist(&operator, &sale.name, &buyer, &[other_buyer]),
+        &[&operator],
+    )
+    .unwrap();
+    assert!(svm.allowance(&sale.name, &buyer).is_approved);
+    assert!(svm.allowance(&sale.name, &other_buyer).is_approved);
+
+    let registered = svm.events::<BuyerRegistered>();
+    assert_eq!(registered.len(), 2);
+    assert_eq!(registered[0].buyer, buyer);
+    assert_eq!(registered[1].buyer, other_buyer);
+    assert_eq!(registered[1].num_buyers, 2);
+    assert!(registered.iter().all(|event| event.is_approved));
+
+    sale.update_state(&mut svm, &operator, false, true).unwrap();
+    sale.buy(&mut svm, &buyer, 2).unwrap();
+
+    svm.process(
+        instructions::remove_from_whitelist(&operator, &sale.name, &buyer, &[other_buyer]),
+        &[&operator],
+    )
+    .unwrap();
+    assert!(!svm.allowance(&sale.name, &buyer).is_approved);
+
+    let removed = svm.events::<BuyerRemoved>();
+    assert_eq!(removed.len(), 2);
+    assert_eq!(removed[0].buyer, buyer);
+    assert_eq!(removed[1].buyer, other_buyer);
+    assert_eq!(removed[1].num_buyers, 0);
+
+    // The owner keeps every operator permission
+    sale.update_state(&mut svm, &sale.owner, false, false)
+        .unwrap();
+}
+
+#[test]
+fn operators_cant_touch_the_proceeds_economics_or_whitelist_rules() {
+    let mut svm = Svm::new();
+    let (sale, operator) = create_sale(&mut svm);
+    // Vault sales send the unsold tokens to the signer's token account
+    svm.mint_tokens(&sale.token, &operator, 0);
+
+    assert_error(
+        svm.process(
+            instructions::withdraw_proceeds(&operator, &sale.name, &sale.owner, None),
+            &[&operator],
+        ),
+        WhitelistError::OnlyOwner,
+    );
+    assert_error(
+        svm.process(
+            instructions::set_treasury(&operator, &sale.name, &operator),
+            &[&operator],
+        ),
+        WhitelistError::OnlyOwner,
+    );
+    assert_error(
+        svm.process(
+            instructions::set_sale_caps(&operator, &sale.name, 1, None),
+            &[&operator],
+        ),
+        WhitelistError::OnlyOwner,
+    );
+    assert_error(
+        svm.process(
+            instructions::propose_owner(&operator, &sale.name, Some(&operator)),
+            &[&operator],
+        ),
+        WhitelistError::OnlyOwner,
+    );
+    assert_error(sale.close(&mut svm, &operator), WhitelistError::OnlyOwner);
+
+    // Who gets whitelisted, and for how much, stays with the owner
+    assert_error(
+        svm.process(
+            instructions::set_whitelist_mode(&operator, &sale.name, WhitelistMode::Merkle),
+            &[&operator],
+        ),
+        WhitelistError::OnlyOwner,
+    );
+    assert_error(
+        svm.process(
+            instructions::update_merkle_root(&operator, &sale.name, [1; 32]),
+            &[&operator],
+        ),
+        WhitelistError::OnlyOwner,
+    );
+    assert_error(
+        svm.process(
+            instructions::set_whitelist_signer(&operator, &sale.name, &operator),
+            &[&operator],
+        ),
+        WhitelistError::OnlyOwner,
+    );
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{entrypoint::ProgramResult, program_option::COption},
+};
+use common::{assert_error, Svm, TestSale};
+use whitelist_token_sale_client::{instructions, state::SaleMode, WhitelistError};
+
+fn create_sale(svm: &mut Svm) -> TestSale {
+    TestSale::create(svm, "ownership", SaleMode::Mint, 6, 100_000_000, 10, 3).unwrap()
+}
+
+fn propose(
+    svm: &mut Svm,
+    sale: &TestSale,
+    signer: &Pubkey,
+    pending_owner: Option<&Pubkey>,
+) -> ProgramResult {
+    svm.process(
+        instructions::propose_owner(signer, &sale.name, pending_owner),
+        &[signer],
+    )
+}
+
+fn accept(svm: &mut Svm, sale: &TestSale, signer: &Pubkey) -> ProgramResult {
+    svm.process(
+        instructions::accept_ownership(signer, &sale.name),
+        &[signer],
+    )
+}
+
+#[test]
+fn ownership_is_transferred_once_accepted() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm);
+    let new_owner = svm.funded_wallet();
+    let stranger = svm.funded_wallet();
+
+    assert_error(
+        propose(&mut svm, &sale, &stranger, Some(&stranger)),
+        WhitelistError::OnlyOwner,
+    );
+
+    propose(&mut svm, &sale, &sale.owner, Some(&new_owner)).unwrap();
+    assert_eq!(svm.sale(&sale.name).pending_owner, Some(new_owner));
+
+    // The owner keeps control until the transfer is accepted
+    assert_error(
+        accept(&mut svm, &sale, &stranger),
+        WhitelistError::OnlyPendingOwner,
+    );
+    sale.update_state(&mut svm, &sale.owner, false, true)
+        .unwrap();
+
+    accept(&mut svm, &sale, &new_owner).unwrap();
+
+    let state = svm.sale(&sale.name);
+    assert_eq!(state.owner, new_owner);
+    assert_eq!(state.pending_owner, None);
+    assert_eq!(state.treasury_address(), new_owner);
+
+    assert_error(
+        sale.update_state(&mut svm, &sale.owner, true, false),
+        WhitelistError::OnlyOperator,
+    );
+    assert_error(sale.close(&mut svm, &sale.owner), WhitelistError::OnlyOwner);
+
+    sale.update_state(&mut svm, &new_owner, true, false)
+        .unwrap();
+    sale.close(&mut svm, &new_owner).unwrap();
+    assert_eq!(
+        svm.mint(&sale.token.mint).mint_authority,
+        COption::Some(new_owner)
+    );
+}
+
+#[test]
+fn proposals_can_be_replaced_and_withdrawn() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm);
+    let first = svm.funded_wallet();
+    let second = svm.funded_wallet();
+
+    propose(&mut svm, &sale, &sale.owner, Some(&first)).unwrap();
+    propose(&mut svm, &sale, &sale.owner, Some(&second)).unwrap();
+    assert_error(
+        accept(&mut svm, &sale, &first),
+        WhitelistError::OnlyPendingOwner,
+    );
+
+    propose(&mut svm, &sale, &sale.owner, None).unwrap();
+    assert_error(
+        accept(&mut svm, &sale, &second),
+        WhitelistError::OnlyPendingOwner,
+    );
+    assert_eq!(svm.sale(&sale.name).owner, sale.owner);
+}
+
+#[test]
+fn a_separate_treasury_is_kept() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm);
+    let new_owner = svm.funded_wallet();
+    let treasury = Pubkey::new_unique();
+
+    svm.process(
+        instructions::set_treasury(&sale.owner, &sale.name, &treasury),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    propose(&mut svm, &sale, &sale.owner, Some(&new_owner)).unwrap();
+    accept(&mut svm, &sale, &new_owner).unwrap();
+
+    assert_eq!(svm.sale(&sale.name).treasury_address(), treasury);
+}
@@ -0,0 +1,415 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::{assert_error, Svm, TestSale, LAMPORTS_PER_SOL};
+use whitelist_token_sale_client::{
+    events::{CommitmentSettled, PaymentCommitted, RefundClaimed, SaleFinalized},
+    instructions,
+    state::SaleMode,
+    WhitelistError,
+};
+
+const LAMPORTS_PER_TOKEN: u64 = 100_000_000;
+
+/// A program deployed by an upgrade authority, which creates the config with a fee of `fee_bps`
+struct Platform {
+    upgrade_authority: Pubkey,
+    fee_recipient: Pubkey,
+}
+
+impl Platform {
+    fn new(svm: &mut Svm, fee_bps: u16) -> Self {
+        let upgrade_authority = Pubkey::new_unique();
+        svm.airdrop(&upgrade_authority, LAMPORTS_PER_SOL);
+        svm.set_upgrade_authority(&upgrade_authority);
+
+        let fee_recipient = Pubkey::new_unique();
+        svm.airdrop(&fee_recipient, LAMPORTS_PER_SOL);
+
+        svm.process(
+            instructions::initialize_config(
+                &upgrade_authority,
+                &upgrade_authority,
+                &fee_recipient,
+                fee_bps,
+            ),
+            &[&upgrade_authority],
+        )
+        .unwrap();
+
+        Self {
+            upgrade_authority,
+            fee_recipient,
+        }
+    }
+}
+
+fn create_sale(svm: &mut Svm, name: &str) -> TestSale {
+    TestSale::create(svm, name, SaleMode::Vault, 6, LAMPORTS_PER_TOKEN, 10, 3).unwrap()
+}
+
+#[test]
+fn only_the_upgrade_authority_can_create_the_config() {
+    let mut svm = Svm::new();
+    let upgrade_authority = Pubkey::new_unique();
+    svm.airdrop(&upgrade_authority, LAMPORTS_PER_SOL);
+    svm.set_upgrade_authority(&upgrade_authority);
+
+    let stranger = Pubkey::new_unique();
+    svm.airdrop(&stranger, LAMPORTS_PER_SOL);
+
+    assert_error(
+        svm.process(
+            instructions::initialize_config(&stranger, &stranger, &stranger, 100),
+            &[&stranger],
+        ),
+        WhitelistError::OnlyUpgradeAuthority,
+    );
+    assert_error(
+        svm.process(
+            instructions::initialize_config(
+                &upgrade_authority,
+                &upgrade_authority,
+                &upgrade_authority,
+                10_001,
+            ),
+            &[&upgrade_authority],
+        ),
+        WhitelistError::InvalidFee,
+    );
+
+    svm.process(
+        instructions::initialize_config(
+            &upgrade_authority,
+            &upgrade_authority,
+            &upgrade_authority,
+            100,
+        ),
+        &[&upgrade_authority],
+    )
+    .unwrap();
+}
+
+#[test]
+fn sales_without_a_config_pay_no_fee() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm, "no fee");
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+
+    let sale_lamports = svm.lamports(&sale.address());
+    sale.buy(&mut svm, &buyer, 2).unwrap();
+
+    assert_eq!(
+        svm.lamports(&sale.address()),
+        sale_lamports + 2 * LAMPORTS_PER_TOKEN
+    );
+    assert_eq!(svm.sale(&sale.name).fees_paid, 0);
+}
+
+#[test]
+fn purchases_pay_the_fee_to_the_fee_recipient() {
+    let mut svm = Svm::new();
+    let platform = Platform::new(&mut svm, 250);
+    let sale = create_sale(&mut svm, "fee");
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+
+    let sale_lamports = svm.lamports(&sale.address());
+    let buyer_lamports = svm.lamports(&buyer);
+    sale.buy(&mut svm, &buyer, 4).unwrap();
+
+    // 2.5% of the 0.4 SOL paid
+    let fee = 10_000_000;
+    assert_eq!(
+        svm.lamports(&platform.fee_recipient),
+        LAMPORTS_PER_SOL + fee
+    );
+    assert_eq!(
+        svm.lamports(&sale.address()),
+        sale_lamports + 4 * LAMPORTS_PER_TOKEN - fee
+    );
+    assert!(buyer_lamports - svm.lamports(&buyer) >= 4 * LAMPORTS_PER_TOKEN);
+
+    let state = svm.sale(&sale.name);
+    assert_eq!(state.lamports_raised, 4 * LAMPORTS_PER_TOKEN);
+    assert_eq!(state.fees_paid, fee);
+}
+
+#[test]
+fn purchases_need_the_sale_fee_recipient() {
+    let mut svm = Svm::new();
+    Platform::new(&mut svm, 250);
+    let sale = create_sale(&mut svm, "fee");
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+
+    assert_error(
+        svm.process(
+            instructions::buy_tokens(
+                &buyer,
+                &sale.name,
+                &sale.token,
+                None,
+                None,
+                None,
+                sale.mode,
+                1,
+            ),
+            &[&buyer],
+        ),
+        WhitelistError::MissingFeeAccounts,
+    );
+    assert_error(
+        svm.process(
+            instructions::buy_tokens(
+                &buyer,
+                &sale.name,
+                &sale.token,
+                None,
+                Some(&buyer),
+                None,
+                sale.mode,
+                1,
+            ),
+            &[&buyer],
+        ),
+        WhitelistError::FeeRecipientMismatch,
+    );
+}
+
+#[test]
+fn config_changes_only_apply_to_new_sales() {
+    let mut svm = Svm::new();
+    let platform = Platform::new(&mut svm, 250);
+    let live_sale = create_sale(&mut svm, "live");
+
+    let stranger = Pubkey::new_unique();
+    svm.airdrop(&stranger, LAMPORTS_PER_SOL);
+    assert_error(
+        svm.process(
+            instructions::update_config(&stranger, &stranger, &stranger, 0),
+            &[&stranger],
+        ),
+        WhitelistError::OnlyAdmin,
+    );
+
+    let new_fee_recipient = Pubkey::new_unique();
+    svm.airdrop(&new_fee_recipient, LAMPORTS_PER_SOL);
+    svm.process(
+        instructions::update_config(
+            &platform.upgrade_authority,
+            &platform.upgrade_authority,
+            &new_fee_recipient,
+            1_000,
+        ),
+        &[&platform.upgrade_authority],
+    )
+    .unwrap();
+
+    let new_sale = create_sale(&mut svm, "new");
+
+    let live_state = svm.sale(&live_sale.name);
+    assert_eq!(live_state.fee_bps, 250);
+    assert_eq!(live_state.fee_recipient, platform.fee_recipient);
+
+    let new_state = svm.sale(&new_sale.name);
+    assert_eq!(new_state.fee_bps, 1_000);
+    assert_eq!(new_state.fee_recipient, new_fee_recipient);
+
+    let buyer = live_sale.new_buyer(&mut svm);
+    live_sale.open_sale(&mut svm);
+    live_sale.buy(&mut svm, &buyer, 4).unwrap();
+
+    assert_eq!(
+        svm.lamports(&platform.fee_recipient),
+        LAMPORTS_PER_SOL + 10_000_000
+    );
+    assert_eq!(svm.lamports(&new_fee_recipient), LAMPORTS_PER_SOL);
+}
+
+#[test]
+fn refunds_include_the_fee() {
+    let mut svm = Svm::new();
+    let platform = Platform::new(&mut svm, 250);
+    let sale = create_sale(&mut svm, "refund");
+
+    let deadline = svm.now() + 3_600;
+    svm.process(
+        instructions::set_soft_cap(&sale.owner, &sale.name, 10 * LAMPORTS_PER_TOKEN, deadline),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+    sale.buy(&mut svm, &buyer, 4).unwrap();
+
+    // The fee stays with the sale while the purchase can be refunded
+    let state = svm.sale(&sale.name);
+    assert_eq!(state.fees_owed, 10_000_000);
+    assert_eq!(state.fees_paid, 0);
+    assert_eq!(svm.lamports(&platform.fee_recipient), LAMPORTS_PER_SOL);
+
+    // Nothing can be withdrawn while buyers can still be refunded
+    assert_error(
+        svm.process(
+            instructions::withdraw_proceeds(&sale.owner, &sale.name, &sale.owner, None),
+            &[&sale.owner],
+        ),
+        WhitelistError::NothingToWithdraw,
+    );
+
+    svm.warp_to(deadline);
+    let buyer_lamports = svm.lamports(&buyer);
+    svm.process(
+        instructions::claim_refund(&buyer, &sale.name, &sale.token.mint, None),
+        &[&buyer],
+    )
+    .unwrap();
+
+    assert_eq!(
+        svm.lamports(&buyer),
+        buyer_lamports + 4 * LAMPORTS_PER_TOKEN
+    );
+
+    let refund = svm.event::<RefundClaimed>();
+    assert_eq!(refund.sale, sale.address());
+    assert_eq!(refund.buyer, buyer);
+    assert_eq!(refund.amount, 4 * LAMPORTS_PER_TOKEN);
+}
+
+#[test]
+fn finalizing_pays_the_fees_held_back() {
+    let mut svm = Svm::new();
+    let platform = Platform::new(&mut svm, 250);
+    let sale = create_sale(&mut svm, "finalize");
+
+    svm.process(
+        instructions::set_soft_cap(
+            &sale.owner,
+            &sale.name,
+            5 * LAMPORTS_PER_TOKEN,
+            svm.now() + 3_600,
+        ),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    let buyer = sale.new_buyer(&mut svm);
+    let other_buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+    sale.buy(&mut svm, &buyer, 3).unwrap();
+    sale.buy(&mut svm, &other_buyer, 2).unwrap();
+
+    // 2.5% of each of the 0.3 SOL and 0.2 SOL payments
+    let fees = 7_500_000 + 5_000_000;
+    assert_eq!(svm.sale(&sale.name).fees_owed, fees);
+
+    let finalize =
+        |fee_recipient| instructions::finalize_sale(&sale.owner, &sale.name, None, fee_recipient);
+    assert_error(
+        svm.process(finalize(None), &[&sale.owner]),
+        WhitelistError::MissingFeeAccounts,
+    );
+
+    let sale_lamports = svm.lamports(&sale.address());
+    svm.process(finalize(Some(&platform.fee_recipient)), &[&sale.owner])
+        .unwrap();
+
+    assert_eq!(
+        svm.lamports(&platform.fee_recipient),
+        LAMPORTS_PER_SOL + fees
+    );
+    assert_eq!(svm.lamports(&sale.address()), sale_lamports - fees);
+    assert_eq!(svm.event::<SaleFinalized>().fee, fees);
+
+    let state = svm.sale(&sale.name);
+    assert_eq!(state.fees_owed, 0);
+    assert_eq!(state.fees_paid, fees);
+
+    // Once finalized, purchases pay their fee right away
+    sale.buy(&mut svm, &buyer, 1).unwrap();
+    assert_eq!(
+        svm.lamports(&platform.fee_recipient),
+        LAMPORTS_PER_SOL + fees + 2_500_000
+    );
+    assert_eq!(svm.sale(&sale.name).fees_owed, 0);
+}
+
+#[test]
+fn settled_commitments_pay_the_fee() {
+    let mut svm = Svm::new();
+    let platform = Platform::new(&mut svm, 250);
+    let sale = create_sale(&mut svm, "commit");
+    svm.process(
+        instructions::set_commit_mode(&sale.owner, &sale.name, true),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+    svm.process(
+        instructions::commit(
+            &buyer,
+            &sale.name,
+            None,
+            4 * LAMPORTS_PER_TOKEN + 50_000_000,
+        ),
+        &[&buyer],
+    )
+    .unwrap();
+
+    let committed = svm.event::<PaymentCommitted>();
+    assert_eq!(committed.buyer, buyer);
+    assert_eq!(committed.amount, 4 * LAMPORTS_PER_TOKEN + 50_000_000);
+    assert_eq!(committed.buyer_committed, committed.amount);
+    assert_eq!(committed.total_committed, committed.amount);
+
+    svm.process(
+        instructions::finalize_sale(&sale.owner, &sale.name, None, None),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    let settle = |fee_recipient| {
+        instructions::settle_commitment(
+            &buyer,
+            &sale.name,
+            &sale.token.mint,
+            None,
+            fee_recipient,
+            None,
+        )
+    };
+    assert_error(
+        svm.process(settle(None), &[&buyer]),
+        WhitelistError::MissingFeeAccounts,
+    );
+
+    let buyer_lamports = svm.lamports(&buyer);
+    let sale_lamports = svm.lamports(&sale.address());
+    svm.process(settle(Some(&platform.fee_recipient)), &[&buyer])
+        .unwrap();
+
+    // 2.5% of the 0.4 SOL the 4 allocated tokens cost, while the excess is refunded in full
+    let fee = 10_000_000;
+    assert_eq!(
+        svm.lamports(&platform.fee_recipient),
+        LAMPORTS_PER_SOL + fee
+    );
+    assert_eq!(svm.lamports(&buyer), buyer_lamports + 50_000_000);
+    assert_eq!(
+        svm.lamports(&sale.address()),
+        sale_lamports - 50_000_000 - fee
+    );
+    assert_eq!(svm.sale(&sale.name).fees_paid, fee);
+
+    let settled = svm.event::<CommitmentSettled>();
+    assert_eq!(settled.buyer, buyer);
+    assert_eq!(settled.tokens, 4);
+    assert_eq!(settled.cost, 4 * LAMPORTS_PER_TOKEN);
+    assert_eq!(settled.refund, 50_000_000);
+    assert_eq!(settled.fee, fee);
+}
@@ -0,0 +1,224 @@
+mod common;
+
+use anchor_lang::{
+    prelude::{ProgramError, Pubkey},
+    solana_program::entrypoint::ProgramResult,
+};
+use common::{assert_error, Svm, TestSale};
+use whitelist_token_sale_client::{
+    events::ReferralRewardsClaimed, instructions, state::SaleMode, WhitelistError,
+};
+
+const LAMPORTS_PER_TOKEN: u64 = 100_000_000;
+
+fn claim(svm: &mut Svm, sale: &TestSale, referrer: &Pubkey) -> ProgramResult {
+    svm.process(
+        instructions::claim_referral_rewards(referrer, &sale.name, None),
+        &[referrer],
+    )
+}
+
+#[test]
+fn referrers_earn_a_share_of_referred_purchases() {
+    let mut svm = Svm::new();
+    let sale = TestSale::create_referral_sale(&mut svm, "referrals");
+
+    // The referrer is a buyer themselves, who wasn't referred
+    let referrer = sale.new_buyer(&mut svm);
+    let buyer = svm.funded_wallet();
+    sale.register_referred(&mut svm, &buyer, Some(&referrer))
+        .unwrap();
+    let other_buyer = svm.funded_wallet();
+    sale.register_referred(&mut svm, &other_buyer, Some(&referrer))
+        .unwrap();
+
+    assert_eq!(svm.allowance(&sale.name, &buyer).referrer, Some(referrer));
+    assert_eq!(svm.referrer_stats(&sale.name, &referrer).num_referrals, 2);
+
+    sale.open_sale(&mut svm);
+    sale.buy(&mut svm, &buyer, 4).unwrap();
+    sale.buy(&mut svm, &other_buyer, 2).unwrap();
+    sale.buy(&mut svm, &referrer, 5).unwrap();
+
+    let reward = 30_000_000;
+    assert_eq!(
+        svm.referrer_stats(&sale.name, &referrer).rewards_earned,
+        reward
+    );
+    assert_eq!(svm.sale(&sale.name).referral_rewards_owed, reward);
+
+    // Rewards stay with the sale when the owner withdraws
+    svm.process(
+        instructions::withdraw_proceeds(&sale.owner, &sale.name, &sale.owner, None),
+        &[&sale.owner],
+    )
+    .unwrap();
+    assert_eq!(
+        svm.sale(&sale.name).lamports_withdrawn,
+        11 * LAMPORTS_PER_TOKEN - reward
+    );
+
+    // Sales can't be closed before referrers are paid
+    assert_error(
+        sale.close(&mut svm, &sale.owner),
+        WhitelistError::ClaimsOutstanding,
+    );
+
+    let referrer_lamports = svm.lamports(&referrer);
+    claim(&mut svm, &sale, &referrer).unwrap();
+    assert_eq!(svm.lamports(&referrer), referrer_lamports + reward);
+
+    let claimed = svm.event::<ReferralRewardsClaimed>();
+    assert_eq!(claimed.sale, sale.address());
+    assert_eq!(claimed.referrer, referrer);
+    assert_eq!(claimed.amount, reward);
+    assert_eq!(claimed.rewards_claimed, reward);
+
+    assert_error(
+        claim(&mut svm, &sale, &referrer),
+        WhitelistError::NothingToClaim,
+    );
+
+    let stats = svm.referrer_stats(&sale.name, &referrer);
+    assert_eq!(stats.rewards_claimed, reward);
+    assert_eq!(svm.sale(&sale.name).referral_rewards_owed, 0);
+
+    sale.close(&mut svm, &sale.owner).unwrap();
+}
+
+#[test]
+fn buyers_cant_refer_themselves() {
+    let mut svm = Svm::new();
+    let sale = TestSale::create_referral_sale(&mut svm, "referrals");
+
+    let buyer = svm.funded_wallet();
+    assert_error(
+        sale.register_referred(&mut svm, &buyer, Some(&buyer)),
+        WhitelistError::SelfReferral,
+    );
+}
+
+#[test]
+fn referrers_have_to_register_before_the_buyers_they_refer() {
+    let mut svm = Svm::new();
+    let sale = TestSale::create_referral_sale(&mut svm, "referrals");
+
+    let referrer = svm.funded_wallet();
+    let buyer = svm.funded_wallet();
+    assert_error(
+        sale.register_referred(&mut svm, &buyer, Some(&referrer)),
+        WhitelistError::ReferrerNotRegistered,
+    );
+
+    sale.register(&mut svm, &referrer).unwrap();
+    sale.register_referred(&mut svm, &buyer, Some(&referrer))
+        .unwrap();
+
+    // Referrers are always registered before the buyers they referred, so the buyer can't refer them
+    // in turn, and referrals can't form a cycle of any length
+    assert_eq!(
+        sale.register_referred(&mut svm, &referrer, Some(&buyer)),
+        Err(ProgramError::AccountAlreadyInitialized)
+    );
+    assert_eq!(svm.allowance(&sale.name, &referrer).referrer, None);
+}
+
+#[test]
+fn purchases_need_the_buyer_referrer() {
+    let mut svm = Svm::new();
+    let sale = TestSale::create_referral_sale(&mut svm, "referrals");
+    let referrer = sale.new_buyer(&mut svm);
+
+    let buyer = svm.funded_wallet();
+    sale.register_referred(&mut svm, &buyer, Some(&referrer))
+        .unwrap();
+
+    // The buyer's own referral gives them stats that aren't their referrer's
+    let other_buyer = svm.funded_wallet();
+    sale.register_referred(&mut svm, &other_buyer, Some(&buyer))
+        .unwrap();
+    sale.open_sale(&mut svm);
+
+    for buyer_referrer in [None, Some(&buyer)] {
+        assert_error(
+            svm.process(
+                instructions::buy_tokens(
+                    &buyer,
+                    &sale.name,
+                    &sale.token,
+                    None,
+                    None,
+                    buyer_referrer,
+                    sale.mode,
+                    1,
+                ),
+                &[&buyer],
+            ),
+            WhitelistError::ReferrerMismatch,
+        );
+    }
+}
+
+#[test]
+fn referral_and_platform_fees_cant_exceed_the_payment() {
+    let mut svm = Svm::new();
+
+    assert_error(
+        TestSale::create_with_referrals(
+            &mut svm,
+            "referrals",
+            SaleMode::Mint,
+            6,
+            LAMPORTS_PER_TOKEN,
+            10,
+            3,
+            10_001,
+        )
+        .map(|_| ()),
+        WhitelistError::InvalidReferralFee,
+    );
+}
+
+#[test]
+fn rewards_are_void_when_the_soft_cap_is_missed() {
+    let mut svm = Svm::new();
+    let sale = TestSale::create_referral_sale(&mut svm, "referrals");
+
+    let deadline = svm.now() + 3_600;
+    svm.process(
+        instructions::set_soft_cap(&sale.owner, &sale.name, 10 * LAMPORTS_PER_TOKEN, deadline),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    let referrer = sale.new_buyer(&mut svm);
+    let buyer = svm.funded_wallet();
+    sale.register_referred(&mut svm, &buyer, Some(&referrer))
+        .unwrap();
+    sale.open_sale(&mut svm);
+    sale.buy(&mut svm, &buyer, 4).unwrap();
+
+    assert_error(
+        claim(&mut svm, &sale, &referrer),
+        WhitelistError::ReferralRewardsUnavailable,
+    );
+
+    // The buyer gets everything back, and the sale can be closed without paying the referrer
+    svm.warp_to(deadline);
+    let buyer_lamports = svm.lamports(&buyer);
+    svm.process(
+        instructions::claim_refund(&buyer, &sale.name, &sale.token.mint, None),
+        &[&buyer],
+    )
+    .unwrap();
+    assert_eq!(
+        svm.lamports(&buyer),
+        buyer_lamports + 4 * LAMPORTS_PER_TOKEN
+    );
+
+    assert_error(
+        claim(&mut svm, &sale, &referrer),
+        WhitelistError::ReferralRewardsUnavailable,
+    );
+    sale.close(&mut svm, &sale.owner).unwrap();
+}
@@ -0,0 +1,601 @@
+//! Stateful fuzzing of the sale's accounting: random instruction sequences over fixed-price, commit-mode and
+//! uniform-price auction sales, with or without vesting and a soft cap, checking after every step that the tokens
+//! and lamports of the sale are all accounted for.
+
+mod common;
+
+use anchor_lang::prelude::{Pubkey, Rent};
+use common::{Svm, TestSale, LAMPORTS_PER_SOL};
+use proptest::prelude::*;
+use whitelist_token_sale_client::{
+    events::{RebateClaimed, RefundClaimed},
+    instructions,
+    pda::{find_allowance_address, find_referrer_stats_address},
+    pricing::{DecayCurve, DutchAuction, PricingMode},
+    state::{Allowance, ReferrerStats, SaleMode, VestingSchedule, WhitelistSale},
+};
+
+const NUM_BUYERS: usize = 4;
+
+/// Seconds until the auction reaches its floor price, and until the soft cap's deadline
+const DURATION: i64 = 1_000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Pricing {
+    Fixed,
+    Commit,
+    UniformAuction,
+}
+
+#[derive(Clone, Debug)]
+struct SaleParams {
+    mode: SaleMode,
+    decimals: u8,
+    lamports_per_token: u64,
+    max_tokens_per_buyer: u64,
+    max_buyers: u64,
+    fee_bps: u16,
+    referral_bps: u16,
+    pricing: Pricing,
+    /// TGE offset from the sale's creation, TGE unlock, cliff and duration
+    vesting: Option<(i64, u16, i64, i64)>,
+    /// In whole tokens at `lamports_per_token`, never set in commit mode
+    soft_cap: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Action {
+    Register {
+        buyer: usize,
+        referrer: Option<usize>,
+    },
+    /// Buys `amount` tokens, or commits to them in commit mode
+    Buy {
+        buyer: usize,
+        amount: u64,
+        /// Commits half a token more than `amount`, which can't be allocated
+        with_excess: bool,
+    },
+    UpdateSaleState {
+        is_registration_open: bool,
+        is_sale_open: bool,
+    },
+    Finalize,
+    SettleCommitment {
+        buyer: usize,
+    },
+    ClaimVested {
+        buyer: usize,
+    },
+    ClaimRefund {
+        buyer: usize,
+    },
+    ClaimRebate {
+        buyer: usize,
+    },
+    Warp {
+        seconds: i64,
+    },
+    WithdrawProceeds,
+    ClaimReferralRewards {
+        referrer: usize,
+    },
+    Close,
+}
+
+fn sale_params() -> impl Strategy<Value = SaleParams> {
+    (
+        prop_oneof![Just(SaleMode::Vault), Just(SaleMode::Mint)],
+        0..=9_u8,
+        1..=LAMPORTS_PER_SOL,
+        1..=100_u64,
+        1..=NUM_BUYERS as u64,
+        prop_oneof![Just(0), 1..=1_000_u16],
+        prop_oneof![Just(0), 1..=1_000_u16],
+        prop_oneof![
+            Just(Pricing::Fixed),
+            Just(Pricing::Commit),
+            Just(Pricing::UniformAuction)
+        ],
+        prop::option::of((0..=DURATION, 0..=10_000_u16, 0..=DURATION / 4, 0..=DURATION)),
+        prop::option::of(1..=50_u64),
+    )
+        .prop_map(
+            |(
+                mode,
+                decimals,
+                lamports_per_token,
+                max_tokens_per_buyer,
+                max_buyers,
+                fee_bps,
+                referral_bps,
+                pricing,
+                vesting,
+                soft_cap,
+            )| SaleParams {
+                mode,
+                decimals,
+                lamports_per_token,
+                max_tokens_per_buyer,
+                max_buyers,
+                fee_bps,
+                referral_bps,
+                pricing,
+                vesting,
+                // Commitments are only settled once the sale is finalized, so they can't be refunded
+                soft_cap: soft_cap.filter(|_| pricing != Pricing::Commit),
+            },
+        )
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    // Finalizing ends most of what can happen to a sale, so it's rarer than the rest
+    prop_oneof![
+        12 => (0..NUM_BUYERS, prop::option::weighted(0.3, 0..NUM_BUYERS))
+            .prop_map(|(buyer, referrer)| Action::Register { buyer, referrer }),
+        20 => (0..NUM_BUYERS, prop_oneof![0..=10_u64, 0..=110_u64], any::<bool>())
+            .prop_map(|(buyer, amount, with_excess)| Action::Buy { buyer, amount, with_excess }),
+        2 => any::<(bool, bool)>().prop_map(|(is_registration_open, is_sale_open)| {
+            Action::UpdateSaleState { is_registration_open, is_sale_open }
+        }),
+        1 => Just(Action::Finalize),
+        6 => (0..NUM_BUYERS).prop_map(|buyer| Action::SettleCommitment { buyer }),
+        6 => (0..NUM_BUYERS).prop_map(|buyer| Action::ClaimVested { buyer }),
+        4 => (0..NUM_BUYERS).prop_map(|buyer| Action::ClaimRefund { buyer }),
+        4 => (0..NUM_BUYERS).prop_map(|buyer| Action::ClaimRebate { buyer }),
+        8 => (0..=DURATION / 2).prop_map(|seconds| Action::Warp { seconds }),
+        2 => Just(Action::WithdrawProceeds),
+        2 => (0..NUM_BUYERS).prop_map(|referrer| Action::ClaimReferralRewards { referrer }),
+    ]
+}
+
+struct Harness {
+    svm: Svm,
+    sale: TestSale,
+    buyers: Vec<Pubkey>,
+    /// Tokens deposited into the vault at creation, in base units
+    deposit: u64,
+    /// Sale account as of the last step, kept once it's closed
+    last_state: WhitelistSale,
+    sale_rent: u64,
+    fee_recipient: Pubkey,
+    fee_recipient_lamports: u64,
+    pricing: Pricing,
+    /// Tokens each buyer was refunded for, in whole tokens, and the lamports they got back
+    refunded_tokens: Vec<u64>,
+    refunded_lamports: Vec<u64>,
+    /// Payments rebated to auction buyers, and the part paid back to them, less the fee and referral shares
+    rebated: u64,
+    rebates_paid: u64,
+}
+
+impl Harness {
+    fn new(params: &SaleParams) -> Self {
+        let mut svm = Svm::new();
+
+        let fee_recipient = Pubkey::new_unique();
+        svm.airdrop(&fee_recipient, LAMPORTS_PER_SOL);
+        if params.fee_bps > 0 {
+            let upgrade_authority = Pubkey::new_unique();
+            svm.airdrop(&upgrade_authority, LAMPORTS_PER_SOL);
+            svm.set_upgrade_authority(&upgrade_authority);
+            svm.process(
+                instructions::initialize_config(
+                    &upgrade_authority,
+                    &upgrade_authority,
+                    &fee_recipient,
+                    params.fee_bps,
+                ),
+                &[&upgrade_authority],
+            )
+            .unwrap();
+        }
+
+        let sale = TestSale::create_with_referrals(
+            &mut svm,
+            "accounting",
+            params.mode,
+            params.decimals,
+            params.lamports_per_token,
+            params.max_tokens_per_buyer,
+            params.max_buyers,
+            params.referral_bps,
+        )
+        .unwrap();
+
+        let now = svm.now();
+        let configure = |svm: &mut Svm, instruction| {
+            svm.process(instruction, &[&sale.owner]).unwrap();
+        };
+
+        if params.pricing == Pricing::UniformAuction {
+            let auction = DutchAuction {
+                start_price: params.lamports_per_token,
+                floor_price: (params.lamports_per_token / 4).max(1),
+                start_timestamp: now,
+                end_timestamp: now + DURATION,
+                curve: DecayCurve::Linear,
+                uniform_clearing: true,
+            };
+            configure(
+                &mut svm,
+                instructions::set_pricing_mode(
+                    &sale.owner,
+                    &sale.name,
+                    PricingMode::DutchAuction(auction),
+                ),
+            );
+        }
+        if let Some(soft_cap) = params.soft_cap {
+            configure(
+                &mut svm,
+                instructions::set_soft_cap(
+                    &sale.owner,
+                    &sale.name,
+                    soft_cap * params.lamports_per_token,
+                    now + DURATION,
+                ),
+            );
+        }
+        if params.pricing == Pricing::Commit {
+            configure(
+                &mut svm,
+                instructions::set_commit_mode(&sale.owner, &sale.name, true),
+            );
+        }
+        if let Some((tge_offset, tge_unlock_bps, cliff_seconds, duration_seconds)) = params.vesting
+        {
+            let vesting = VestingSchedule {
+                tge_timestamp: now + tge_offset,
+                tge_unlock_bps,
+                cliff_seconds,
+                duration_seconds,
+            };
+            configure(
+                &mut svm,
+                instructions::set_vesting_schedule(&sale.owner, &sale.name, Some(vesting)),
+            );
+        }
+
+        // Sequences start with registration and the sale open, so that most of them get to purchases
+        sale.update_state(&mut svm, &sale.owner, true, true)
+            .unwrap();
+
+        let buyers = (0..NUM_BUYERS)
+            .map(|_| {
+                let buyer = Pubkey::new_unique();
+                svm.airdrop(&buyer, 1_000 * LAMPORTS_PER_SOL);
+                buyer
+            })
+            .collect();
+
+        let deposit = svm.token_balance(&sale.vault());
+        let last_state = svm.sale(&sale.name);
+        let sale_rent =
+            Rent::default().minimum_balance(svm.account(&sale.address()).unwrap().data.len());
+
+        Self {
+            svm,
+            sale,
+            buyers,
+            deposit,
+            last_state,
+            sale_rent,
+            fee_recipient,
+            fee_recipient_lamports: LAMPORTS_PER_SOL,
+            pricing: params.pricing,
+            refunded_tokens: vec![0; NUM_BUYERS],
+            refunded_lamports: vec![0; NUM_BUYERS],
+            rebated: 0,
+            rebates_paid: 0,
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        self.svm.account(&self.sale.address()).is_none()
+    }
+
+    fn apply(&mut self, action: &Action) {
+        let action = &self.with_claimant(*action);
+
+        // Refunds and rebates are tracked from the state they were claimed from
+        let allowance_before = match *action {
+            Action::ClaimRefund { buyer } => self.allowance(&self.buyers[buyer]),
+            _ => None,
+        };
+        let lamports_raised_before = self.last_state.lamports_raised;
+
+        let (svm, sale) = (&mut self.svm, &self.sale);
+        let fee_recipient = sale.fee_recipient.as_ref();
+
+        // Failing instructions leave every account untouched, so only the invariants matter
+        let result = match *action {
+            Action::Register { buyer, referrer } => sale.register_referred(
+                svm,
+                &self.buyers[buyer],
+                referrer.map(|referrer| &self.buyers[referrer]),
+            ),
+            Action::Buy { buyer, amount, .. } if self.pricing != Pricing::Commit => {
+                sale.buy(svm, &self.buyers[buyer], amount)
+            }
+            Action::Buy {
+                buyer,
+                amount,
+                with_excess,
+            } => {
+                let excess = if with_excess {
+                    sale.lamports_per_token / 2
+                } else {
+                    0
+                };
+                svm.process(
+                    instructions::commit(
+                        &self.buyers[buyer],
+                        &sale.name,
+                        None,
+                        amount * sale.lamports_per_token + excess,
+                    ),
+                    &[&self.buyers[buyer]],
+                )
+            }
+            Action::Finalize => svm.process(
+                instructions::finalize_sale(&sale.owner, &sale.name, None, fee_recipient),
+                &[&sale.owner],
+            ),
+            Action::SettleCommitment { buyer } => {
+                let referrer = sale.referrer(svm, &self.buyers[buyer]);
+                svm.process(
+                    instructions::settle_commitment(
+                        &self.buyers[buyer],
+                        &sale.name,
+                        &sale.token.mint,
+                        None,
+                        fee_recipient,
+                        referrer.as_ref(),
+                    ),
+                    &[&self.buyers[buyer]],
+                )
+            }
+            Action::ClaimVested { buyer } => svm.process(
+                instructions::claim_vested(&self.buyers[buyer], &sale.name, &sale.token, sale.mode),
+                &[&self.buyers[buyer]],
+            ),
+            Action::ClaimRefund { buyer } => svm.process(
+                instructions::claim_refund(&self.buyers[buyer], &sale.name, &sale.token.mint, None),
+                &[&self.buyers[buyer]],
+            ),
+            Action::ClaimRebate { buyer } => svm.process(
+                instructions::claim_rebate(&self.buyers[buyer], &sale.name, None),
+                &[&self.buyers[buyer]],
+            ),
+            Action::Warp { seconds } => {
+                svm.warp_to(svm.now() + seconds);
+                Ok(())
+            }
+            Action::UpdateSaleState {
+                is_registration_open,
+                is_sale_open,
+            } => sale.update_state(svm, &sale.owner, is_registration_open, is_sale_open),
+            Action::WithdrawProceeds => svm.process(
+                instructions::withdraw_proceeds(&sale.owner, &sale.name, &sale.owner, None),
+                &[&sale.owner],
+            ),
+            Action::ClaimReferralRewards { referrer } => svm.process(
+                instructions::claim_referral_rewards(&self.buyers[referrer], &sale.name, None),
+                &[&self.buyers[referrer]],
+            ),
+            Action::Close => sale.close(svm, &sale.owner),
+        };
+
+        if !self.is_closed() {
+            self.last_state = self.svm.sale(&self.sale.name);
+        }
+
+        if result.is_err() {
+            return;
+        }
+        match *action {
+            Action::ClaimRefund { buyer } => {
+                let allowance = allowance_before.unwrap();
+                let refund = self.svm.event::<RefundClaimed>();
+                assert_eq!(refund.amount, allowance.lamports_paid);
+
+                self.refunded_tokens[buyer] += allowance.tokens_held;
+                self.refunded_lamports[buyer] += refund.amount;
+            }
+            Action::ClaimRebate { .. } => {
+                let rebate = self.svm.event::<RebateClaimed>();
+                let rebated = lamports_raised_before - self.last_state.lamports_raised;
+                assert!(rebate.amount <= rebated);
+
+                self.rebated += rebated;
+                self.rebates_paid += rebate.amount;
+            }
+            _ => {}
+        }
+    }
+
+    /// Points claims at the buyers who registered, wrapping around, since the others have nothing to claim
+    fn with_claimant(&self, action: Action) -> Action {
+        let registered: Vec<usize> = (0..NUM_BUYERS)
+            .filter(|&i| self.allowance(&self.buyers[i]).is_some())
+            .collect();
+        if registered.is_empty() {
+            return action;
+        }
+        let claimant = |buyer: usize| registered[buyer % registered.len()];
+
+        match action {
+            Action::SettleCommitment { buyer } => Action::SettleCommitment {
+                buyer: claimant(buyer),
+            },
+            Action::ClaimVested { buyer } => Action::ClaimVested {
+                buyer: claimant(buyer),
+            },
+            Action::ClaimRefund { buyer } => Action::ClaimRefund {
+                buyer: claimant(buyer),
+            },
+            Action::ClaimRebate { buyer } => Action::ClaimRebate {
+                buyer: claimant(buyer),
+            },
+            action => action,
+        }
+    }
+
+    /// Tokens held by the buyers, in base units
+    fn delivered_tokens(&self) -> u64 {
+        self.buyers
+            .iter()
+            .map(|buyer| self.svm.token_balance(&self.sale.token_account(buyer)))
+            .sum()
+    }
+
+    fn check_invariants(&self) {
+        let state = &self.last_state;
+        let unit = 10_u64.pow(self.svm.mint(&self.sale.token.mint).decimals.into());
+        let delivered = self.delivered_tokens();
+
+        // Sold tokens are delivered, held back until buyers claim them, or were refunded
+        let refunded_tokens: u64 = self.refunded_tokens.iter().sum();
+        assert_eq!(
+            delivered + state.tokens_owed + refunded_tokens * unit,
+            state.tokens_sold * unit
+        );
+        assert!(state.tokens_sold <= state.total_tokens_for_sale);
+
+        match self.sale.mode {
+            SaleMode::Vault => {
+                // Unsold tokens go back to the owner when the sale is closed
+                let returned = self
+                    .svm
+                    .token_balance(&self.sale.token_account(&self.sale.owner));
+                assert_eq!(
+                    self.svm.token_balance(&self.sale.vault()) + delivered + returned,
+                    self.deposit
+                );
+                assert_eq!(self.deposit, state.total_tokens_for_sale * unit);
+            }
+            SaleMode::Mint => {
+                assert_eq!(self.svm.mint(&self.sale.token.mint).supply, delivered);
+            }
+        }
+
+        let mut tokens_bought = 0;
+        let mut lamports_paid = 0;
+        let mut tokens_owed = 0;
+        let mut tokens_claimed = 0;
+        let mut lamports_committed = 0;
+        let mut rewards_owed = 0;
+        let mut rewards_claimed = 0;
+        for (i, buyer) in self.buyers.iter().enumerate() {
+            if let Some(stats) = self.referrer_stats(buyer) {
+                rewards_owed += stats.rewards_earned - stats.rewards_claimed;
+                rewards_claimed += stats.rewards_claimed;
+            }
+
+            let Some(allowance) = self.allowance(buyer) else {
+                continue;
+            };
+            assert_ne!(allowance.referrer, Some(*buyer));
+            if let Some(referrer) = allowance.referrer {
+                assert!(self.allowance(&referrer).is_some());
+            }
+            assert!(allowance.tokens_bought <= state.max_tokens_per_buyer);
+            assert_eq!(
+                self.svm.token_balance(&self.sale.token_account(buyer)),
+                (allowance.tokens_bought - allowance.tokens_held - self.refunded_tokens[i]) * unit
+                    + allowance.tokens_claimed
+            );
+            tokens_bought += allowance.tokens_bought;
+            lamports_paid += allowance.lamports_paid + self.refunded_lamports[i];
+            tokens_owed += allowance.tokens_held * unit - allowance.tokens_claimed;
+            tokens_claimed += allowance.tokens_claimed;
+            lamports_committed += allowance.lamports_committed;
+
+            // Auction buyers pay the price at the time of purchase, until they claim their rebate
+            if self.pricing != Pricing::UniformAuction {
+                assert_eq!(
+                    allowance.lamports_paid + self.refunded_lamports[i],
+                    state.lamports_per_token * allowance.tokens_bought
+                );
+            }
+        }
+
+        assert_eq!(tokens_bought, state.tokens_sold);
+        assert_eq!(lamports_paid, state.lamports_raised);
+        assert_eq!(tokens_owed, state.tokens_owed);
+        assert_eq!(tokens_claimed, state.tokens_claimed);
+        assert_eq!(lamports_committed, state.committed_unsettled);
+        assert!(state.num_buyers <= state.max_buyers);
+        assert_eq!(rewards_owed, state.referral_rewards_owed);
+
+        // Fees are paid out at purchase or settlement, or held back until the soft cap is finalized, and the rest
+        // stays with the sale until it's withdrawn, claimed by referrers, refunded or rebated
+        let refunded_lamports: u64 = self.refunded_lamports.iter().sum();
+        assert!(
+            state.fees_paid + state.fees_owed
+                <= state.fee_for(lamports_paid + self.rebated).unwrap()
+        );
+        if state.soft_cap == 0 || state.is_finalized {
+            assert_eq!(state.fees_owed, 0);
+        }
+        assert_eq!(
+            self.svm.lamports(&self.fee_recipient),
+            self.fee_recipient_lamports + state.fees_paid
+        );
+        if !self.is_closed() {
+            assert_eq!(
+                self.svm.lamports(&self.sale.address()),
+                self.sale_rent + lamports_paid + self.rebated + state.committed_unsettled
+                    - state.fees_paid
+                    - state.lamports_withdrawn
+                    - rewards_claimed
+                    - refunded_lamports
+                    - self.rebates_paid
+            );
+        }
+    }
+
+    fn allowance(&self, buyer: &Pubkey) -> Option<Allowance> {
+        self.svm
+            .account(&find_allowance_address(&self.sale.name, buyer).0)?;
+
+        Some(self.svm.allowance(&self.sale.name, buyer))
+    }
+
+    fn referrer_stats(&self, referrer: &Pubkey) -> Option<ReferrerStats> {
+        self.svm
+            .account(&find_referrer_stats_address(&self.sale.name, referrer).0)?;
+
+        Some(self.svm.referrer_stats(&self.sale.name, referrer))
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(128))]
+
+    #[test]
+    fn tokens_and_lamports_are_conserved(
+        params in sale_params(),
+        mut actions in prop::collection::vec(action(), 1..100),
+        close in any::<bool>(),
+    ) {
+        let mut harness = Harness::new(&params);
+        harness.check_invariants();
+
+        let total_lamports = harness.svm.total_lamports();
+
+        // Nothing can happen to a closed sale, so closing it only ever ends a sequence
+        if close {
+            actions.push(Action::Close);
+        }
+
+        for action in &actions {
+            harness.apply(action);
+            harness.check_invariants();
+
+            // Lamports only ever move between accounts
+            prop_assert_eq!(harness.svm.total_lamports(), total_lamports);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::{assert_error, Svm, TestSale, LAMPORTS_PER_SOL};
+use whitelist_token_sale_client::{
+    events::{SaleFinalized, TokensClaimed},
+    instructions,
+    state::SaleMode,
+    WhitelistError,
+};
+
+const LAMPORTS_PER_TOKEN: u64 = 100_000_000;
+const SOFT_CAP: u64 = 4 * LAMPORTS_PER_TOKEN;
+
+#[test]
+fn anyone_can_finalize_a_successful_sale_after_the_deadline() {
+    let mut svm = Svm::new();
+    let sale = TestSale::create(
+        &mut svm,
+        "soft-cap",
+        SaleMode::Vault,
+        6,
+        LAMPORTS_PER_TOKEN,
+        10,
+        3,
+    )
+    .unwrap();
+
+    let deadline = svm.now() + 3_600;
+    svm.process(
+        instructions::set_soft_cap(&sale.owner, &sale.name, SOFT_CAP, deadline),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+    sale.buy(&mut svm, &buyer, 5).unwrap();
+
+    let stranger = Pubkey::new_unique();
+    svm.airdrop(&stranger, LAMPORTS_PER_SOL);
+    let finalize = |svm: &mut Svm| {
+        svm.process(
+            instructions::finalize_sale(&stranger, &sale.name, None, None),
+            &[&stranger],
+        )
+    };
+
+    // Until the deadline, finalizing is up to the owner
+    assert_error(finalize(&mut svm), WhitelistError::OnlyOwner);
+
+    svm.warp_to(deadline);
+    finalize(&mut svm).unwrap();
+    assert!(svm.sale(&sale.name).is_finalized);
+
+    let finalized = svm.event::<SaleFinalized>();
+    assert_eq!(finalized.sale, sale.address());
+    assert_eq!(finalized.actor, stranger);
+    assert_eq!(finalized.tokens_sold, 5);
+    assert_eq!(finalized.lamports_raised, 5 * LAMPORTS_PER_TOKEN);
+    assert_eq!(finalized.timestamp, deadline);
+
+    svm.process(
+        instructions::claim_vested(&buyer, &sale.name, &sale.token, sale.mode),
+        &[&buyer],
+    )
+    .unwrap();
+    assert_eq!(svm.token_balance(&sale.token_account(&buyer)), 5_000_000);
+
+    let claimed = svm.event::<TokensClaimed>();
+    assert_eq!(claimed.buyer, buyer);
+    assert_eq!(claimed.amount, 5_000_000);
+    assert_eq!(claimed.buyer_tokens_claimed, 5_000_000);
+    assert_eq!(claimed.tokens_owed, 0);
+}
+
+#[test]
+fn sales_that_missed_the_soft_cap_cant_be_finalized() {
+    let mut svm = Svm::new();
+    let sale = TestSale::create(
+        &mut svm,
+        "soft-cap",
+        SaleMode::Mint,
+        6,
+        LAMPORTS_PER_TOKEN,
+        10,
+        3,
+    )
+    .unwrap();
+
+    let deadline = svm.now() + 3_600;
+    svm.process(
+        instructions::set_soft_cap(&sale.owner, &sale.name, SOFT_CAP, deadline),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+    sale.buy(&mut svm, &buyer, 3).unwrap();
+
+    svm.warp_to(deadline);
+    assert_error(
+        svm.process(
+            instructions::finalize_sale(&buyer, &sale.name, None, None),
+            &[&buyer],
+        ),
+        WhitelistError::SoftCapNotReached,
+    );
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token_2022::spl_token_2022;
+use common::{Svm, TestSale};
+use whitelist_token_sale_client::state::SaleMode;
+
+const LAMPORTS_PER_TOKEN: u64 = 50_000_000;
+
+fn create_sale(svm: &mut Svm, mode: SaleMode) -> TestSale {
+    TestSale::create_token_2022(svm, "token-2022", mode, 6, LAMPORTS_PER_TOKEN, 10, 3).unwrap()
+}
+
+#[test]
+fn vault_sales_of_token_2022_mints_deliver_and_return_tokens() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm, SaleMode::Vault);
+    assert_eq!(
+        svm.account(&sale.vault()).unwrap().owner,
+        spl_token_2022::ID
+    );
+
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+    sale.buy(&mut svm, &buyer, 4).unwrap();
+
+    let buyer_tokens = sale.token_account(&buyer);
+    assert_eq!(
+        svm.account(&buyer_tokens).unwrap().owner,
+        spl_token_2022::ID
+    );
+    assert_eq!(svm.token_balance(&buyer_tokens), 4_000_000);
+    assert_eq!(svm.token_balance(&sale.vault()), 26_000_000);
+
+    sale.close(&mut svm, &sale.owner).unwrap();
+
+    assert!(svm.account(&sale.address()).is_none());
+    assert_eq!(svm.token_balance(&sale.vault()), 0);
+    assert_eq!(
+        svm.token_balance(&sale.token_account(&sale.owner)),
+        26_000_000
+    );
+}
+
+#[test]
+fn mint_sales_of_token_2022_mints_mint_and_hand_back_the_authority() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm, SaleMode::Mint);
+    assert_eq!(
+        svm.mint(&sale.token.mint).mint_authority,
+        COption::Some(sale.address())
+    );
+
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+    sale.buy(&mut svm, &buyer, 7).unwrap();
+
+    assert_eq!(svm.token_balance(&sale.token_account(&buyer)), 7_000_000);
+    assert_eq!(svm.mint(&sale.token.mint).supply, 7_000_000);
+
+    sale.close(&mut svm, &sale.owner).unwrap();
+
+    assert_eq!(
+        svm.mint(&sale.token.mint).mint_authority,
+        COption::Some(sale.owner)
+    );
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use anchor_lang::{prelude::Pubkey, solana_program::entrypoint::ProgramResult};
+use common::{assert_error, Svm, TestSale};
+use whitelist_token_sale_client::{
+    instructions,
+    state::{SaleMode, VestingSchedule},
+    WhitelistError,
+};
+
+const LAMPORTS_PER_TOKEN: u64 = 100_000_000;
+const DURATION: i64 = 1_000;
+
+/// A sale unlocking 25% of the tokens at TGE, and the rest linearly over `DURATION` seconds
+fn create_sale(svm: &mut Svm) -> (TestSale, VestingSchedule) {
+    let sale =
+        TestSale::create(svm, "vesting", SaleMode::Mint, 6, LAMPORTS_PER_TOKEN, 10, 3).unwrap();
+
+    let vesting = VestingSchedule {
+        tge_timestamp: svm.now() + 100,
+        tge_unlock_bps: 2_500,
+        cliff_seconds: 0,
+        duration_seconds: DURATION,
+    };
+    set_vesting(svm, &sale, Some(vesting)).unwrap();
+
+    (sale, vesting)
+}
+
+fn set_vesting(svm: &mut Svm, sale: &TestSale, vesting: Option<VestingSchedule>) -> ProgramResult {
+    svm.process(
+        instructions::set_vesting_schedule(&sale.owner, &sale.name, vesting),
+        &[&sale.owner],
+    )
+}
+
+fn claim(svm: &mut Svm, sale: &TestSale, buyer: &Pubkey) -> ProgramResult {
+    svm.process(
+        instructions::claim_vested(buyer, &sale.name, &sale.token, sale.mode),
+        &[buyer],
+    )
+}
+
+#[test]
+fn buyers_claim_tokens_as_they_unlock() {
+    let mut svm = Svm::new();
+    let (sale, vesting) = create_sale(&mut svm);
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+    sale.buy(&mut svm, &buyer, 4).unwrap();
+
+    assert_eq!(svm.token_balance(&sale.token_account(&buyer)), 0);
+    assert_error(
+        claim(&mut svm, &sale, &buyer),
+        WhitelistError::NothingToClaim,
+    );
+
+    svm.warp_to(vesting.tge_timestamp);
+    claim(&mut svm, &sale, &buyer).unwrap();
+    assert_eq!(svm.token_balance(&sale.token_account(&buyer)), 1_000_000);
+
+    svm.warp_to(vesting.tge_timestamp + DURATION / 2);
+    claim(&mut svm, &sale, &buyer).unwrap();
+    assert_eq!(svm.token_balance(&sale.token_account(&buyer)), 2_500_000);
+
+    svm.warp_to(vesting.tge_timestamp + DURATION);
+    claim(&mut svm, &sale, &buyer).unwrap();
+    assert_eq!(svm.token_balance(&sale.token_account(&buyer)), 4_000_000);
+
+    let state = svm.sale(&sale.name);
+    assert_eq!(state.tokens_owed, 0);
+    assert_eq!(state.tokens_claimed, 4_000_000);
+}
+
+#[test]
+fn the_schedule_is_locked_once_tokens_are_held_back_or_claimed() {
+    let mut svm = Svm::new();
+    let (sale, vesting) = create_sale(&mut svm);
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+
+    // Nothing is held back yet, so the owner can still change the schedule
+    let longer_vesting = VestingSchedule {
+        duration_seconds: 10 * DURATION,
+        ..vesting
+    };
+    set_vesting(&mut svm, &sale, Some(longer_vesting)).unwrap();
+    set_vesting(&mut svm, &sale, Some(vesting)).unwrap();
+
+    sale.buy(&mut svm, &buyer, 4).unwrap();
+    assert_error(
+        set_vesting(&mut svm, &sale, Some(longer_vesting)),
+        WhitelistError::VestingLocked,
+    );
+
+    svm.warp_to(vesting.tge_timestamp + DURATION);
+    claim(&mut svm, &sale, &buyer).unwrap();
+    assert_eq!(svm.sale(&sale.name).tokens_owed, 0);
+
+    // A longer schedule would unlock less than the buyer already claimed
+    assert_error(
+        set_vesting(&mut svm, &sale, Some(longer_vesting)),
+        WhitelistError::VestingLocked,
+    );
+    assert_eq!(svm.allowance(&sale.name, &buyer).tokens_claimed, 4_000_000);
+}
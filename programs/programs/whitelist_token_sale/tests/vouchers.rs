@@ -0,0 +1,211 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::{entrypoint::ProgramResult, program_error::ProgramError};
+use common::{assert_error, Svm, TestSale};
+use solana_sdk::signature::{Keypair, Signer};
+use whitelist_token_sale_client::{
+    instructions,
+    pda::find_allowance_address,
+    state::{SaleMode, WhitelistMode},
+    voucher::Voucher,
+    WhitelistError,
+};
+
+const LAMPORTS_PER_TOKEN: u64 = 1_000_000;
+
+fn create_sale(svm: &mut Svm, whitelist_signer: &Keypair) -> TestSale {
+    let sale = TestSale::create(
+        svm,
+        "vouchers",
+        SaleMode::Vault,
+        6,
+        LAMPORTS_PER_TOKEN,
+        10,
+        3,
+    )
+    .unwrap();
+
+    svm.process(
+        instructions::set_whitelist_mode(&sale.owner, &sale.name, WhitelistMode::Voucher),
+        &[&sale.owner],
+    )
+    .unwrap();
+    svm.process(
+        instructions::set_whitelist_signer(&sale.owner, &sale.name, &whitelist_signer.pubkey()),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    sale
+}
+
+fn register_with_voucher(
+    svm: &mut Svm,
+    sale: &TestSale,
+    buyer: &Pubkey,
+    signer: &Keypair,
+    voucher: &Voucher,
+) -> ProgramResult {
+    let signature = signer.sign_message(&voucher.to_message());
+
+    svm.process_transaction(
+        &[
+            instructions::verify_voucher(
+                &signer.pubkey(),
+                signature.as_ref().try_into().unwrap(),
+                voucher,
+            ),
+            instructions::register_for_whitelist(buyer, &sale.name, None),
+        ],
+        &[buyer],
+    )
+}
+
+#[test]
+fn a_signed_voucher_registers_the_buyer_with_its_allocation() {
+    let mut svm = Svm::new();
+    let whitelist_signer = Keypair::new();
+    let sale = create_sale(&mut svm, &whitelist_signer);
+    let buyer = svm.funded_wallet();
+
+    let voucher = Voucher {
+        sale: sale.address(),
+        buyer,
+        allocation: 5,
+        expires_at: svm.now() + 60,
+    };
+    register_with_voucher(&mut svm, &sale, &buyer, &whitelist_signer, &voucher).unwrap();
+
+    let allowance = svm.allowance(&sale.name, &buyer);
+    assert!(allowance.is_approved);
+    assert_eq!(allowance.allocation, Some(5));
+
+    sale.open_sale(&mut svm);
+    assert_error(
+        sale.buy(&mut svm, &buyer, 6),
+        WhitelistError::AllowanceExceeded,
+    );
+    sale.buy(&mut svm, &buyer, 5).unwrap();
+}
+
+#[test]
+fn vouchers_have_to_be_signed_by_the_whitelist_signer() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm, &Keypair::new());
+    let buyer = svm.funded_wallet();
+
+    let voucher = Voucher {
+        sale: sale.address(),
+        buyer,
+        allocation: 5,
+        expires_at: svm.now() + 60,
+    };
+
+    assert_error(
+        register_with_voucher(&mut svm, &sale, &buyer, &Keypair::new(), &voucher),
+        WhitelistError::InvalidVoucher,
+    );
+    assert!(svm
+        .account(&find_allowance_address(&sale.name, &buyer).0)
+        .is_none());
+}
+
+#[test]
+fn vouchers_are_bound_to_their_buyer_and_sale() {
+    let mut svm = Svm::new();
+    let whitelist_signer = Keypair::new();
+    let sale = create_sale(&mut svm, &whitelist_signer);
+    let buyer = svm.funded_wallet();
+    let other_buyer = svm.funded_wallet();
+
+    let voucher = Voucher {
+        sale: sale.address(),
+        buyer: other_buyer,
+        allocation: 5,
+        expires_at: svm.now() + 60,
+    };
+    assert_error(
+        register_with_voucher(&mut svm, &sale, &buyer, &whitelist_signer, &voucher),
+        WhitelistError::InvalidVoucher,
+    );
+
+    let voucher = Voucher {
+        sale: Pubkey::new_unique(),
+        buyer,
+        allocation: 5,
+        expires_at: svm.now() + 60,
+    };
+    assert_error(
+        register_with_voucher(&mut svm, &sale, &buyer, &whitelist_signer, &voucher),
+        WhitelistError::InvalidVoucher,
+    );
+}
+
+#[test]
+fn expired_vouchers_are_rejected() {
+    let mut svm = Svm::new();
+    let whitelist_signer = Keypair::new();
+    let sale = create_sale(&mut svm, &whitelist_signer);
+    let buyer = svm.funded_wallet();
+
+    let voucher = Voucher {
+        sale: sale.address(),
+        buyer,
+        allocation: 5,
+        expires_at: svm.now() - 1,
+    };
+
+    assert_error(
+        register_with_voucher(&mut svm, &sale, &buyer, &whitelist_signer, &voucher),
+        WhitelistError::VoucherExpired,
+    );
+}
+
+#[test]
+fn registering_needs_a_voucher() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm, &Keypair::new());
+    let buyer = svm.funded_wallet();
+
+    assert_error(
+        sale.register(&mut svm, &buyer),
+        WhitelistError::InvalidVoucher,
+    );
+}
+
+#[test]
+fn tampered_vouchers_fail_signature_verification() {
+    let mut svm = Svm::new();
+    let whitelist_signer = Keypair::new();
+    let sale = create_sale(&mut svm, &whitelist_signer);
+    let buyer = svm.funded_wallet();
+
+    let voucher = Voucher {
+        sale: sale.address(),
+        buyer,
+        allocation: 5,
+        expires_at: svm.now() + 60,
+    };
+    let signature = whitelist_signer.sign_message(&voucher.to_message());
+    let inflated = Voucher {
+        allocation: 500,
+        ..voucher
+    };
+
+    // The Ed25519 program rejects the transaction before the sale program runs
+    assert_eq!(
+        svm.process_transaction(
+            &[
+                instructions::verify_voucher(
+                    &whitelist_signer.pubkey(),
+                    signature.as_ref().try_into().unwrap(),
+                    &inflated,
+                ),
+                instructions::register_for_whitelist(&buyer, &sale.name, None),
+            ],
+            &[&buyer],
+        ),
+        Err(ProgramError::InvalidInstructionData)
+    );
+}
@@ -0,0 +1,170 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::{assert_error, Svm, TestSale, LAMPORTS_PER_SOL};
+use whitelist_token_sale_client::{instructions, state::SaleMode, WhitelistError};
+
+const LAMPORTS_PER_TOKEN: u64 = 1_000_000;
+
+fn create_sale(svm: &mut Svm, mode: SaleMode, max_buyers: u64) -> TestSale {
+    TestSale::create(svm, "errors", mode, 6, LAMPORTS_PER_TOKEN, 10, max_buyers).unwrap()
+}
+
+#[test]
+fn only_the_owner_or_operator_can_update_the_sale_state() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm, SaleMode::Vault, 2);
+
+    let stranger = Pubkey::new_unique();
+    svm.airdrop(&stranger, LAMPORTS_PER_SOL);
+
+    assert_error(
+        sale.update_state(&mut svm, &stranger, false, true),
+        WhitelistError::OnlyOperator,
+    );
+    assert!(!svm.sale(&sale.name).is_sale_open);
+
+    sale.update_state(&mut svm, &sale.owner, false, true)
+        .unwrap();
+    assert!(svm.sale(&sale.name).is_sale_open);
+}
+
+#[test]
+fn only_the_owner_can_close_the_sale() {
+    for mode in [SaleMode::Vault, SaleMode::Mint] {
+        let mut svm = Svm::new();
+        let sale = create_sale(&mut svm, mode, 2);
+
+        let stranger = Pubkey::new_unique();
+        svm.airdrop(&stranger, LAMPORTS_PER_SOL);
+        // Vault sales send the unsold tokens to the signer's token account
+        svm.mint_tokens(&sale.token, &stranger, 0);
+
+        assert_error(sale.close(&mut svm, &stranger), WhitelistError::OnlyOwner);
+        assert!(svm.account(&sale.address()).is_some());
+    }
+}
+
+#[test]
+fn buyers_cant_register_once_registration_is_closed() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm, SaleMode::Vault, 2);
+    sale.update_state(&mut svm, &sale.owner, false, false)
+        .unwrap();
+
+    let buyer = Pubkey::new_unique();
+    svm.airdrop(&buyer, LAMPORTS_PER_SOL);
+
+    assert_error(
+        sale.register(&mut svm, &buyer),
+        WhitelistError::WhitelistRegistrationClosed,
+    );
+    assert_eq!(svm.sale(&sale.name).num_buyers, 0);
+}
+
+#[test]
+fn buyers_cant_buy_while_the_sale_is_closed() {
+    for mode in [SaleMode::Vault, SaleMode::Mint] {
+        let mut svm = Svm::new();
+        let sale = create_sale(&mut svm, mode, 2);
+        let buyer = sale.new_buyer(&mut svm);
+
+        // Not opened yet
+        assert_error(sale.buy(&mut svm, &buyer, 1), WhitelistError::SaleClosed);
+
+        sale.open_sale(&mut svm);
+        sale.buy(&mut svm, &buyer, 1).unwrap();
+
+        // Closed again
+        sale.update_state(&mut svm, &sale.owner, false, false)
+            .unwrap();
+        assert_error(sale.buy(&mut svm, &buyer, 1), WhitelistError::SaleClosed);
+
+        assert_eq!(svm.sale(&sale.name).tokens_sold, 1);
+    }
+}
+
+#[test]
+fn buyers_cant_buy_more_than_their_allowance() {
+    for mode in [SaleMode::Vault, SaleMode::Mint] {
+        let mut svm = Svm::new();
+        let sale = create_sale(&mut svm, mode, 2);
+        let buyer = sale.new_buyer(&mut svm);
+        sale.open_sale(&mut svm);
+
+        assert_error(
+            sale.buy(&mut svm, &buyer, 11),
+            WhitelistError::AllowanceExceeded,
+        );
+
+        // The allowance holds across purchases
+        sale.buy(&mut svm, &buyer, 6).unwrap();
+        assert_error(
+            sale.buy(&mut svm, &buyer, 5),
+            WhitelistError::AllowanceExceeded,
+        );
+        sale.buy(&mut svm, &buyer, 4).unwrap();
+
+        assert_eq!(svm.allowance(&sale.name, &buyer).tokens_bought, 10);
+        assert_eq!(svm.token_balance(&sale.token_account(&buyer)), 10_000_000);
+    }
+}
+
+#[test]
+fn registration_stops_at_the_buyer_limit() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm, SaleMode::Vault, 2);
+    sale.new_buyer(&mut svm);
+    sale.new_buyer(&mut svm);
+
+    let buyer = Pubkey::new_unique();
+    svm.airdrop(&buyer, LAMPORTS_PER_SOL);
+
+    assert_error(
+        sale.register(&mut svm, &buyer),
+        WhitelistError::BuyerLimitReached,
+    );
+    assert_eq!(svm.sale(&sale.name).num_buyers, 2);
+}
+
+#[test]
+fn buyers_have_to_be_registered() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm, SaleMode::Vault, 2);
+    sale.open_sale(&mut svm);
+
+    let buyer = Pubkey::new_unique();
+    svm.airdrop(&buyer, LAMPORTS_PER_SOL);
+
+    assert_error(
+        sale.buy(&mut svm, &buyer, 1),
+        WhitelistError::NotWhitelisted,
+    );
+}
+
+#[test]
+fn purchases_have_to_use_the_sale_mint() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm, SaleMode::Mint, 2);
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+
+    let other_token = svm.create_mint(&buyer, 6);
+
+    assert_error(
+        svm.process(
+            instructions::buy_tokens(
+                &buyer,
+                &sale.name,
+                &other_token,
+                None,
+                None,
+                None,
+                sale.mode,
+                1,
+            ),
+            &[&buyer],
+        ),
+        WhitelistError::MintMismatch,
+    );
+}
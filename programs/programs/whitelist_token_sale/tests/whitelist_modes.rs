@@ -0,0 +1,209 @@
+mod common;
+
+use anchor_lang::solana_program::{pubkey::Pubkey, sysvar};
+use common::{assert_error, Account, Svm, TestSale, LAMPORTS_PER_SOL};
+use whitelist_token_sale_client::{
+    events::{BuyerRegistered, LotteryTicketSettled},
+    instructions, merkle,
+    pda::find_allowance_address,
+    state::{SaleMode, WhitelistMode},
+    RandomnessSource, WhitelistError,
+};
+
+const LAMPORTS_PER_TOKEN: u64 = 1_000_000;
+
+fn create_sale(svm: &mut Svm, whitelist_mode: WhitelistMode) -> TestSale {
+    let sale =
+        TestSale::create(svm, "modes", SaleMode::Vault, 6, LAMPORTS_PER_TOKEN, 10, 3).unwrap();
+
+    svm.process(
+        instructions::set_whitelist_mode(&sale.owner, &sale.name, whitelist_mode),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    sale
+}
+
+#[test]
+fn buyers_register_with_a_merkle_proof() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm, WhitelistMode::Merkle);
+
+    let buyer = svm.funded_wallet();
+    let other_buyer = svm.funded_wallet();
+    let buyer_leaf = merkle::leaf(&buyer, 4_000_000);
+    let other_leaf = merkle::leaf(&other_buyer, 0);
+    svm.process(
+        instructions::update_merkle_root(
+            &sale.owner,
+            &sale.name,
+            merkle::node(&buyer_leaf, &other_leaf),
+        ),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    // A proof for another allocation doesn't lead to the root
+    assert_error(
+        svm.process(
+            instructions::register_with_merkle_proof(
+                &buyer,
+                &sale.name,
+                10_000_000,
+                vec![other_leaf],
+            ),
+            &[&buyer],
+        ),
+        WhitelistError::InvalidMerkleProof,
+    );
+
+    svm.process(
+        instructions::register_with_merkle_proof(&buyer, &sale.name, 4_000_000, vec![other_leaf]),
+        &[&buyer],
+    )
+    .unwrap();
+
+    let allowance = svm.allowance(&sale.name, &buyer);
+    assert!(allowance.is_approved);
+    assert_eq!(allowance.allocation, Some(4_000_000));
+
+    let event = svm.event::<BuyerRegistered>();
+    assert_eq!(event.sale, sale.address());
+    assert_eq!(event.buyer, buyer);
+    assert!(event.is_approved);
+    assert_eq!(event.allocation, Some(4_000_000));
+    assert_eq!(event.referrer, None);
+    assert_eq!(event.num_buyers, 1);
+    assert_eq!(event.timestamp, svm.now());
+}
+
+#[test]
+fn lottery_tickets_settle_to_the_drawn_winners() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm, WhitelistMode::Lottery);
+    svm.process(
+        instructions::set_randomness_source(&sale.owner, &sale.name, RandomnessSource::SlotHashes),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    let buyers: Vec<Pubkey> = (0..6).map(|_| svm.funded_wallet()).collect();
+    for (ticket, buyer) in buyers.iter().enumerate() {
+        sale.register(&mut svm, buyer).unwrap();
+
+        let event = svm.event::<BuyerRegistered>();
+        assert!(!event.is_approved);
+        assert_eq!(event.num_buyers, 0);
+        assert_eq!(event.num_tickets, ticket as u64 + 1);
+    }
+
+    // One entry of the sysvar: its length, then the most recent slot and its hash
+    let mut slot_hashes = 1_u64.to_le_bytes().to_vec();
+    slot_hashes.extend_from_slice(&7_u64.to_le_bytes());
+    slot_hashes.extend_from_slice(&[42; 32]);
+    svm.set_account(
+        sysvar::slot_hashes::ID,
+        Account {
+            lamports: 1,
+            data: slot_hashes,
+            owner: sysvar::ID,
+            executable: false,
+        },
+    );
+
+    sale.update_state(&mut svm, &sale.owner, false, false)
+        .unwrap();
+    svm.process(
+        instructions::draw_lottery(&sale.owner, &sale.name, &sysvar::slot_hashes::ID),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    let mut winners = 0;
+    for (ticket, buyer) in buyers.iter().enumerate() {
+        svm.process(
+            instructions::settle_lottery_ticket(buyer, &sale.name),
+            &[buyer],
+        )
+        .unwrap();
+
+        let event = svm.event::<LotteryTicketSettled>();
+        assert_eq!(event.sale, sale.address());
+        assert_eq!(event.buyer, *buyer);
+        assert_eq!(event.ticket_number, ticket as u64);
+
+        // Losing tickets are closed, winning ones approved
+        if event.is_winner {
+            winners += 1;
+            assert!(svm.allowance(&sale.name, buyer).is_approved);
+        } else {
+            let allowance = find_allowance_address(&sale.name, buyer).0;
+            assert!(svm.account(&allowance).is_none());
+        }
+        assert_eq!(event.num_buyers, winners);
+    }
+
+    assert_eq!(winners, 3);
+    assert_eq!(svm.sale(&sale.name).num_buyers, 3);
+}
+
+#[test]
+fn lottery_draws_need_the_oracle_programs_account() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm, WhitelistMode::Lottery);
+
+    let oracle_program = Pubkey::new_unique();
+    let randomness_account = Pubkey::new_unique();
+    let randomness_source = RandomnessSource::Oracle {
+        program: oracle_program,
+        account: randomness_account,
+        offset: 8,
+    };
+    svm.process(
+        instructions::set_randomness_source(&sale.owner, &sale.name, randomness_source),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    let buyer = svm.funded_wallet();
+    sale.register(&mut svm, &buyer).unwrap();
+
+    // Ticket holders keep the source they registered under
+    assert_error(
+        svm.process(
+            instructions::set_randomness_source(
+                &sale.owner,
+                &sale.name,
+                RandomnessSource::SlotHashes,
+            ),
+            &[&sale.owner],
+        ),
+        WhitelistError::RandomnessSourceLocked,
+    );
+
+    sale.update_state(&mut svm, &sale.owner, false, false)
+        .unwrap();
+    let draw = instructions::draw_lottery(&sale.owner, &sale.name, &randomness_account);
+
+    let mut randomness = vec![0; 8];
+    randomness.extend_from_slice(&[7; 32]);
+    let mut account = Account {
+        lamports: LAMPORTS_PER_SOL,
+        data: randomness,
+        owner: Pubkey::new_unique(),
+        executable: false,
+    };
+
+    // The same bytes written by any other program aren't the oracle's
+    svm.set_account(randomness_account, account.clone());
+    assert_error(
+        svm.process(draw.clone(), &[&sale.owner]),
+        WhitelistError::InvalidRandomnessSource,
+    );
+
+    account.owner = oracle_program;
+    svm.set_account(randomness_account, account);
+    svm.process(draw, &[&sale.owner]).unwrap();
+    assert!(svm.sale(&sale.name).is_lottery_drawn);
+}
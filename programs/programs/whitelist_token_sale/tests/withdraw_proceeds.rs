@@ -0,0 +1,166 @@
+mod common;
+
+use anchor_lang::{
+    prelude::{Pubkey, Rent},
+    solana_program::entrypoint::ProgramResult,
+};
+use common::{assert_error, Svm, TestSale, LAMPORTS_PER_SOL};
+use whitelist_token_sale_client::{
+    events::ProceedsWithdrawn, instructions, state::SaleMode, WhitelistError,
+};
+
+const LAMPORTS_PER_TOKEN: u64 = 100_000_000;
+
+fn create_sale(svm: &mut Svm) -> TestSale {
+    TestSale::create(
+        svm,
+        "proceeds",
+        SaleMode::Vault,
+        6,
+        LAMPORTS_PER_TOKEN,
+        10,
+        3,
+    )
+    .unwrap()
+}
+
+fn withdraw(svm: &mut Svm, sale: &TestSale, signer: &Pubkey, treasury: &Pubkey) -> ProgramResult {
+    svm.process(
+        instructions::withdraw_proceeds(signer, &sale.name, treasury, None),
+        &[signer],
+    )
+}
+
+fn rent_exempt_minimum(svm: &Svm, sale: &TestSale) -> u64 {
+    Rent::default().minimum_balance(svm.account(&sale.address()).unwrap().data.len())
+}
+
+#[test]
+fn proceeds_are_withdrawn_to_the_owner_by_default() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm);
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+    sale.buy(&mut svm, &buyer, 3).unwrap();
+
+    let owner_lamports = svm.lamports(&sale.owner);
+    withdraw(&mut svm, &sale, &sale.owner, &sale.owner).unwrap();
+
+    assert_eq!(
+        svm.lamports(&sale.owner),
+        owner_lamports + 3 * LAMPORTS_PER_TOKEN
+    );
+    assert_eq!(
+        svm.lamports(&sale.address()),
+        rent_exempt_minimum(&svm, &sale)
+    );
+    assert_eq!(
+        svm.sale(&sale.name).lamports_withdrawn,
+        3 * LAMPORTS_PER_TOKEN
+    );
+
+    let withdrawn = svm.event::<ProceedsWithdrawn>();
+    assert_eq!(withdrawn.sale, sale.address());
+    assert_eq!(withdrawn.treasury, sale.owner);
+    assert_eq!(withdrawn.amount, 3 * LAMPORTS_PER_TOKEN);
+    assert_eq!(withdrawn.lamports_withdrawn, 3 * LAMPORTS_PER_TOKEN);
+
+    // Only new proceeds can be withdrawn afterwards
+    assert_error(
+        withdraw(&mut svm, &sale, &sale.owner, &sale.owner),
+        WhitelistError::NothingToWithdraw,
+    );
+
+    sale.buy(&mut svm, &buyer, 2).unwrap();
+    withdraw(&mut svm, &sale, &sale.owner, &sale.owner).unwrap();
+
+    let state = svm.sale(&sale.name);
+    assert_eq!(state.lamports_withdrawn, 5 * LAMPORTS_PER_TOKEN);
+    assert_eq!(state.lamports_raised, 5 * LAMPORTS_PER_TOKEN);
+
+    // The sale goes on, and can still be closed
+    assert!(state.is_sale_open);
+    sale.close(&mut svm, &sale.owner).unwrap();
+}
+
+#[test]
+fn proceeds_go_to_the_configured_treasury() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm);
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+    sale.buy(&mut svm, &buyer, 1).unwrap();
+
+    let treasury = Pubkey::new_unique();
+    svm.airdrop(&treasury, LAMPORTS_PER_SOL);
+
+    let stranger = Pubkey::new_unique();
+    svm.airdrop(&stranger, LAMPORTS_PER_SOL);
+    assert_error(
+        svm.process(
+            instructions::set_treasury(&stranger, &sale.name, &stranger),
+            &[&stranger],
+        ),
+        WhitelistError::OnlyOwner,
+    );
+
+    svm.process(
+        instructions::set_treasury(&sale.owner, &sale.name, &treasury),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    assert_error(
+        withdraw(&mut svm, &sale, &sale.owner, &sale.owner),
+        WhitelistError::TreasuryMismatch,
+    );
+    assert_error(
+        withdraw(&mut svm, &sale, &stranger, &treasury),
+        WhitelistError::OnlyOwner,
+    );
+
+    withdraw(&mut svm, &sale, &sale.owner, &treasury).unwrap();
+    assert_eq!(
+        svm.lamports(&treasury),
+        LAMPORTS_PER_SOL + LAMPORTS_PER_TOKEN
+    );
+}
+
+#[test]
+fn refundable_payments_stay_with_the_sale() {
+    let mut svm = Svm::new();
+    let sale = create_sale(&mut svm);
+
+    svm.process(
+        instructions::set_soft_cap(
+            &sale.owner,
+            &sale.name,
+            4 * LAMPORTS_PER_TOKEN,
+            svm.now() + 3_600,
+        ),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    let buyer = sale.new_buyer(&mut svm);
+    sale.open_sale(&mut svm);
+    sale.buy(&mut svm, &buyer, 5).unwrap();
+
+    // Buyers get everything back if the sale isn't finalized
+    assert_error(
+        withdraw(&mut svm, &sale, &sale.owner, &sale.owner),
+        WhitelistError::NothingToWithdraw,
+    );
+
+    svm.process(
+        instructions::finalize_sale(&sale.owner, &sale.name, None, None),
+        &[&sale.owner],
+    )
+    .unwrap();
+
+    withdraw(&mut svm, &sale, &sale.owner, &sale.owner).unwrap();
+    assert_eq!(
+        svm.sale(&sale.name).lamports_withdrawn,
+        5 * LAMPORTS_PER_TOKEN
+    );
+}
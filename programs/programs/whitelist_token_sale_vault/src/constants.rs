@@ -1,7 +0,0 @@
-use anchor_lang::prelude::*;
-
-#[constant]
-pub const PDA_SEED_SALE: &[u8; 4] = b"sale";
-
-#[constant]
-pub const PDA_SEED_ALLOWANCE: &[u8; 9] = b"allowance";
@@ -1,11 +0,0 @@
-pub mod buy_tokens;
-pub mod close_whitelist_sale;
-pub mod create_whitelist_sale;
-pub mod register_for_whitelist;
-pub mod update_sale_state;
-
-pub use buy_tokens::*;
-pub use close_whitelist_sale::*;
-pub use create_whitelist_sale::*;
-pub use register_for_whitelist::*;
-pub use update_sale_state::*;
@@ -1,19 +1,26 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program, getProvider, BN } from "@coral-xyz/anchor";
-import { WhitelistTokenSaleMint } from "../target/types/whitelist_token_sale_mint";
+import { WhitelistTokenSale } from "../target/types/whitelist_token_sale";
 import {
+  Ed25519Program,
   Keypair,
   PublicKey,
   LAMPORTS_PER_SOL,
+  SYSVAR_SLOT_HASHES_PUBKEY,
   SendTransactionError,
 } from "@solana/web3.js";
 import {
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
   createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
   getAccount,
   getAssociatedTokenAddress,
   getMint,
 } from "@solana/spl-token";
 import { assert } from "chai";
+import { createHash } from "crypto";
 
 describe("Mint Whitelist Token Sale - e2e story", () => {
   // PRELUDE
@@ -24,21 +31,60 @@ describe("Mint Whitelist Token Sale - e2e story", () => {
   const provider = getProvider();
 
   const program = anchor.workspace
-    .WhitelistTokenSaleMint as Program<WhitelistTokenSaleMint>;
+    .WhitelistTokenSale as Program<WhitelistTokenSale>;
 
   // Number of decimals for the token mint
   const DECIMALS = 6;
 
-  const SALE_NAME = "a token sale";
+  const SALE_NAME = "a minted sale";
+  const CURATED_SALE_NAME = "a curated minted sale";
+  const MERKLE_SALE_NAME = "a merkle minted sale";
+  const MERKLE_BUYER_ALLOCATION = new BN(50);
+  const VOUCHER_SALE_NAME = "a voucher minted sale";
+  const VOUCHER_BUYER_ALLOCATION = new BN(25);
+  const WINDOWED_SALE_NAME = "a minted sale with time windows";
+  const PHASED_SALE_NAME = "a minted sale in phases";
+  const VESTING_SALE_NAME = "a vesting minted sale";
+  const FAILED_RAISE_SALE_NAME = "a minted sale missing soft cap";
+  const SOFT_CAP_SALE_NAME = "a minted sale with a soft cap";
+  const CAPPED_SALE_NAME = "a minted sale with caps";
+  const AUCTION_SALE_NAME = "a dutch auction minted sale";
+  const CURVE_SALE_NAME = "a bonding curve minted sale";
+  const COMMIT_SALE_NAME = "a commit minted sale";
+  const LOTTERY_SALE_NAME = "a lottery minted sale";
+  const EVENTS_SALE_NAME = "a minted sale emitting events";
+  const TOKEN_2022_SALE_NAME = "a token-2022 minted sale";
+  const PAYMENT_SALE_NAME = "a minted sale paid in other mint";
+  const PAYMENT_MINT_DECIMALS = 6;
+  const PAYMENT_SALE_PRICE_PER_TOKEN = new BN(2 * Math.pow(10, PAYMENT_MINT_DECIMALS)); // 1 token = 2 payment tokens
   const SALE_PRICE_PER_TOKEN_LAMPORTS = new BN(LAMPORTS_PER_SOL / 100); // 1 token = 0.01 SOL
+  const PHASE_PRICE_PER_TOKEN_LAMPORTS = new BN(LAMPORTS_PER_SOL / 50); // 1 token = 0.02 SOL
   const SALE_MAX_BUYERS = new BN(2);
   const SALE_MAX_TOKENS_PER_BUYER = new BN(1000);
 
   // Actors in our tests
   const MINT_KEYPAIR = Keypair.generate();
+  // Each mint can only have its authority held by one sale at a time
+  const MERKLE_MINT_KEYPAIR = Keypair.generate();
+  const VOUCHER_MINT_KEYPAIR = Keypair.generate();
+  const PAYMENT_SALE_MINT_KEYPAIR = Keypair.generate();
+  const WINDOWED_MINT_KEYPAIR = Keypair.generate();
+  const PHASED_MINT_KEYPAIR = Keypair.generate();
+  const VESTING_MINT_KEYPAIR = Keypair.generate();
+  const FAILED_RAISE_MINT_KEYPAIR = Keypair.generate();
+  const SOFT_CAP_MINT_KEYPAIR = Keypair.generate();
+  const CAPPED_MINT_KEYPAIR = Keypair.generate();
+  const AUCTION_MINT_KEYPAIR = Keypair.generate();
+  const CURVE_MINT_KEYPAIR = Keypair.generate();
+  const COMMIT_MINT_KEYPAIR = Keypair.generate();
+  const LOTTERY_MINT_KEYPAIR = Keypair.generate();
+  const EVENTS_MINT_KEYPAIR = Keypair.generate();
   const OWNER_KEYPAIR = Keypair.generate();
   const BUYER_KEYPAIR = Keypair.generate();
   const NON_BUYER_KEYPAIR = Keypair.generate();
+  const MERKLE_BUYER_KEYPAIR = Keypair.generate();
+  const VOUCHER_SIGNER_KEYPAIR = Keypair.generate();
+  const PAYMENT_MINT_KEYPAIR = Keypair.generate();
 
   const getSaleStateAddress = (name: string) => {
     const [address, _bump] = PublicKey.findProgramAddressSync(
@@ -58,6 +104,49 @@ describe("Mint Whitelist Token Sale - e2e story", () => {
     return address;
   };
 
+  // Mirrors the leaf and node hashing of the program's `merkle` module
+  const hashMerkleLeaf = (buyer: PublicKey, allocation: BN) =>
+    createHash("sha256")
+      .update(Buffer.from([0]))
+      .update(buyer.toBuffer())
+      .update(allocation.toArrayLike(Buffer, "le", 8))
+      .digest();
+
+  const hashMerkleNode = (a: Buffer, b: Buffer) => {
+    const [left, right] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+
+    return createHash("sha256")
+      .update(Buffer.from([1]))
+      .update(left)
+      .update(right)
+      .digest();
+  };
+
+  // A two-entry whitelist: one buyer with the default allocation, one with a custom allocation
+  const merkleRoot = hashMerkleNode(
+    hashMerkleLeaf(BUYER_KEYPAIR.publicKey, new BN(0)),
+    hashMerkleLeaf(MERKLE_BUYER_KEYPAIR.publicKey, MERKLE_BUYER_ALLOCATION)
+  );
+
+  // Vouchers are valid for an hour, which is plenty for the test suite
+  const createVoucherInstruction = (
+    sale: PublicKey,
+    buyer: PublicKey,
+    allocation: BN
+  ) => {
+    const expiresAt = new BN(Math.floor(Date.now() / 1000) + 60 * 60);
+
+    return Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: VOUCHER_SIGNER_KEYPAIR.secretKey,
+      message: Buffer.concat([
+        sale.toBuffer(),
+        buyer.toBuffer(),
+        allocation.toArrayLike(Buffer, "le", 8),
+        expiresAt.toArrayLike(Buffer, "le", 8),
+      ]),
+    });
+  };
+
   const confirmTransaction = async (tx: string) => {
     const bh = await provider.connection.getLatestBlockhash();
 
@@ -80,12 +169,38 @@ describe("Mint Whitelist Token Sale - e2e story", () => {
     );
   };
 
+  // Events are emitted through a CPI into the program itself, with the event tag followed by the event as data
+  const getCpiEvents = async (tx: string) => {
+    await confirmTransaction(tx);
+
+    const txResponse = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const accountKeys = txResponse.transaction.message.getAccountKeys();
+
+    return txResponse.meta.innerInstructions
+      .flatMap((inner) => inner.instructions)
+      .filter((ix) =>
+        accountKeys.get(ix.programIdIndex).equals(program.programId)
+      )
+      .map((ix) =>
+        program.coder.events.decode(
+          anchor.utils.bytes.base64.encode(
+            anchor.utils.bytes.bs58.decode(ix.data).subarray(8)
+          )
+        )
+      )
+      .filter((event) => event !== null);
+  };
+
   // BEGIN TESTS
 
   before("airdrop SOL into wallets and create a token mint", async () => {
     await airdropSol(OWNER_KEYPAIR.publicKey, 100);
     await airdropSol(BUYER_KEYPAIR.publicKey, 100);
     await airdropSol(NON_BUYER_KEYPAIR.publicKey, 100);
+    await airdropSol(MERKLE_BUYER_KEYPAIR.publicKey, 100);
 
     await createMint(
       provider.connection,
@@ -95,6 +210,158 @@ describe("Mint Whitelist Token Sale - e2e story", () => {
       DECIMALS,
       MINT_KEYPAIR
     );
+
+    await createMint(
+      provider.connection,
+      OWNER_KEYPAIR,
+      OWNER_KEYPAIR.publicKey,
+      OWNER_KEYPAIR.publicKey,
+      DECIMALS,
+      MERKLE_MINT_KEYPAIR
+    );
+
+    await createMint(
+      provider.connection,
+      OWNER_KEYPAIR,
+      OWNER_KEYPAIR.publicKey,
+      OWNER_KEYPAIR.publicKey,
+      DECIMALS,
+      VOUCHER_MINT_KEYPAIR
+    );
+
+    await createMint(
+      provider.connection,
+      OWNER_KEYPAIR,
+      OWNER_KEYPAIR.publicKey,
+      OWNER_KEYPAIR.publicKey,
+      DECIMALS,
+      PAYMENT_SALE_MINT_KEYPAIR
+    );
+
+    await createMint(
+      provider.connection,
+      OWNER_KEYPAIR,
+      OWNER_KEYPAIR.publicKey,
+      OWNER_KEYPAIR.publicKey,
+      DECIMALS,
+      WINDOWED_MINT_KEYPAIR
+    );
+
+    await createMint(
+      provider.connection,
+      OWNER_KEYPAIR,
+      OWNER_KEYPAIR.publicKey,
+      OWNER_KEYPAIR.publicKey,
+      DECIMALS,
+      PHASED_MINT_KEYPAIR
+    );
+
+    await createMint(
+      provider.connection,
+      OWNER_KEYPAIR,
+      OWNER_KEYPAIR.publicKey,
+      OWNER_KEYPAIR.publicKey,
+      DECIMALS,
+      VESTING_MINT_KEYPAIR
+    );
+
+    await createMint(
+      provider.connection,
+      OWNER_KEYPAIR,
+      OWNER_KEYPAIR.publicKey,
+      OWNER_KEYPAIR.publicKey,
+      DECIMALS,
+      FAILED_RAISE_MINT_KEYPAIR
+    );
+
+    await createMint(
+      provider.connection,
+      OWNER_KEYPAIR,
+      OWNER_KEYPAIR.publicKey,
+      OWNER_KEYPAIR.publicKey,
+      DECIMALS,
+      SOFT_CAP_MINT_KEYPAIR
+    );
+
+    await createMint(
+      provider.connection,
+      OWNER_KEYPAIR,
+      OWNER_KEYPAIR.publicKey,
+      OWNER_KEYPAIR.publicKey,
+      DECIMALS,
+      CAPPED_MINT_KEYPAIR
+    );
+
+    await createMint(
+      provider.connection,
+      OWNER_KEYPAIR,
+      OWNER_KEYPAIR.publicKey,
+      OWNER_KEYPAIR.publicKey,
+      DECIMALS,
+      AUCTION_MINT_KEYPAIR
+    );
+
+    await createMint(
+      provider.connection,
+      OWNER_KEYPAIR,
+      OWNER_KEYPAIR.publicKey,
+      OWNER_KEYPAIR.publicKey,
+      DECIMALS,
+      CURVE_MINT_KEYPAIR
+    );
+
+    await createMint(
+      provider.connection,
+      OWNER_KEYPAIR,
+      OWNER_KEYPAIR.publicKey,
+      OWNER_KEYPAIR.publicKey,
+      DECIMALS,
+      COMMIT_MINT_KEYPAIR
+    );
+
+    await createMint(
+      provider.connection,
+      OWNER_KEYPAIR,
+      OWNER_KEYPAIR.publicKey,
+      OWNER_KEYPAIR.publicKey,
+      DECIMALS,
+      LOTTERY_MINT_KEYPAIR
+    );
+
+    await createMint(
+      provider.connection,
+      OWNER_KEYPAIR,
+      OWNER_KEYPAIR.publicKey,
+      OWNER_KEYPAIR.publicKey,
+      DECIMALS,
+      EVENTS_MINT_KEYPAIR
+    );
+
+    // Buyers of the payment mint sale pay in this mint, so they need some of it
+    await createMint(
+      provider.connection,
+      OWNER_KEYPAIR,
+      OWNER_KEYPAIR.publicKey,
+      OWNER_KEYPAIR.publicKey,
+      PAYMENT_MINT_DECIMALS,
+      PAYMENT_MINT_KEYPAIR
+    );
+
+    const buyerPaymentAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      BUYER_KEYPAIR,
+      PAYMENT_MINT_KEYPAIR.publicKey,
+      BUYER_KEYPAIR.publicKey
+    );
+
+    await mintTo(
+      provider.connection,
+      OWNER_KEYPAIR,
+      PAYMENT_MINT_KEYPAIR.publicKey,
+      buyerPaymentAta.address,
+      OWNER_KEYPAIR,
+      1_000 * Math.pow(10, PAYMENT_MINT_DECIMALS)
+    );
   });
 
   it("should initialize a sale and transfer mint authority to sale PDA", async () => {
@@ -103,11 +370,16 @@ describe("Mint Whitelist Token Sale - e2e story", () => {
         SALE_NAME,
         SALE_PRICE_PER_TOKEN_LAMPORTS,
         SALE_MAX_TOKENS_PER_BUYER,
-        SALE_MAX_BUYERS
+        SALE_MAX_BUYERS,
+        { mint: {} },
+        0
       )
       .accounts({
+        vaultAta: null,
+        signerAta: null,
         signer: OWNER_KEYPAIR.publicKey,
         tokenMint: MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([OWNER_KEYPAIR])
       .rpc();
@@ -235,7 +507,7 @@ describe("Mint Whitelist Token Sale - e2e story", () => {
           assert.fail("Non-owner should not be able to change sale state!");
         },
         (e: SendTransactionError) => {
-          assert.ok(e.logs.some((log) => log.includes("OnlyOwner")));
+          assert.ok(e.logs.some((log) => log.includes("OnlyOperator")));
         }
       );
 
@@ -254,8 +526,10 @@ describe("Mint Whitelist Token Sale - e2e story", () => {
     await program.methods
       .buyTokens(SALE_NAME, buyAmount)
       .accounts({
+        vaultAta: null,
         signer: BUYER_KEYPAIR.publicKey,
         tokenMint: MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([BUYER_KEYPAIR])
       .rpc()
@@ -295,8 +569,10 @@ describe("Mint Whitelist Token Sale - e2e story", () => {
     await program.methods
       .buyTokens(SALE_NAME, buyAmount)
       .accounts({
+        vaultAta: null,
         signer: BUYER_KEYPAIR.publicKey,
         tokenMint: MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([BUYER_KEYPAIR])
       .rpc();
@@ -322,8 +598,10 @@ describe("Mint Whitelist Token Sale - e2e story", () => {
     await program.methods
       .buyTokens(SALE_NAME, buyAmount)
       .accounts({
+        vaultAta: null,
         signer: BUYER_KEYPAIR.publicKey,
         tokenMint: MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([BUYER_KEYPAIR])
       .rpc()
@@ -346,8 +624,10 @@ describe("Mint Whitelist Token Sale - e2e story", () => {
     await program.methods
       .buyTokens(SALE_NAME, remainingBuyAmount)
       .accounts({
+        vaultAta: null,
         signer: BUYER_KEYPAIR.publicKey,
         tokenMint: MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([BUYER_KEYPAIR])
       .rpc();
@@ -373,8 +653,10 @@ describe("Mint Whitelist Token Sale - e2e story", () => {
     await program.methods
       .buyTokens(SALE_NAME, buyAmount)
       .accounts({
+        vaultAta: null,
         signer: NON_BUYER_KEYPAIR.publicKey,
         tokenMint: MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([NON_BUYER_KEYPAIR])
       .rpc()
@@ -420,8 +702,11 @@ describe("Mint Whitelist Token Sale - e2e story", () => {
     await program.methods
       .closeWhitelistSale(SALE_NAME)
       .accounts({
+        vaultAta: null,
+        signerAta: null,
         signer: NON_BUYER_KEYPAIR.publicKey,
         tokenMint: MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([NON_BUYER_KEYPAIR])
       .rpc()
@@ -439,8 +724,11 @@ describe("Mint Whitelist Token Sale - e2e story", () => {
     await program.methods
       .closeWhitelistSale(SALE_NAME)
       .accounts({
+        vaultAta: null,
+        signerAta: null,
         signer: OWNER_KEYPAIR.publicKey,
         tokenMint: MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([OWNER_KEYPAIR])
       .rpc();
@@ -452,4 +740,1764 @@ describe("Mint Whitelist Token Sale - e2e story", () => {
       OWNER_KEYPAIR.publicKey.toBase58()
     );
   });
+
+  it("should allow owner to switch a new sale to a curated whitelist", async () => {
+    await program.methods
+      .createWhitelistSale(
+        CURATED_SALE_NAME,
+        SALE_PRICE_PER_TOKEN_LAMPORTS,
+        SALE_MAX_TOKENS_PER_BUYER,
+        SALE_MAX_BUYERS,
+        { mint: {} },
+        0
+      )
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer: OWNER_KEYPAIR.publicKey,
+        tokenMint: MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .setWhitelistMode(CURATED_SALE_NAME, { curated: {} })
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    const saleState = await program.account.whitelistSale.fetch(
+      getSaleStateAddress(CURATED_SALE_NAME)
+    );
+
+    assert.deepEqual(saleState.whitelistMode, { curated: {} });
+  });
+
+  it("should disallow a buyer to register themselves on a curated whitelist", async () => {
+    await program.methods
+      .registerForWhitelist(CURATED_SALE_NAME)
+      .accounts({
+        signer: BUYER_KEYPAIR.publicKey,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc()
+      .then(
+        () => {
+          assert.fail(
+            "Buyer should not be able to register themselves on a curated whitelist!"
+          );
+        },
+        (e: SendTransactionError) => {
+          assert.ok(
+            e.logs.some((log) => log.includes("SelfRegistrationDisabled"))
+          );
+        }
+      );
+  });
+
+  it("should disallow anyone other than owner to add buyers to a curated whitelist", async () => {
+    await program.methods
+      .addToWhitelist(CURATED_SALE_NAME)
+      .accounts({
+        signer: BUYER_KEYPAIR.publicKey,
+        buyer: BUYER_KEYPAIR.publicKey,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc()
+      .then(
+        () => {
+          assert.fail("Non-owner should not be able to add buyers!");
+        },
+        (e: SendTransactionError) => {
+          assert.ok(e.logs.some((log) => log.includes("OnlyOperator")));
+        }
+      );
+  });
+
+  it("should allow owner to add buyers to a curated whitelist, one by one or in a batch", async () => {
+    const batchBuyer = Keypair.generate();
+
+    await program.methods
+      .addToWhitelist(CURATED_SALE_NAME)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+        buyer: BUYER_KEYPAIR.publicKey,
+      })
+      .remainingAccounts([
+        { pubkey: batchBuyer.publicKey, isSigner: false, isWritable: false },
+        {
+          pubkey: getAllowanceAddress(CURATED_SALE_NAME, batchBuyer.publicKey),
+          isSigner: false,
+          isWritable: true,
+        },
+      ])
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    for (const buyer of [BUYER_KEYPAIR.publicKey, batchBuyer.publicKey]) {
+      const allowance = await program.account.allowance.fetch(
+        getAllowanceAddress(CURATED_SALE_NAME, buyer)
+      );
+
+      assert.isTrue(allowance.isApproved);
+      assert.isTrue(allowance.buyer.equals(buyer));
+    }
+
+    const saleState = await program.account.whitelistSale.fetch(
+      getSaleStateAddress(CURATED_SALE_NAME)
+    );
+
+    assert.isTrue(saleState.numBuyers.eq(new BN(2)));
+  });
+
+  it("should disallow a buyer removed from a curated whitelist to purchase tokens", async () => {
+    await program.methods
+      .removeFromWhitelist(CURATED_SALE_NAME)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+        allowance: getAllowanceAddress(
+          CURATED_SALE_NAME,
+          BUYER_KEYPAIR.publicKey
+        ),
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .updateSaleState(CURATED_SALE_NAME, false, true)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .buyTokens(CURATED_SALE_NAME, new BN(10))
+      .accounts({
+        vaultAta: null,
+        signer: BUYER_KEYPAIR.publicKey,
+        tokenMint: MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc()
+      .then(
+        () => {
+          assert.fail(
+            "Buyer removed from the whitelist should not be able to purchase tokens!"
+          );
+        },
+        (e: SendTransactionError) => {
+          assert.ok(e.logs.some((log) => log.includes("NotWhitelisted")));
+        }
+      );
+
+    const saleState = await program.account.whitelistSale.fetch(
+      getSaleStateAddress(CURATED_SALE_NAME)
+    );
+
+    assert.isTrue(saleState.numBuyers.eq(new BN(1)));
+  });
+
+  it("should allow owner to set a Merkle root for a sale with a Merkle whitelist", async () => {
+    await program.methods
+      .createWhitelistSale(
+        MERKLE_SALE_NAME,
+        SALE_PRICE_PER_TOKEN_LAMPORTS,
+        SALE_MAX_TOKENS_PER_BUYER,
+        SALE_MAX_BUYERS,
+        { mint: {} },
+        0
+      )
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer: OWNER_KEYPAIR.publicKey,
+        tokenMint: MERKLE_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .setWhitelistMode(MERKLE_SALE_NAME, { merkle: {} })
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .updateMerkleRoot(MERKLE_SALE_NAME, Array.from(merkleRoot))
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    const saleState = await program.account.whitelistSale.fetch(
+      getSaleStateAddress(MERKLE_SALE_NAME)
+    );
+
+    assert.deepEqual(saleState.whitelistMode, { merkle: {} });
+    assert.isTrue(Buffer.from(saleState.merkleRoot).equals(merkleRoot));
+  });
+
+  it("should disallow a buyer outside of the Merkle whitelist to register", async () => {
+    await program.methods
+      .registerWithMerkleProof(
+        MERKLE_SALE_NAME,
+        MERKLE_BUYER_ALLOCATION,
+        [Array.from(hashMerkleLeaf(BUYER_KEYPAIR.publicKey, new BN(0)))]
+      )
+      .accounts({
+        signer: NON_BUYER_KEYPAIR.publicKey,
+      })
+      .signers([NON_BUYER_KEYPAIR])
+      .rpc()
+      .then(
+        () => {
+          assert.fail(
+            "Buyer outside of the Merkle whitelist should not be able to register!"
+          );
+        },
+        (e: SendTransactionError) => {
+          assert.ok(e.logs.some((log) => log.includes("InvalidMerkleProof")));
+        }
+      );
+  });
+
+  it("should allow a buyer with a valid Merkle proof to register with their allocation", async () => {
+    await program.methods
+      .registerWithMerkleProof(
+        MERKLE_SALE_NAME,
+        MERKLE_BUYER_ALLOCATION,
+        [Array.from(hashMerkleLeaf(BUYER_KEYPAIR.publicKey, new BN(0)))]
+      )
+      .accounts({
+        signer: MERKLE_BUYER_KEYPAIR.publicKey,
+      })
+      .signers([MERKLE_BUYER_KEYPAIR])
+      .rpc();
+
+    const allowance = await program.account.allowance.fetch(
+      getAllowanceAddress(MERKLE_SALE_NAME, MERKLE_BUYER_KEYPAIR.publicKey)
+    );
+
+    assert.isTrue(allowance.isApproved);
+    assert.isTrue(allowance.allocation.eq(MERKLE_BUYER_ALLOCATION));
+  });
+
+  it("should allow owner to require whitelist vouchers signed by a designated signer", async () => {
+    await program.methods
+      .createWhitelistSale(
+        VOUCHER_SALE_NAME,
+        SALE_PRICE_PER_TOKEN_LAMPORTS,
+        SALE_MAX_TOKENS_PER_BUYER,
+        SALE_MAX_BUYERS,
+        { mint: {} },
+        0
+      )
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer: OWNER_KEYPAIR.publicKey,
+        tokenMint: VOUCHER_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .setWhitelistMode(VOUCHER_SALE_NAME, { voucher: {} })
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .setWhitelistSigner(VOUCHER_SALE_NAME, VOUCHER_SIGNER_KEYPAIR.publicKey)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    const saleState = await program.account.whitelistSale.fetch(
+      getSaleStateAddress(VOUCHER_SALE_NAME)
+    );
+
+    assert.deepEqual(saleState.whitelistMode, { voucher: {} });
+    assert.isTrue(
+      saleState.whitelistSigner.equals(VOUCHER_SIGNER_KEYPAIR.publicKey)
+    );
+  });
+
+  it("should disallow a buyer to register without a whitelist voucher", async () => {
+    await program.methods
+      .registerForWhitelist(VOUCHER_SALE_NAME)
+      .accounts({
+        signer: BUYER_KEYPAIR.publicKey,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc()
+      .then(
+        () => {
+          assert.fail(
+            "Buyer should not be able to register without a whitelist voucher!"
+          );
+        },
+        (e: SendTransactionError) => {
+          assert.ok(e.logs.some((log) => log.includes("InvalidVoucher")));
+        }
+      );
+  });
+
+  it("should disallow a buyer to register with a voucher issued to someone else", async () => {
+    await program.methods
+      .registerForWhitelist(VOUCHER_SALE_NAME)
+      .accounts({
+        signer: NON_BUYER_KEYPAIR.publicKey,
+      })
+      .preInstructions([
+        createVoucherInstruction(
+          getSaleStateAddress(VOUCHER_SALE_NAME),
+          BUYER_KEYPAIR.publicKey,
+          VOUCHER_BUYER_ALLOCATION
+        ),
+      ])
+      .signers([NON_BUYER_KEYPAIR])
+      .rpc()
+      .then(
+        () => {
+          assert.fail(
+            "Buyer should not be able to register with someone else's voucher!"
+          );
+        },
+        (e: SendTransactionError) => {
+          assert.ok(e.logs.some((log) => log.includes("InvalidVoucher")));
+        }
+      );
+  });
+
+  it("should allow a buyer to register with a voucher and receive its allocation", async () => {
+    await program.methods
+      .registerForWhitelist(VOUCHER_SALE_NAME)
+      .accounts({
+        signer: BUYER_KEYPAIR.publicKey,
+      })
+      .preInstructions([
+        createVoucherInstruction(
+          getSaleStateAddress(VOUCHER_SALE_NAME),
+          BUYER_KEYPAIR.publicKey,
+          VOUCHER_BUYER_ALLOCATION
+        ),
+      ])
+      .signers([BUYER_KEYPAIR])
+      .rpc();
+
+    const allowance = await program.account.allowance.fetch(
+      getAllowanceAddress(VOUCHER_SALE_NAME, BUYER_KEYPAIR.publicKey)
+    );
+
+    assert.isTrue(allowance.isApproved);
+    assert.isTrue(allowance.allocation.eq(VOUCHER_BUYER_ALLOCATION));
+  });
+
+  it("should initialize a sale that is paid for in a payment mint", async () => {
+    await program.methods
+      .createWhitelistSale(
+        PAYMENT_SALE_NAME,
+        PAYMENT_SALE_PRICE_PER_TOKEN,
+        SALE_MAX_TOKENS_PER_BUYER,
+        SALE_MAX_BUYERS,
+        { mint: {} },
+        0
+      )
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer: OWNER_KEYPAIR.publicKey,
+        tokenMint: PAYMENT_SALE_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        paymentMint: PAYMENT_MINT_KEYPAIR.publicKey,
+        paymentTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    const saleState = await program.account.whitelistSale.fetch(
+      getSaleStateAddress(PAYMENT_SALE_NAME)
+    );
+
+    assert.isTrue(saleState.paymentMint.equals(PAYMENT_MINT_KEYPAIR.publicKey));
+  });
+
+  it("should charge buyers in the payment mint instead of SOL", async () => {
+    const buyAmount = new BN(10);
+
+    await program.methods
+      .registerForWhitelist(PAYMENT_SALE_NAME)
+      .accounts({
+        signer: BUYER_KEYPAIR.publicKey,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .updateSaleState(PAYMENT_SALE_NAME, false, true)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .buyTokens(PAYMENT_SALE_NAME, buyAmount)
+      .accounts({
+        vaultAta: null,
+        signer: BUYER_KEYPAIR.publicKey,
+        tokenMint: PAYMENT_SALE_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        paymentMint: PAYMENT_MINT_KEYPAIR.publicKey,
+        paymentTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc();
+
+    const proceedsAta = await getAccount(
+      provider.connection,
+      await getAssociatedTokenAddress(
+        PAYMENT_MINT_KEYPAIR.publicKey,
+        getSaleStateAddress(PAYMENT_SALE_NAME),
+        true
+      )
+    );
+    const expectedAmount = PAYMENT_SALE_PRICE_PER_TOKEN.mul(buyAmount);
+
+    assert.isTrue(
+      new BN(proceedsAta.amount.toString()).eq(expectedAmount),
+      `Proceeds ATA amount is ${proceedsAta.amount}, while it should be ${expectedAmount}`
+    );
+  });
+
+  it("should sweep payment mint proceeds to the owner when closing a sale", async () => {
+    await program.methods
+      .closeWhitelistSale(PAYMENT_SALE_NAME)
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer: OWNER_KEYPAIR.publicKey,
+        tokenMint: PAYMENT_SALE_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        paymentMint: PAYMENT_MINT_KEYPAIR.publicKey,
+        paymentTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    const ownerPaymentAta = await getAccount(
+      provider.connection,
+      await getAssociatedTokenAddress(
+        PAYMENT_MINT_KEYPAIR.publicKey,
+        OWNER_KEYPAIR.publicKey
+      )
+    );
+    const expectedAmount = PAYMENT_SALE_PRICE_PER_TOKEN.mul(new BN(10));
+
+    assert.isTrue(
+      new BN(ownerPaymentAta.amount.toString()).eq(expectedAmount),
+      `Owner's payment ATA amount is ${ownerPaymentAta.amount}, while it should be ${expectedAmount}`
+    );
+  });
+
+  it("should run a sale for a Token-2022 mint", async () => {
+    const buyAmount = new BN(10);
+    const token2022Mint = await createMint(
+      provider.connection,
+      OWNER_KEYPAIR,
+      OWNER_KEYPAIR.publicKey,
+      null,
+      DECIMALS,
+      Keypair.generate(),
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await program.methods
+      .createWhitelistSale(
+        TOKEN_2022_SALE_NAME,
+        SALE_PRICE_PER_TOKEN_LAMPORTS,
+        SALE_MAX_TOKENS_PER_BUYER,
+        SALE_MAX_BUYERS,
+        { mint: {} },
+        0
+      )
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer: OWNER_KEYPAIR.publicKey,
+        tokenMint: token2022Mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .registerForWhitelist(TOKEN_2022_SALE_NAME)
+      .accounts({
+        signer: BUYER_KEYPAIR.publicKey,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .updateSaleState(TOKEN_2022_SALE_NAME, false, true)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .buyTokens(TOKEN_2022_SALE_NAME, buyAmount)
+      .accounts({
+        vaultAta: null,
+        signer: BUYER_KEYPAIR.publicKey,
+        tokenMint: token2022Mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc();
+
+    const signerAta = await getAccount(
+      provider.connection,
+      await getAssociatedTokenAddress(
+        token2022Mint,
+        BUYER_KEYPAIR.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    assert.isTrue(
+      new BN(signerAta.amount.toString()).eq(
+        buyAmount.mul(new BN(Math.pow(10, DECIMALS)))
+      ),
+      `Signer's ATA amount is ${signerAta.amount}, while it should be ${buyAmount}`
+    );
+  });
+
+  it("should keep registration and purchasing closed outside of their time windows", async () => {
+    await program.methods
+      .createWhitelistSale(
+        WINDOWED_SALE_NAME,
+        SALE_PRICE_PER_TOKEN_LAMPORTS,
+        SALE_MAX_TOKENS_PER_BUYER,
+        SALE_MAX_BUYERS,
+        { mint: {} },
+        0
+      )
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer: OWNER_KEYPAIR.publicKey,
+        tokenMint: WINDOWED_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .registerForWhitelist(WINDOWED_SALE_NAME)
+      .accounts({
+        signer: BUYER_KEYPAIR.publicKey,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc();
+
+    // Registration ended a minute ago, while purchasing only starts in an hour
+    const now = Math.floor(Date.now() / 1000);
+
+    await program.methods
+      .setSaleWindows(
+        WINDOWED_SALE_NAME,
+        null,
+        new BN(now - 60),
+        new BN(now + 60 * 60),
+        null
+      )
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .updateSaleState(WINDOWED_SALE_NAME, true, true)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .registerForWhitelist(WINDOWED_SALE_NAME)
+      .accounts({
+        signer: NON_BUYER_KEYPAIR.publicKey,
+      })
+      .signers([NON_BUYER_KEYPAIR])
+      .rpc()
+      .then(
+        () => {
+          assert.fail(
+            "Buyer should not be able to register after the registration window!"
+          );
+        },
+        (e: SendTransactionError) => {
+          assert.ok(
+            e.logs.some((log) =>
+              log.includes("Whitelist registration is closed")
+            )
+          );
+        }
+      );
+
+    await program.methods
+      .buyTokens(WINDOWED_SALE_NAME, new BN(10))
+      .accounts({
+        vaultAta: null,
+        signer: BUYER_KEYPAIR.publicKey,
+        tokenMint: WINDOWED_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc()
+      .then(
+        () => {
+          assert.fail(
+            "Whitelisted buyer should not be able to buy before the sale window!"
+          );
+        },
+        (e: SendTransactionError) => {
+          assert.ok(e.logs.some((log) => log.includes("SaleClosed")));
+        }
+      );
+  });
+
+  it("should sell tokens in phases with their own price and limits", async () => {
+    await program.methods
+      .createWhitelistSale(
+        PHASED_SALE_NAME,
+        SALE_PRICE_PER_TOKEN_LAMPORTS,
+        new BN(10),
+        SALE_MAX_BUYERS,
+        { mint: {} },
+        0
+      )
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer: OWNER_KEYPAIR.publicKey,
+        tokenMint: PHASED_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    // A public phase starting in a couple of seconds, open to buyers who never registered
+    const phaseStart = Math.floor(Date.now() / 1000) + 2;
+    const setPhase = () =>
+      program.methods
+        .setSalePhase(
+          PHASED_SALE_NAME,
+          0,
+          new BN(phaseStart),
+          new BN(phaseStart + 60 * 60),
+          PHASE_PRICE_PER_TOKEN_LAMPORTS,
+          new BN(8),
+          new BN(10),
+          false
+        )
+        .accounts({
+          signer: OWNER_KEYPAIR.publicKey,
+        })
+        .signers([OWNER_KEYPAIR])
+        .rpc();
+
+    await setPhase();
+
+    await program.methods
+      .updateSaleState(PHASED_SALE_NAME, false, true)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 4000));
+
+    await setPhase().then(
+      () => {
+        assert.fail("Owner should not be able to edit a phase that started!");
+      },
+      (e: SendTransactionError) => {
+        assert.ok(e.logs.some((log) => log.includes("PhaseAlreadyStarted")));
+      }
+    );
+
+    const buy = (buyer: Keypair, amount: number) =>
+      program.methods
+        .buyTokens(PHASED_SALE_NAME, new BN(amount))
+        .accounts({
+          vaultAta: null,
+          signer: buyer.publicKey,
+          tokenMint: PHASED_MINT_KEYPAIR.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([buyer])
+        .rpc();
+
+    const saleStateAddress = getSaleStateAddress(PHASED_SALE_NAME);
+    const balanceBefore = await provider.connection.getBalance(
+      saleStateAddress
+    );
+
+    await buy(NON_BUYER_KEYPAIR, 8);
+
+    const balanceAfter = await provider.connection.getBalance(
+      saleStateAddress
+    );
+    assert.equal(
+      balanceAfter - balanceBefore,
+      PHASE_PRICE_PER_TOKEN_LAMPORTS.muln(8).toNumber()
+    );
+
+    const allowance = await program.account.allowance.fetch(
+      getAllowanceAddress(PHASED_SALE_NAME, NON_BUYER_KEYPAIR.publicKey)
+    );
+    assert.equal(allowance.phaseTokensBought[0].toNumber(), 8);
+
+    await buy(NON_BUYER_KEYPAIR, 1).then(
+      () => {
+        assert.fail("Buyer should not be able to exceed the phase's cap!");
+      },
+      (e: SendTransactionError) => {
+        assert.ok(e.logs.some((log) => log.includes("AllowanceExceeded")));
+      }
+    );
+
+    await buy(BUYER_KEYPAIR, 3).then(
+      () => {
+        assert.fail("Buyer should not be able to exceed the phase's total!");
+      },
+      (e: SendTransactionError) => {
+        assert.ok(e.logs.some((log) => log.includes("PhaseSoldOut")));
+      }
+    );
+
+    await buy(BUYER_KEYPAIR, 2);
+
+    const saleState = await program.account.whitelistSale.fetch(
+      saleStateAddress
+    );
+    assert.equal(saleState.numPhases, 1);
+    assert.equal(saleState.phases[0].tokensSold.toNumber(), 10);
+  });
+
+  it("should hold back vesting tokens until they unlock", async () => {
+    await program.methods
+      .createWhitelistSale(
+        VESTING_SALE_NAME,
+        SALE_PRICE_PER_TOKEN_LAMPORTS,
+        new BN(10),
+        SALE_MAX_BUYERS,
+        { mint: {} },
+        0
+      )
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer: OWNER_KEYPAIR.publicKey,
+        tokenMint: VESTING_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    // Half of the tokens unlock at TGE, the rest only after a cliff of an hour
+    await program.methods
+      .setVestingSchedule(VESTING_SALE_NAME, {
+        tgeTimestamp: new BN(Math.floor(Date.now() / 1000) - 60),
+        tgeUnlockBps: 5000,
+        cliffSeconds: new BN(60 * 60),
+        durationSeconds: new BN(60 * 60),
+      })
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .updateSaleState(VESTING_SALE_NAME, true, true)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .registerForWhitelist(VESTING_SALE_NAME)
+      .accounts({
+        signer: BUYER_KEYPAIR.publicKey,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .buyTokens(VESTING_SALE_NAME, new BN(10))
+      .accounts({
+        vaultAta: null,
+        signer: BUYER_KEYPAIR.publicKey,
+        tokenMint: VESTING_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc();
+
+    const signerAtaAddress = await getAssociatedTokenAddress(
+      VESTING_MINT_KEYPAIR.publicKey,
+      BUYER_KEYPAIR.publicKey
+    );
+    let signerAta = await getAccount(provider.connection, signerAtaAddress);
+    assert.equal(signerAta.amount.toString(), "0");
+
+    const claimVested = () =>
+      program.methods
+        .claimVested(VESTING_SALE_NAME)
+        .accounts({
+          vaultAta: null,
+          signer: BUYER_KEYPAIR.publicKey,
+          tokenMint: VESTING_MINT_KEYPAIR.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([BUYER_KEYPAIR])
+        .rpc();
+
+    await claimVested();
+
+    signerAta = await getAccount(provider.connection, signerAtaAddress);
+    assert.equal(
+      signerAta.amount.toString(),
+      (5 * Math.pow(10, DECIMALS)).toString()
+    );
+
+    await claimVested().then(
+      () => {
+        assert.fail(
+          "Buyer should not be able to claim tokens still in their cliff!"
+        );
+      },
+      (e: SendTransactionError) => {
+        assert.ok(e.logs.some((log) => log.includes("NothingToClaim")));
+      }
+    );
+
+    await program.methods
+      .closeWhitelistSale(VESTING_SALE_NAME)
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer: OWNER_KEYPAIR.publicKey,
+        tokenMint: VESTING_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc()
+      .then(
+        () => {
+          assert.fail(
+            "Owner should not be able to close a sale with tokens vesting!"
+          );
+        },
+        (e: SendTransactionError) => {
+          assert.ok(e.logs.some((log) => log.includes("ClaimsOutstanding")));
+        }
+      );
+  });
+
+  it("should refund buyers when the soft cap is missed", async () => {
+    await program.methods
+      .createWhitelistSale(
+        FAILED_RAISE_SALE_NAME,
+        SALE_PRICE_PER_TOKEN_LAMPORTS,
+        new BN(10),
+        SALE_MAX_BUYERS,
+        { mint: {} },
+        0
+      )
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer: OWNER_KEYPAIR.publicKey,
+        tokenMint: FAILED_RAISE_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    // The soft cap is worth all 20 tokens, while the buyer only buys 5 before the deadline
+    await program.methods
+      .setSoftCap(
+        FAILED_RAISE_SALE_NAME,
+        SALE_PRICE_PER_TOKEN_LAMPORTS.muln(20),
+        new BN(Math.floor(Date.now() / 1000) + 3)
+      )
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .updateSaleState(FAILED_RAISE_SALE_NAME, true, true)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .registerForWhitelist(FAILED_RAISE_SALE_NAME)
+      .accounts({
+        signer: BUYER_KEYPAIR.publicKey,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .buyTokens(FAILED_RAISE_SALE_NAME, new BN(5))
+      .accounts({
+        vaultAta: null,
+        signer: BUYER_KEYPAIR.publicKey,
+        tokenMint: FAILED_RAISE_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .finalizeSale(FAILED_RAISE_SALE_NAME)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc()
+      .then(
+        () => {
+          assert.fail(
+            "Owner should not be able to finalize below the soft cap!"
+          );
+        },
+        (e: SendTransactionError) => {
+          assert.ok(e.logs.some((log) => log.includes("SoftCapNotReached")));
+        }
+      );
+
+    await new Promise((resolve) => setTimeout(resolve, 4000));
+
+    const saleStateAddress = getSaleStateAddress(FAILED_RAISE_SALE_NAME);
+    const balanceBefore = await provider.connection.getBalance(
+      saleStateAddress
+    );
+
+    await program.methods
+      .claimRefund(FAILED_RAISE_SALE_NAME)
+      .accounts({
+        signer: BUYER_KEYPAIR.publicKey,
+        tokenMint: FAILED_RAISE_MINT_KEYPAIR.publicKey,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc();
+
+    const balanceAfter = await provider.connection.getBalance(
+      saleStateAddress
+    );
+    assert.equal(
+      balanceBefore - balanceAfter,
+      SALE_PRICE_PER_TOKEN_LAMPORTS.muln(5).toNumber()
+    );
+
+    const allowance = await program.account.allowance.fetch(
+      getAllowanceAddress(FAILED_RAISE_SALE_NAME, BUYER_KEYPAIR.publicKey)
+    );
+    assert.equal(allowance.lamportsPaid.toNumber(), 0);
+    assert.equal(allowance.tokensHeld.toNumber(), 0);
+  });
+
+  it("should let buyers claim their tokens once the soft cap is reached", async () => {
+    await program.methods
+      .createWhitelistSale(
+        SOFT_CAP_SALE_NAME,
+        SALE_PRICE_PER_TOKEN_LAMPORTS,
+        new BN(10),
+        SALE_MAX_BUYERS,
+        { mint: {} },
+        0
+      )
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer: OWNER_KEYPAIR.publicKey,
+        tokenMint: SOFT_CAP_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .setSoftCap(
+        SOFT_CAP_SALE_NAME,
+        SALE_PRICE_PER_TOKEN_LAMPORTS.muln(5),
+        new BN(Math.floor(Date.now() / 1000) + 60 * 60)
+      )
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .updateSaleState(SOFT_CAP_SALE_NAME, true, true)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .registerForWhitelist(SOFT_CAP_SALE_NAME)
+      .accounts({
+        signer: BUYER_KEYPAIR.publicKey,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .buyTokens(SOFT_CAP_SALE_NAME, new BN(5))
+      .accounts({
+        vaultAta: null,
+        signer: BUYER_KEYPAIR.publicKey,
+        tokenMint: SOFT_CAP_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc();
+
+    const claimVested = () =>
+      program.methods
+        .claimVested(SOFT_CAP_SALE_NAME)
+        .accounts({
+          vaultAta: null,
+          signer: BUYER_KEYPAIR.publicKey,
+          tokenMint: SOFT_CAP_MINT_KEYPAIR.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([BUYER_KEYPAIR])
+        .rpc();
+
+    await claimVested().then(
+      () => {
+        assert.fail("Buyer should not be able to claim before finalization!");
+      },
+      (e: SendTransactionError) => {
+        assert.ok(e.logs.some((log) => log.includes("SaleNotFinalized")));
+      }
+    );
+
+    await program.methods
+      .finalizeSale(SOFT_CAP_SALE_NAME)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await claimVested();
+
+    const signerAtaAddress = await getAssociatedTokenAddress(
+      SOFT_CAP_MINT_KEYPAIR.publicKey,
+      BUYER_KEYPAIR.publicKey
+    );
+    const signerAta = await getAccount(provider.connection, signerAtaAddress);
+    assert.equal(
+      signerAta.amount.toString(),
+      (5 * Math.pow(10, DECIMALS)).toString()
+    );
+  });
+
+  it("should enforce the sale's token and raise caps", async () => {
+    await program.methods
+      .createWhitelistSale(
+        CAPPED_SALE_NAME,
+        SALE_PRICE_PER_TOKEN_LAMPORTS,
+        new BN(10),
+        SALE_MAX_BUYERS,
+        { mint: {} },
+        0
+      )
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer: OWNER_KEYPAIR.publicKey,
+        tokenMint: CAPPED_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    const setSaleCaps = (totalTokensForSale: number, hardCapTokens: number) =>
+      program.methods
+        .setSaleCaps(
+          CAPPED_SALE_NAME,
+          new BN(totalTokensForSale),
+          SALE_PRICE_PER_TOKEN_LAMPORTS.muln(hardCapTokens)
+        )
+        .accounts({
+          signer: OWNER_KEYPAIR.publicKey,
+        })
+        .signers([OWNER_KEYPAIR])
+        .rpc();
+
+    await setSaleCaps(6, 20);
+
+    await program.methods
+      .updateSaleState(CAPPED_SALE_NAME, true, true)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    for (const buyer of [BUYER_KEYPAIR, NON_BUYER_KEYPAIR]) {
+      await program.methods
+        .registerForWhitelist(CAPPED_SALE_NAME)
+        .accounts({
+          signer: buyer.publicKey,
+        })
+        .signers([buyer])
+        .rpc();
+    }
+
+    const buy = (buyer: Keypair, amount: number) =>
+      program.methods
+        .buyTokens(CAPPED_SALE_NAME, new BN(amount))
+        .accounts({
+          vaultAta: null,
+          signer: buyer.publicKey,
+          tokenMint: CAPPED_MINT_KEYPAIR.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([buyer])
+        .rpc();
+
+    await buy(BUYER_KEYPAIR, 5);
+
+    await buy(NON_BUYER_KEYPAIR, 2).then(
+      () => {
+        assert.fail("Buyer should not be able to buy more than is for sale!");
+      },
+      (e: SendTransactionError) => {
+        assert.ok(e.logs.some((log) => log.includes("SaleSoldOut")));
+      }
+    );
+
+    // Raising the token cap leaves the hard cap, worth 6 tokens, as the limit
+    await setSaleCaps(20, 6);
+
+    await buy(NON_BUYER_KEYPAIR, 2).then(
+      () => {
+        assert.fail("Buyer should not be able to exceed the hard cap!");
+      },
+      (e: SendTransactionError) => {
+        assert.ok(e.logs.some((log) => log.includes("HardCapReached")));
+      }
+    );
+
+    await buy(NON_BUYER_KEYPAIR, 1);
+
+    const saleState = await program.account.whitelistSale.fetch(
+      getSaleStateAddress(CAPPED_SALE_NAME)
+    );
+    assert.equal(saleState.tokensSold.toNumber(), 6);
+    assert.equal(
+      saleState.lamportsRaised.toNumber(),
+      SALE_PRICE_PER_TOKEN_LAMPORTS.muln(6).toNumber()
+    );
+  });
+
+  it("should rebate Dutch auction buyers down to the clearing price", async () => {
+    await program.methods
+      .createWhitelistSale(
+        AUCTION_SALE_NAME,
+        SALE_PRICE_PER_TOKEN_LAMPORTS,
+        new BN(10),
+        SALE_MAX_BUYERS,
+        { mint: {} },
+        0
+      )
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer: OWNER_KEYPAIR.publicKey,
+        tokenMint: AUCTION_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    // Price falls from twice the fixed price down to the fixed price within a few seconds
+    const auctionStart = Math.floor(Date.now() / 1000) - 1;
+    const auctionEnd = auctionStart + 10;
+
+    await program.methods
+      .setPricingMode(AUCTION_SALE_NAME, {
+        dutchAuction: {
+          "0": {
+            startPrice: SALE_PRICE_PER_TOKEN_LAMPORTS.muln(2),
+            floorPrice: SALE_PRICE_PER_TOKEN_LAMPORTS,
+            startTimestamp: new BN(auctionStart),
+            endTimestamp: new BN(auctionEnd),
+            curve: { linear: {} },
+            uniformClearing: true,
+          },
+        },
+      })
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .updateSaleState(AUCTION_SALE_NAME, true, true)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    for (const buyer of [BUYER_KEYPAIR, NON_BUYER_KEYPAIR]) {
+      await program.methods
+        .registerForWhitelist(AUCTION_SALE_NAME)
+        .accounts({
+          signer: buyer.publicKey,
+        })
+        .signers([buyer])
+        .rpc();
+    }
+
+    const buy = (buyer: Keypair, amount: number) =>
+      program.methods
+        .buyTokens(AUCTION_SALE_NAME, new BN(amount))
+        .accounts({
+          vaultAta: null,
+          signer: buyer.publicKey,
+          tokenMint: AUCTION_MINT_KEYPAIR.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([buyer])
+        .rpc();
+
+    const claimRebate = () =>
+      program.methods
+        .claimRebate(AUCTION_SALE_NAME)
+        .accounts({
+          signer: BUYER_KEYPAIR.publicKey,
+        })
+        .signers([BUYER_KEYPAIR])
+        .rpc();
+
+    await buy(BUYER_KEYPAIR, 5);
+
+    await claimRebate().then(
+      () => {
+        assert.fail("Buyer should not be able to claim a rebate mid-auction!");
+      },
+      (e: SendTransactionError) => {
+        assert.ok(e.logs.some((log) => log.includes("RebateUnavailable")));
+      }
+    );
+
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    // Buying later in the auction lowers the clearing price for everyone
+    await buy(NON_BUYER_KEYPAIR, 1);
+
+    const msUntilEnd = auctionEnd * 1000 - Date.now();
+    await new Promise((resolve) => setTimeout(resolve, msUntilEnd + 2000));
+
+    // Tokens can't be bought once the auction has ended
+    await buy(NON_BUYER_KEYPAIR, 1).then(
+      () => {
+        assert.fail("Buyer should not be able to buy after the auction!");
+      },
+      (e: SendTransactionError) => {
+        assert.ok(e.logs.some((log) => log.includes("SaleClosed")));
+      }
+    );
+
+    const saleStateAddress = getSaleStateAddress(AUCTION_SALE_NAME);
+    const balanceBefore = await provider.connection.getBalance(
+      saleStateAddress
+    );
+
+    await claimRebate();
+
+    const balanceAfter = await provider.connection.getBalance(
+      saleStateAddress
+    );
+    assert.ok(balanceBefore > balanceAfter);
+
+    const sale = await program.account.whitelistSale.fetch(saleStateAddress);
+    const allowance = await program.account.allowance.fetch(
+      getAllowanceAddress(AUCTION_SALE_NAME, BUYER_KEYPAIR.publicKey)
+    );
+    assert.equal(
+      allowance.lamportsPaid.toNumber(),
+      sale.clearingPrice.muln(5).toNumber()
+    );
+  });
+
+  it("should price purchases along a linear bonding curve", async () => {
+    await program.methods
+      .createWhitelistSale(
+        CURVE_SALE_NAME,
+        SALE_PRICE_PER_TOKEN_LAMPORTS,
+        new BN(10),
+        SALE_MAX_BUYERS,
+        { mint: {} },
+        0
+      )
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer: OWNER_KEYPAIR.publicKey,
+        tokenMint: CURVE_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    // Each token sold makes the next one 10% of the base price more expensive
+    const basePrice = SALE_PRICE_PER_TOKEN_LAMPORTS;
+    const slope = SALE_PRICE_PER_TOKEN_LAMPORTS.divn(10);
+
+    await program.methods
+      .setPricingMode(CURVE_SALE_NAME, {
+        linearCurve: { "0": { basePrice, slope } },
+      })
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .updateSaleState(CURVE_SALE_NAME, true, true)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    const saleStateAddress = getSaleStateAddress(CURVE_SALE_NAME);
+
+    // Tokens 0..2 cost 3 base prices plus (0 + 1 + 2) slopes, tokens 3..4 cost 2 base prices plus (3 + 4) slopes
+    for (const [buyer, amount, slopes] of [
+      [BUYER_KEYPAIR, 3, 3],
+      [NON_BUYER_KEYPAIR, 2, 7],
+    ] as [Keypair, number, number][]) {
+      await program.methods
+        .registerForWhitelist(CURVE_SALE_NAME)
+        .accounts({
+          signer: buyer.publicKey,
+        })
+        .signers([buyer])
+        .rpc();
+
+      const balanceBefore = await provider.connection.getBalance(
+        saleStateAddress
+      );
+
+      await program.methods
+        .buyTokens(CURVE_SALE_NAME, new BN(amount))
+        .accounts({
+          vaultAta: null,
+          signer: buyer.publicKey,
+          tokenMint: CURVE_MINT_KEYPAIR.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([buyer])
+        .rpc();
+
+      const balanceAfter = await provider.connection.getBalance(
+        saleStateAddress
+      );
+      assert.equal(
+        balanceAfter - balanceBefore,
+        basePrice.muln(amount).add(slope.muln(slopes)).toNumber()
+      );
+    }
+  });
+
+  it("should allocate an oversubscribed commit sale pro-rata", async () => {
+    await program.methods
+      .createWhitelistSale(
+        COMMIT_SALE_NAME,
+        SALE_PRICE_PER_TOKEN_LAMPORTS,
+        new BN(10),
+        SALE_MAX_BUYERS,
+        { mint: {} },
+        0
+      )
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer: OWNER_KEYPAIR.publicKey,
+        tokenMint: COMMIT_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    // Only 4 tokens are for sale, while buyers commit to 8
+    await program.methods
+      .setSaleCaps(COMMIT_SALE_NAME, new BN(4), null)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .setCommitMode(COMMIT_SALE_NAME, true)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .updateSaleState(COMMIT_SALE_NAME, true, true)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    for (const [buyer, tokens] of [
+      [BUYER_KEYPAIR, 6],
+      [NON_BUYER_KEYPAIR, 2],
+    ] as [Keypair, number][]) {
+      await program.methods
+        .registerForWhitelist(COMMIT_SALE_NAME)
+        .accounts({
+          signer: buyer.publicKey,
+        })
+        .signers([buyer])
+        .rpc();
+
+      await program.methods
+        .commit(COMMIT_SALE_NAME, SALE_PRICE_PER_TOKEN_LAMPORTS.muln(tokens))
+        .accounts({
+          signer: buyer.publicKey,
+        })
+        .signers([buyer])
+        .rpc();
+    }
+
+    await program.methods
+      .buyTokens(COMMIT_SALE_NAME, new BN(1))
+      .accounts({
+        vaultAta: null,
+        signer: BUYER_KEYPAIR.publicKey,
+        tokenMint: COMMIT_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc()
+      .then(
+        () => {
+          assert.fail("Buyer should not be able to buy in a commit sale!");
+        },
+        (e: SendTransactionError) => {
+          assert.ok(e.logs.some((log) => log.includes("CommitModeMismatch")));
+        }
+      );
+
+    await program.methods
+      .finalizeSale(COMMIT_SALE_NAME)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    // The buyer committed to 6 of 8 tokens, so they get 3 of the 4 for sale and half their payment back
+    const saleStateAddress = getSaleStateAddress(COMMIT_SALE_NAME);
+    const balanceBefore = await provider.connection.getBalance(
+      saleStateAddress
+    );
+
+    await program.methods
+      .settleCommitment(COMMIT_SALE_NAME)
+      .accounts({
+        signer: BUYER_KEYPAIR.publicKey,
+        tokenMint: COMMIT_MINT_KEYPAIR.publicKey,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc();
+
+    const balanceAfter = await provider.connection.getBalance(
+      saleStateAddress
+    );
+    assert.equal(
+      balanceBefore - balanceAfter,
+      SALE_PRICE_PER_TOKEN_LAMPORTS.muln(3).toNumber()
+    );
+
+    await program.methods
+      .claimVested(COMMIT_SALE_NAME)
+      .accounts({
+        vaultAta: null,
+        signer: BUYER_KEYPAIR.publicKey,
+        tokenMint: COMMIT_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([BUYER_KEYPAIR])
+      .rpc();
+
+    const signerAtaAddress = await getAssociatedTokenAddress(
+      COMMIT_MINT_KEYPAIR.publicKey,
+      BUYER_KEYPAIR.publicKey
+    );
+    const signerAta = await getAccount(provider.connection, signerAtaAddress);
+    assert.equal(
+      signerAta.amount.toString(),
+      (3 * Math.pow(10, DECIMALS)).toString()
+    );
+  });
+
+  it("should approve only the lottery's winning tickets", async () => {
+    await program.methods
+      .createWhitelistSale(
+        LOTTERY_SALE_NAME,
+        SALE_PRICE_PER_TOKEN_LAMPORTS,
+        new BN(10),
+        SALE_MAX_BUYERS,
+        { mint: {} },
+        0
+      )
+      .accounts({
+        vaultAta: null,
+        signerAta: null,
+        signer: OWNER_KEYPAIR.publicKey,
+        tokenMint: LOTTERY_MINT_KEYPAIR.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .setWhitelistMode(LOTTERY_SALE_NAME, { lottery: {} })
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .updateSaleState(LOTTERY_SALE_NAME, true, false)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    // Three tickets for the two spots
+    const lateBuyer = Keypair.generate();
+    await airdropSol(lateBuyer.publicKey, 100);
+
+    const buyers = [BUYER_KEYPAIR, NON_BUYER_KEYPAIR, lateBuyer];
+    for (const buyer of buyers) {
+      await program.methods
+        .registerForWhitelist(LOTTERY_SALE_NAME)
+        .accounts({
+          signer: buyer.publicKey,
+        })
+        .signers([buyer])
+        .rpc();
+    }
+
+    await program.methods
+      .drawLottery(LOTTERY_SALE_NAME)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+        randomnessAccount: SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc()
+      .then(
+        () => {
+          assert.fail(
+            "Owner should not be able to draw while registration is open!"
+          );
+        },
+        (e: SendTransactionError) => {
+          assert.ok(
+            e.logs.some((log) => log.includes("WhitelistRegistrationClosed"))
+          );
+        }
+      );
+
+    await program.methods
+      .updateSaleState(LOTTERY_SALE_NAME, false, false)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .drawLottery(LOTTERY_SALE_NAME)
+      .accounts({
+        signer: OWNER_KEYPAIR.publicKey,
+        randomnessAccount: SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .signers([OWNER_KEYPAIR])
+      .rpc();
+
+    let winners = 0;
+    for (const buyer of buyers) {
+      await program.methods
+        .settleLotteryTicket(LOTTERY_SALE_NAME)
+        .accounts({
+          signer: buyer.publicKey,
+        })
+        .signers([buyer])
+        .rpc();
+
+      // Losing tickets are closed, and winning ones are approved
+      const allowance = await program.account.allowance.fetchNullable(
+        getAllowanceAddress(LOTTERY_SALE_NAME, buyer.publicKey)
+      );
+      if (allowance) {
+        assert.ok(allowance.isApproved);
+        winners++;
+      }
+    }
+    assert.equal(winners, 2);
+
+    const saleState = await program.account.whitelistSale.fetch(
+      getSaleStateAddress(LOTTERY_SALE_NAME)
+    );
+    assert.equal(saleState.numTickets.toNumber(), 3);
+    assert.equal(saleState.numBuyers.toNumber(), 2);
+  });
+
+  it("should emit an event for every step of a sale", async () => {
+    const saleStateAddress = getSaleStateAddress(EVENTS_SALE_NAME);
+
+    let [event] = await getCpiEvents(
+      await program.methods
+        .createWhitelistSale(
+          EVENTS_SALE_NAME,
+          SALE_PRICE_PER_TOKEN_LAMPORTS,
+          new BN(10),
+          SALE_MAX_BUYERS,
+          { mint: {} },
+          0
+        )
+        .accounts({
+          vaultAta: null,
+          signerAta: null,
+          signer: OWNER_KEYPAIR.publicKey,
+          tokenMint: EVENTS_MINT_KEYPAIR.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([OWNER_KEYPAIR])
+        .rpc()
+    );
+    assert.ok(event.data.sale.equals(saleStateAddress));
+    assert.ok(event.data.tokenMint.equals(EVENTS_MINT_KEYPAIR.publicKey));
+    assert.equal(event.data.totalTokensForSale.toNumber(), 20);
+
+    [event] = await getCpiEvents(
+      await program.methods
+        .registerForWhitelist(EVENTS_SALE_NAME)
+        .accounts({
+          signer: BUYER_KEYPAIR.publicKey,
+        })
+        .signers([BUYER_KEYPAIR])
+        .rpc()
+    );
+    assert.ok(event.data.buyer.equals(BUYER_KEYPAIR.publicKey));
+    assert.ok(event.data.isApproved);
+    assert.equal(event.data.numBuyers.toNumber(), 1);
+
+    [event] = await getCpiEvents(
+      await program.methods
+        .updateSaleState(EVENTS_SALE_NAME, false, true)
+        .accounts({
+          signer: OWNER_KEYPAIR.publicKey,
+        })
+        .signers([OWNER_KEYPAIR])
+        .rpc()
+    );
+    assert.ok(event.data.actor.equals(OWNER_KEYPAIR.publicKey));
+    assert.isFalse(event.data.isRegistrationOpen);
+    assert.isTrue(event.data.isSaleOpen);
+
+    [event] = await getCpiEvents(
+      await program.methods
+        .buyTokens(EVENTS_SALE_NAME, new BN(2))
+        .accounts({
+          vaultAta: null,
+          signer: BUYER_KEYPAIR.publicKey,
+          tokenMint: EVENTS_MINT_KEYPAIR.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([BUYER_KEYPAIR])
+        .rpc()
+    );
+    assert.equal(event.data.amount.toNumber(), 2);
+    assert.ok(event.data.price.eq(SALE_PRICE_PER_TOKEN_LAMPORTS));
+    assert.ok(event.data.cost.eq(SALE_PRICE_PER_TOKEN_LAMPORTS.muln(2)));
+    assert.equal(event.data.tokensSold.toNumber(), 2);
+
+    [event] = await getCpiEvents(
+      await program.methods
+        .closeWhitelistSale(EVENTS_SALE_NAME)
+        .accounts({
+          vaultAta: null,
+          signerAta: null,
+          signer: OWNER_KEYPAIR.publicKey,
+          tokenMint: EVENTS_MINT_KEYPAIR.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([OWNER_KEYPAIR])
+        .rpc()
+    );
+    assert.equal(event.data.numBuyers.toNumber(), 1);
+    assert.ok(
+      event.data.lamportsRaised.eq(SALE_PRICE_PER_TOKEN_LAMPORTS.muln(2))
+    );
+  });
 });